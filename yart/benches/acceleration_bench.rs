@@ -0,0 +1,158 @@
+//! Compares the binary bounding box hierarchy with the wide one, and intersecting mesh triangles one by one with
+//! intersecting them in packets. Run with `--no-default-features` to compare with the scalar fallbacks of the wide
+//! hierarchy and the packets.
+
+#![feature(test)]
+
+extern crate test;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::sync::Arc;
+use test::{black_box, Bencher};
+use yart::{
+    common::{Real, TWO_PI},
+    geometries::{
+        bounding_box_hierarchy::{BoundingBoxHierarchy, BoundingBoxHierarchyOptions},
+        intersectable::Intersectable,
+        mesh_buffers::MeshBuffers,
+        mesh_triangle::MeshTriangle,
+        ray::Ray,
+        sphere::Sphere,
+        triangle::Triangle,
+        triangle_mesh::TriangleMesh,
+        wide_bounding_box_hierarchy::WideBoundingBoxHierarchy,
+    },
+    math::vector3::Vector3,
+    normalize,
+};
+
+const SPHERE_COUNT: usize = 10_000;
+const RAY_COUNT: usize = 10_000;
+
+/// The number of segments around and along the tessellated sphere, which has twice their product in triangles.
+const MESH_SEGMENTS: usize = 200;
+
+fn create_spheres() -> Vec<Sphere> {
+    let mut rng = StdRng::seed_from_u64(1);
+
+    (0..SPHERE_COUNT)
+        .map(|_| {
+            let position = Vector3::new(
+                rng.gen_range(-50.0..50.0),
+                rng.gen_range(-50.0..50.0),
+                rng.gen_range(-50.0..50.0),
+            );
+
+            Sphere::new(&position, rng.gen_range(0.1..1.0), 0)
+        })
+        .collect()
+}
+
+fn create_mesh() -> TriangleMesh {
+    let mut positions = Vec::new();
+
+    for latitude in 0..=MESH_SEGMENTS {
+        let theta = latitude as Real / MESH_SEGMENTS as Real * TWO_PI * 0.5;
+
+        for longitude in 0..=MESH_SEGMENTS {
+            let phi = longitude as Real / MESH_SEGMENTS as Real * TWO_PI;
+
+            positions.push(
+                Vector3::new(
+                    Real::sin(theta) * Real::cos(phi),
+                    Real::cos(theta),
+                    Real::sin(theta) * Real::sin(phi),
+                ) * 40.0,
+            );
+        }
+    }
+
+    let buffers = Arc::new(MeshBuffers::new(positions, Vec::new(), Vec::new(), Vec::new()));
+    let row = MESH_SEGMENTS as u32 + 1;
+
+    let mut triangles = Vec::new();
+
+    for latitude in 0..MESH_SEGMENTS as u32 {
+        for longitude in 0..MESH_SEGMENTS as u32 {
+            let index = latitude * row + longitude;
+
+            triangles.push(MeshTriangle::new([index, index + row, index + 1], None, None));
+            triangles.push(MeshTriangle::new([index + 1, index + row, index + row + 1], None, None));
+        }
+    }
+
+    TriangleMesh::new(&buffers, triangles, 0)
+}
+
+/// The triangles of the tessellated sphere as separate geometries, each with its own copy of its vertices.
+fn create_triangles() -> Vec<Triangle> {
+    let mesh = create_mesh();
+
+    mesh.triangles()
+        .iter()
+        .map(|triangle| {
+            let [vertex0, vertex1, vertex2] = [0, 1, 2].map(|x| triangle.vertex(mesh.buffers(), x));
+            let normal = normalize!((vertex1 - vertex0) % (vertex2 - vertex0));
+
+            Triangle::new(&vertex0, &vertex1, &vertex2, &normal, &normal, &normal, 0)
+        })
+        .collect()
+}
+
+/// Rays from random points around the scene towards random points inside it.
+fn create_rays() -> Vec<Ray> {
+    let mut rng = StdRng::seed_from_u64(2);
+
+    let mut random_point = |extent: Real| {
+        Vector3::new(
+            rng.gen_range(-extent..extent),
+            rng.gen_range(-extent..extent),
+            rng.gen_range(-extent..extent),
+        )
+    };
+
+    (0..RAY_COUNT)
+        .map(|_| {
+            let position = random_point(100.0);
+            let target = random_point(30.0);
+
+            Ray::new(&position, &normalize!(target - position))
+        })
+        .collect()
+}
+
+fn intersect_all(geometry: &dyn Intersectable, rays: &[Ray]) -> usize {
+    rays.iter().filter(|x| geometry.intersect(x).is_some()).count()
+}
+
+#[bench]
+fn binary_hierarchy_spheres(bench: &mut Bencher) {
+    let hierarchy = BoundingBoxHierarchy::new(create_spheres(), &BoundingBoxHierarchyOptions::default());
+    let rays = create_rays();
+
+    bench.iter(|| black_box(intersect_all(&hierarchy, &rays)));
+}
+
+#[bench]
+fn wide_hierarchy_spheres(bench: &mut Bencher) {
+    let hierarchy = WideBoundingBoxHierarchy::new(create_spheres(), &BoundingBoxHierarchyOptions::default());
+    let rays = create_rays();
+
+    bench.iter(|| black_box(intersect_all(&hierarchy, &rays)));
+}
+
+#[bench]
+fn binary_hierarchy_triangles(bench: &mut Bencher) {
+    let hierarchy = BoundingBoxHierarchy::new(create_triangles(), &BoundingBoxHierarchyOptions::default());
+    let rays = create_rays();
+
+    bench.iter(|| black_box(intersect_all(&hierarchy, &rays)));
+}
+
+#[bench]
+fn triangle_mesh_packets(bench: &mut Bencher) {
+    let mesh = create_mesh();
+    let rays = create_rays();
+
+    bench.iter(|| black_box(intersect_all(&mesh, &rays)));
+}
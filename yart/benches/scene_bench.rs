@@ -20,8 +20,9 @@ fn scene_bench(bench: &mut Bencher) {
 
         for y in 0..HEIGHT {
             for x in 0..WIDTH {
-                let ray = scene.camera.create_ray(&mut rng, (x, y), (0, 0));
-                color += scene.cast_ray_color(&mut rng, &ray, 1);
+                if let Some(ray) = scene.camera.create_ray(&mut rng, (x, y), (0, 0)) {
+                    color += scene.cast_ray_color(&mut rng, &ray, 1);
+                }
             }
         }
 
@@ -0,0 +1,85 @@
+use crate::common::{Real, PI, TWO_PI};
+use rand::{Rng, RngCore};
+
+/// The opening of a thin lens. Points on the lens are sampled uniformly within either a disk or a regular polygon
+/// which determines the shape of out of focus highlights.
+#[derive(Debug, Copy, Clone)]
+pub struct Aperture {
+    pub radius: Real,
+
+    /// The number of blades forming the aperture. Fewer than three blades gives a circular aperture.
+    pub blade_count: u32,
+
+    /// The rotation of the blades in radians.
+    pub rotation: Real,
+}
+
+impl Aperture {
+    pub fn new(radius: Real, blade_count: u32, rotation: Real) -> Self {
+        Self {
+            radius,
+            blade_count,
+            rotation,
+        }
+    }
+
+    /// Creates a pinhole aperture which keeps everything in focus.
+    pub fn pinhole() -> Self {
+        Self::new(0.0, 0, 0.0)
+    }
+
+    pub fn is_pinhole(&self) -> bool {
+        self.radius <= 0.0
+    }
+
+    /// Samples a point on the aperture. The returned offsets are in the same units as the radius.
+    pub fn sample(&self, rng: &mut dyn RngCore) -> (Real, Real) {
+        if self.blade_count < 3 {
+            return self.sample_disk(rng);
+        }
+
+        // Every blade contributes an identical triangle between the center and two adjacent corners, so pick one of
+        // them and then sample it uniformly.
+        let blade_angle = TWO_PI / self.blade_count as Real;
+        let blade = usize::min(
+            (rng.gen::<Real>() * self.blade_count as Real) as usize,
+            self.blade_count as usize - 1,
+        );
+
+        let angle1 = self.rotation + blade as Real * blade_angle;
+        let angle2 = angle1 + blade_angle;
+
+        let mut a = rng.gen::<Real>();
+        let mut b = rng.gen::<Real>();
+
+        if a + b > 1.0 {
+            a = 1.0 - a;
+            b = 1.0 - b;
+        }
+
+        let x = a * Real::cos(angle1) + b * Real::cos(angle2);
+        let y = a * Real::sin(angle1) + b * Real::sin(angle2);
+
+        (x * self.radius, y * self.radius)
+    }
+
+    fn sample_disk(&self, rng: &mut dyn RngCore) -> (Real, Real) {
+        // Concentric mapping from the unit square to the unit disk which keeps the samples well stratified.
+        let x = rng.gen::<Real>() * 2.0 - 1.0;
+        let y = rng.gen::<Real>() * 2.0 - 1.0;
+
+        if x == 0.0 && y == 0.0 {
+            return (0.0, 0.0);
+        }
+
+        let (r, theta) = if Real::abs(x) > Real::abs(y) {
+            (x, (PI * 0.25) * (y / x))
+        } else {
+            (y, (PI * 0.5) - (PI * 0.25) * (x / y))
+        };
+
+        let theta = theta + self.rotation;
+
+        (r * Real::cos(theta) * self.radius, r * Real::sin(theta) * self.radius)
+    }
+}
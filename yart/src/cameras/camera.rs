@@ -1,9 +1,82 @@
-use crate::geometries::ray::Ray;
-use rand::RngCore;
+use crate::{
+    common::Real,
+    geometries::ray::Ray,
+    math::{vector::Vector, vector3::Vector3},
+    normalize,
+};
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
 
-pub trait Camera {
+pub trait Camera: Send + Sync {
     fn screen_size(&self) -> (u32, u32);
     fn subpixel_count(&self) -> u32;
 
-    fn create_ray(&self, rng: &mut dyn RngCore, pixel: (u32, u32), subpixel: (u32, u32)) -> Ray;
+    /// Creates the ray for a subpixel. Returns `None` for pixels the camera doesn't see anything through, such as the
+    /// area outside the image circle of a fisheye lens, which are left transparent.
+    fn create_ray(&self, rng: &mut dyn RngCore, pixel: (u32, u32), subpixel: (u32, u32)) -> Option<Ray>;
+
+    /// Returns the ray whose first hit determines the focus distance, or `None` if the camera doesn't autofocus.
+    fn autofocus_ray(&self) -> Option<Ray> {
+        None
+    }
+
+    fn set_focus_distance(&mut self, _focus_distance: Real) {}
+
+    /// Returns the factor the radiance arriving along a ray created by this camera is multiplied by before it's stored
+    /// in the image. Physical cameras use this for their exposure and vignetting.
+    fn calculate_exposure(&self, _ray: &Ray) -> Real {
+        1.0
+    }
+}
+
+/// Builds the orthonormal right, up and forward vectors of a camera at `position` looking towards `look_at`.
+pub fn build_camera_basis(position: &Vector3, look_at: &Vector3, up: &Vector3) -> (Vector3, Vector3, Vector3) {
+    let forward = normalize!(look_at - position);
+
+    let right = normalize!(up % forward);
+    let up = normalize!(forward % right);
+
+    (right, up, forward)
+}
+
+/// Picks a random position within a subpixel. The position is returned in normalized image coordinates where `(0, 0)`
+/// is the upper left corner of the image and `(1, 1)` is the lower right corner.
+pub fn sample_image_position(
+    rng: &mut dyn RngCore,
+    screen_size: (u32, u32),
+    subpixel_count: u32,
+    pixel: (u32, u32),
+    subpixel: (u32, u32),
+) -> (Real, Real) {
+    let subpixel_count = subpixel_count as Real;
+
+    let x = (pixel.0 as Real) + ((subpixel.0 as Real) + rng.gen::<Real>()) / subpixel_count;
+    let y = (pixel.1 as Real) + ((subpixel.1 as Real) + rng.gen::<Real>()) / subpixel_count;
+
+    (x / (screen_size.0 as Real), y / (screen_size.1 as Real))
+}
+
+/// Estimates the cone a camera ray stands for from the rays through two neighboring pixels in the middle of the image.
+/// Returns the width of the cone at the camera, which is the distance between the rays' positions, and how much it
+/// widens per unit of distance, which is the angle between their directions. Both are zero if the camera doesn't see
+/// anything through those pixels.
+pub fn estimate_ray_cone(camera: &dyn Camera) -> (Real, Real) {
+    let (width, height) = camera.screen_size();
+    let pixel = (width / 2, height / 2);
+
+    // Use the same random numbers for both rays so that they only differ by the pixel they go through.
+    let create_ray = |pixel: (u32, u32)| camera.create_ray(&mut StdRng::seed_from_u64(0), pixel, (0, 0));
+
+    match (create_ray(pixel), create_ray((pixel.0 + 1, pixel.1))) {
+        (Some(ray), Some(neighbor_ray)) => {
+            let cone_width = (neighbor_ray.position() - ray.position()).length();
+            let cone_spread = Real::acos(Real::clamp(
+                normalize!(*ray.direction()) ^ normalize!(*neighbor_ray.direction()),
+                -1.0,
+                1.0,
+            ));
+
+            (cone_width, cone_spread)
+        }
+        _ => (0.0, 0.0),
+    }
 }
@@ -0,0 +1,69 @@
+use super::{
+    camera::{sample_image_position, Camera},
+    shutter::Shutter,
+};
+use crate::{common::Real, geometries::ray::Ray, math::vector3::Vector3, normalize};
+use rand::RngCore;
+
+/// The forward and up directions of each face of a cubemap in the order `+x`, `-x`, `+y`, `-y`, `+z`, `-z`.
+const FACES: [(Vector3, Vector3); 6] = [
+    (Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0)),
+    (Vector3::new(-1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0)),
+    (Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 0.0, -1.0)),
+    (Vector3::new(0.0, -1.0, 0.0), Vector3::new(0.0, 0.0, 1.0)),
+    (Vector3::new(0.0, 0.0, 1.0), Vector3::new(0.0, 1.0, 0.0)),
+    (Vector3::new(0.0, 0.0, -1.0), Vector3::new(0.0, 1.0, 0.0)),
+];
+
+/// A camera that renders the six 90 degree faces of a cube around its position, for example to capture an
+/// environment probe. The faces are aligned with the world axes and laid out side by side in a horizontal strip in
+/// the order `+x`, `-x`, `+y`, `-y`, `+z`, `-z`, so the image should be six times as wide as it is tall.
+pub struct CubemapCamera {
+    pub position: Vector3,
+
+    pub screen_size: (u32, u32),
+    pub subpixel_count: u32,
+
+    pub shutter: Shutter,
+}
+
+impl CubemapCamera {
+    pub fn new(position: &Vector3, subpixel_count: u32, screen_size: (u32, u32), shutter: &Shutter) -> CubemapCamera {
+        Self {
+            position: *position,
+            screen_size,
+            subpixel_count,
+            shutter: *shutter,
+        }
+    }
+}
+
+impl Camera for CubemapCamera {
+    fn screen_size(&self) -> (u32, u32) {
+        self.screen_size
+    }
+
+    fn subpixel_count(&self) -> u32 {
+        self.subpixel_count
+    }
+
+    fn create_ray(&self, rng: &mut dyn RngCore, pixel: (u32, u32), subpixel: (u32, u32)) -> Option<Ray> {
+        let (x, y) = sample_image_position(rng, self.screen_size, self.subpixel_count, pixel, subpixel);
+
+        let face_x = x * FACES.len() as Real;
+        let face_index = usize::min(face_x as usize, FACES.len() - 1);
+        let (forward, up) = FACES[face_index];
+
+        let right = up % forward;
+
+        // Map the position within the face to [-1, 1] which covers exactly 90 degrees.
+        let u = (face_x - face_index as Real) * 2.0 - 1.0;
+        let v = 1.0 - y * 2.0;
+
+        Some(Ray::new_at_time(
+            &self.position,
+            &normalize!(forward + right * u + up * v),
+            self.shutter.sample_time(rng),
+        ))
+    }
+}
@@ -0,0 +1,91 @@
+use super::{
+    camera::{build_camera_basis, sample_image_position, Camera},
+    shutter::Shutter,
+};
+use crate::{
+    common::{Real, PI, TWO_PI},
+    geometries::ray::Ray,
+    math::vector3::Vector3,
+};
+use rand::RngCore;
+
+/// A camera that sees in every direction and maps them onto a latitude-longitude image covering 360 degrees
+/// horizontally and 180 degrees vertically. The look at direction is at the center of the image.
+pub struct EquirectangularCamera {
+    right: Vector3,
+    true_up: Vector3,
+    forward: Vector3,
+
+    pub position: Vector3,
+    pub look_at: Vector3,
+    pub up: Vector3,
+
+    pub screen_size: (u32, u32),
+    pub subpixel_count: u32,
+
+    pub shutter: Shutter,
+}
+
+impl EquirectangularCamera {
+    pub fn new(
+        position: &Vector3,
+        look_at: &Vector3,
+        up: &Vector3,
+        subpixel_count: u32,
+        screen_size: (u32, u32),
+        shutter: &Shutter,
+    ) -> EquirectangularCamera {
+        let (right, true_up, forward) = build_camera_basis(position, look_at, up);
+
+        Self {
+            right,
+            true_up,
+            forward,
+            position: *position,
+            look_at: *look_at,
+            up: *up,
+            screen_size,
+            subpixel_count,
+            shutter: *shutter,
+        }
+    }
+
+    /// Calculates the direction seen through a position in normalized image coordinates.
+    pub fn calculate_direction(&self, x: Real, y: Real) -> Vector3 {
+        let longitude = (x - 0.5) * TWO_PI;
+        let latitude = (0.5 - y) * PI;
+
+        let cos_latitude = Real::cos(latitude);
+
+        (self.right * Real::sin(longitude) + self.forward * Real::cos(longitude)) * cos_latitude
+            + self.true_up * Real::sin(latitude)
+    }
+
+    /// Calculates the horizontal direction perpendicular to the direction seen through a column of the image. This
+    /// is the direction the eyes are offset in when rendering stereo images.
+    pub fn calculate_tangent(&self, x: Real) -> Vector3 {
+        let longitude = (x - 0.5) * TWO_PI;
+
+        self.right * Real::cos(longitude) - self.forward * Real::sin(longitude)
+    }
+}
+
+impl Camera for EquirectangularCamera {
+    fn screen_size(&self) -> (u32, u32) {
+        self.screen_size
+    }
+
+    fn subpixel_count(&self) -> u32 {
+        self.subpixel_count
+    }
+
+    fn create_ray(&self, rng: &mut dyn RngCore, pixel: (u32, u32), subpixel: (u32, u32)) -> Option<Ray> {
+        let (x, y) = sample_image_position(rng, self.screen_size, self.subpixel_count, pixel, subpixel);
+
+        Some(Ray::new_at_time(
+            &self.position,
+            &self.calculate_direction(x, y),
+            self.shutter.sample_time(rng),
+        ))
+    }
+}
@@ -0,0 +1,45 @@
+use crate::common::Real;
+
+/// The exposure settings of a physical camera. Together they determine how much of the radiance arriving at the
+/// sensor ends up in the image which lets scenes lit with physically specified lights produce a sensible image.
+#[derive(Debug, Copy, Clone)]
+pub struct Exposure {
+    pub iso: Real,
+
+    /// How long the sensor is exposed for in seconds.
+    pub shutter_speed: Real,
+
+    pub f_number: Real,
+}
+
+impl Exposure {
+    pub fn new(iso: Real, shutter_speed: Real, f_number: Real) -> Self {
+        Self {
+            iso,
+            shutter_speed,
+            f_number,
+        }
+    }
+
+    /// Calculates the exposure value of the settings relative to ISO 100.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx::*;
+    /// # use yart::cameras::exposure::*;
+    /// #
+    /// let sunny_16 = Exposure::new(100.0, 1.0 / 125.0, 16.0);
+    ///
+    /// assert_relative_eq!(14.965784284662087, sunny_16.calculate_ev100());
+    /// ```
+    pub fn calculate_ev100(&self) -> Real {
+        Real::log2(self.f_number * self.f_number / self.shutter_speed * 100.0 / self.iso)
+    }
+
+    /// Calculates the factor radiance is multiplied by to get the value stored in the image. This uses the saturation
+    /// based sensitivity so that a radiance which would saturate the sensor maps to one.
+    pub fn calculate_scale(&self) -> Real {
+        Real::recip(1.2 * Real::exp2(self.calculate_ev100()))
+    }
+}
@@ -0,0 +1,122 @@
+use super::{
+    camera::{build_camera_basis, sample_image_position, Camera},
+    shutter::Shutter,
+};
+use crate::{common::Real, geometries::ray::Ray, math::vector3::Vector3};
+use rand::RngCore;
+
+/// How a fisheye lens maps the angle between a direction and the view direction to a distance from the center of the
+/// image.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FisheyeProjection {
+    /// The distance from the center of the image is proportional to the angle.
+    Equidistant,
+
+    /// Equal solid angles cover equal areas of the image.
+    Equisolid,
+}
+
+/// A camera with a fisheye lens. The image circle is centered in the image and touches its shorter sides. Pixels
+/// outside of the image circle don't see anything.
+pub struct FisheyeCamera {
+    right: Vector3,
+    true_up: Vector3,
+    forward: Vector3,
+
+    pub position: Vector3,
+    pub look_at: Vector3,
+    pub up: Vector3,
+
+    pub screen_size: (u32, u32),
+    pub subpixel_count: u32,
+
+    /// The angle, in radians, covered by the diameter of the image circle.
+    pub field_of_view: Real,
+    pub projection: FisheyeProjection,
+
+    pub shutter: Shutter,
+}
+
+impl FisheyeCamera {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        position: &Vector3,
+        look_at: &Vector3,
+        up: &Vector3,
+        subpixel_count: u32,
+        screen_size: (u32, u32),
+        field_of_view: Real,
+        projection: FisheyeProjection,
+        shutter: &Shutter,
+    ) -> FisheyeCamera {
+        let (right, true_up, forward) = build_camera_basis(position, look_at, up);
+
+        Self {
+            right,
+            true_up,
+            forward,
+            position: *position,
+            look_at: *look_at,
+            up: *up,
+            screen_size,
+            subpixel_count,
+            field_of_view,
+            projection,
+            shutter: *shutter,
+        }
+    }
+
+    /// Converts a distance from the center of the image circle, where the edge of the circle is at one, to the angle
+    /// from the view direction.
+    fn calculate_angle(&self, radius: Real) -> Real {
+        let half_field_of_view = self.field_of_view * 0.5;
+
+        match self.projection {
+            FisheyeProjection::Equidistant => radius * half_field_of_view,
+            FisheyeProjection::Equisolid => {
+                2.0 * Real::asin(Real::clamp(radius * Real::sin(half_field_of_view * 0.5), -1.0, 1.0))
+            }
+        }
+    }
+}
+
+impl Camera for FisheyeCamera {
+    fn screen_size(&self) -> (u32, u32) {
+        self.screen_size
+    }
+
+    fn subpixel_count(&self) -> u32 {
+        self.subpixel_count
+    }
+
+    fn create_ray(&self, rng: &mut dyn RngCore, pixel: (u32, u32), subpixel: (u32, u32)) -> Option<Ray> {
+        let (x, y) = sample_image_position(rng, self.screen_size, self.subpixel_count, pixel, subpixel);
+
+        // Scale the image so that the image circle has a radius of one.
+        let width = self.screen_size.0 as Real;
+        let height = self.screen_size.1 as Real;
+        let scale = Real::min(width, height) * 0.5;
+
+        let circle_x = (x - 0.5) * width / scale;
+        let circle_y = (0.5 - y) * height / scale;
+
+        let radius = Real::sqrt(circle_x * circle_x + circle_y * circle_y);
+
+        if radius > 1.0 {
+            return None;
+        }
+
+        let theta = self.calculate_angle(radius);
+        let phi = Real::atan2(circle_y, circle_x);
+
+        let sin_theta = Real::sin(theta);
+        let direction =
+            self.forward * Real::cos(theta) + (self.right * Real::cos(phi) + self.true_up * Real::sin(phi)) * sin_theta;
+
+        Some(Ray::new_at_time(
+            &self.position,
+            &direction,
+            self.shutter.sample_time(rng),
+        ))
+    }
+}
@@ -1,2 +1,10 @@
+pub mod aperture;
 pub mod camera;
+pub mod cubemap_camera;
+pub mod equirectangular_camera;
+pub mod exposure;
+pub mod fisheye_camera;
+pub mod omni_directional_stereo_camera;
+pub mod orthographic_camera;
 pub mod perspective_camera;
+pub mod shutter;
@@ -0,0 +1,71 @@
+use super::{
+    camera::{sample_image_position, Camera},
+    equirectangular_camera::EquirectangularCamera,
+    shutter::Shutter,
+};
+use crate::{common::Real, geometries::ray::Ray, math::vector3::Vector3};
+use rand::RngCore;
+
+/// An equirectangular camera that renders a stereo pair for viewing in VR. The left eye is rendered to the top half of
+/// the image and the right eye to the bottom half. For every direction the eyes sit on a circle whose diameter is the
+/// interpupillary distance so that the stereo effect holds no matter which way the viewer looks.
+pub struct OmniDirectionalStereoCamera {
+    equirectangular_camera: EquirectangularCamera,
+
+    /// The distance between the eyes in scene units.
+    pub interpupillary_distance: Real,
+}
+
+impl OmniDirectionalStereoCamera {
+    pub fn new(
+        position: &Vector3,
+        look_at: &Vector3,
+        up: &Vector3,
+        subpixel_count: u32,
+        screen_size: (u32, u32),
+        shutter: &Shutter,
+        interpupillary_distance: Real,
+    ) -> OmniDirectionalStereoCamera {
+        Self {
+            equirectangular_camera: EquirectangularCamera::new(
+                position,
+                look_at,
+                up,
+                subpixel_count,
+                screen_size,
+                shutter,
+            ),
+            interpupillary_distance,
+        }
+    }
+}
+
+impl Camera for OmniDirectionalStereoCamera {
+    fn screen_size(&self) -> (u32, u32) {
+        self.equirectangular_camera.screen_size
+    }
+
+    fn subpixel_count(&self) -> u32 {
+        self.equirectangular_camera.subpixel_count
+    }
+
+    fn create_ray(&self, rng: &mut dyn RngCore, pixel: (u32, u32), subpixel: (u32, u32)) -> Option<Ray> {
+        let camera = &self.equirectangular_camera;
+        let (x, y) = sample_image_position(rng, camera.screen_size, camera.subpixel_count, pixel, subpixel);
+
+        // Each eye gets half of the image vertically.
+        let (eye_y, eye_offset) = if y < 0.5 {
+            (y * 2.0, -0.5 * self.interpupillary_distance)
+        } else {
+            ((y - 0.5) * 2.0, 0.5 * self.interpupillary_distance)
+        };
+
+        let ray_position = camera.position + camera.calculate_tangent(x) * eye_offset;
+
+        Some(Ray::new_at_time(
+            &ray_position,
+            &camera.calculate_direction(x, eye_y),
+            camera.shutter.sample_time(rng),
+        ))
+    }
+}
@@ -0,0 +1,111 @@
+use super::{camera::Camera, shutter::Shutter};
+use crate::{
+    common::Real,
+    geometries::ray::Ray,
+    math::{vector2::Vector2, vector3::Vector3},
+    normalize,
+};
+use rand::{Rng, RngCore};
+
+/// A camera that casts parallel rays from a rectangle centered on the camera's position. Lines that are parallel in
+/// the scene stay parallel in the image which makes it useful for architectural and technical drawings.
+pub struct OrthographicCamera {
+    reciprical_width: Real,
+    reciprical_height: Real,
+
+    subpixel_size_x: Real,
+    subpixel_size_y: Real,
+
+    du: Vector3,
+    dv: Vector3,
+
+    direction: Vector3,
+    upper_left_corner: Vector3,
+
+    pub position: Vector3,
+    pub look_at: Vector3,
+    pub up: Vector3,
+
+    /// The width and height, in world units, of the rectangle rays are cast from.
+    pub ortho_size: Vector2,
+
+    pub screen_size: (u32, u32),
+    pub subpixel_count: u32,
+
+    pub shutter: Shutter,
+}
+
+impl OrthographicCamera {
+    pub fn new(
+        position: &Vector3,
+        look_at: &Vector3,
+        up: &Vector3,
+        ortho_size: &Vector2,
+        subpixel_count: u32,
+        screen_size: (u32, u32),
+        shutter: &Shutter,
+    ) -> OrthographicCamera {
+        let forward = normalize!(position - look_at);
+
+        let u = normalize!(up % forward);
+        let v = normalize!(forward % u);
+
+        let du = ortho_size.x * u;
+        let dv = ortho_size.y * v;
+
+        let upper_left_corner = position - du * 0.5 + dv * 0.5;
+
+        let reciprical_width = Real::recip(screen_size.0 as Real);
+        let reciprical_height = Real::recip(screen_size.1 as Real);
+
+        let subpixel_size_x = Real::recip(subpixel_count as Real) * reciprical_width;
+        let subpixel_size_y = Real::recip(subpixel_count as Real) * reciprical_height;
+
+        Self {
+            reciprical_width,
+            reciprical_height,
+            subpixel_size_x,
+            subpixel_size_y,
+            du,
+            dv,
+            direction: -forward,
+            upper_left_corner,
+            position: *position,
+            look_at: *look_at,
+            up: *up,
+            ortho_size: *ortho_size,
+            screen_size,
+            subpixel_count,
+            shutter: *shutter,
+        }
+    }
+}
+
+impl Camera for OrthographicCamera {
+    fn screen_size(&self) -> (u32, u32) {
+        self.screen_size
+    }
+
+    fn subpixel_count(&self) -> u32 {
+        self.subpixel_count
+    }
+
+    fn create_ray(&self, rng: &mut dyn RngCore, pixel: (u32, u32), subpixel: (u32, u32)) -> Option<Ray> {
+        let mut normalized_x = ((self.screen_size.0 as Real) - (pixel.0 as Real) - 1.0) * self.reciprical_width;
+        let mut normalized_y = (pixel.1 as Real) * self.reciprical_height;
+
+        normalized_x += (subpixel.0 as Real) * self.subpixel_size_x;
+        normalized_y += (subpixel.1 as Real) * self.subpixel_size_y;
+
+        normalized_x += rng.gen::<Real>() * self.subpixel_size_x;
+        normalized_y += rng.gen::<Real>() * self.subpixel_size_y;
+
+        let ray_position = self.upper_left_corner + (normalized_x * self.du) - (normalized_y * self.dv);
+
+        Some(Ray::new_at_time(
+            &ray_position,
+            &self.direction,
+            self.shutter.sample_time(rng),
+        ))
+    }
+}
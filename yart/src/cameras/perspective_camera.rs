@@ -1,7 +1,10 @@
-use super::camera::Camera;
+use super::{aperture::Aperture, camera::Camera, exposure::Exposure, shutter::Shutter};
 use crate::{common::Real, geometries::ray::Ray, math::vector3::Vector3, normalize};
 use rand::{Rng, RngCore};
 
+/// A camera that casts rays through a thin lens. When the aperture is a pinhole every ray starts at the camera's
+/// position and everything is in focus. Otherwise rays start on the lens and converge on the plane that is
+/// `focus_distance` in front of the camera.
 pub struct PerspectiveCamera {
     reciprical_width: Real,
     reciprical_height: Real,
@@ -12,8 +15,14 @@ pub struct PerspectiveCamera {
     du: Vector3,
     dv: Vector3,
 
+    u: Vector3,
+    v: Vector3,
+    forward: Vector3,
+
     upper_left_corner: Vector3,
 
+    exposure_scale: Real,
+
     pub position: Vector3,
     pub look_at: Vector3,
     pub up: Vector3,
@@ -21,9 +30,26 @@ pub struct PerspectiveCamera {
     pub screen_size: (u32, u32),
     pub subpixel_count: u32,
     pub field_of_view: Real,
+
+    pub aperture: Aperture,
+    pub focus_distance: Real,
+
+    /// When set the focus distance is replaced with the distance to the first surface through the center of the image
+    /// once the scene has been loaded.
+    pub autofocus: bool,
+
+    pub shutter: Shutter,
+
+    /// The physical exposure settings of the camera. Without them radiance is stored in the image unchanged.
+    pub exposure: Option<Exposure>,
+
+    /// How strongly the image darkens towards its edges following the natural cos^4 falloff of a lens. Zero disables
+    /// vignetting and one applies the full falloff.
+    pub vignetting: Real,
 }
 
 impl PerspectiveCamera {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         position: &Vector3,
         look_at: &Vector3,
@@ -31,6 +57,12 @@ impl PerspectiveCamera {
         subpixel_count: u32,
         screen_size: (u32, u32),
         field_of_view: Real,
+        aperture: &Aperture,
+        focus_distance: Real,
+        autofocus: bool,
+        shutter: &Shutter,
+        exposure: Option<&Exposure>,
+        vignetting: Real,
     ) -> PerspectiveCamera {
         let forward = normalize!(position - look_at);
 
@@ -61,13 +93,23 @@ impl PerspectiveCamera {
             subpixel_size_y,
             du,
             dv,
+            u,
+            v,
+            forward,
             upper_left_corner,
+            exposure_scale: exposure.map_or(1.0, |x| x.calculate_scale()),
             position: *position,
             look_at: *look_at,
             up: *up,
             screen_size,
             subpixel_count,
             field_of_view,
+            aperture: *aperture,
+            focus_distance,
+            autofocus,
+            shutter: *shutter,
+            exposure: exposure.copied(),
+            vignetting,
         }
     }
 }
@@ -81,7 +123,7 @@ impl Camera for PerspectiveCamera {
         self.subpixel_count
     }
 
-    fn create_ray(&self, rng: &mut dyn RngCore, pixel: (u32, u32), subpixel: (u32, u32)) -> Ray {
+    fn create_ray(&self, rng: &mut dyn RngCore, pixel: (u32, u32), subpixel: (u32, u32)) -> Option<Ray> {
         let mut normalized_x = ((self.screen_size.0 as Real) - (pixel.0 as Real) - 1.0) * self.reciprical_width;
         let mut normalized_y = (pixel.1 as Real) * self.reciprical_height;
 
@@ -94,6 +136,45 @@ impl Camera for PerspectiveCamera {
         let ray_direction =
             normalize!(self.upper_left_corner + (normalized_x * self.du) - (normalized_y * self.dv) - self.position);
 
-        Ray::new(&self.position, &ray_direction)
+        let time = self.shutter.sample_time(rng);
+
+        if self.aperture.is_pinhole() {
+            return Some(Ray::new_at_time(&self.position, &ray_direction, time));
+        }
+
+        // Find where the pinhole ray crosses the focal plane and aim a ray from a random point on the lens at it.
+        let focus_point = self.position + ray_direction * (self.focus_distance / -(ray_direction ^ self.forward));
+
+        let (lens_x, lens_y) = self.aperture.sample(rng);
+        let ray_position = self.position + self.u * lens_x + self.v * lens_y;
+
+        Some(Ray::new_at_time(
+            &ray_position,
+            &normalize!(focus_point - ray_position),
+            time,
+        ))
+    }
+
+    fn autofocus_ray(&self) -> Option<Ray> {
+        if self.autofocus {
+            Some(Ray::new_at_time(&self.position, &-self.forward, self.shutter.open))
+        } else {
+            None
+        }
+    }
+
+    fn set_focus_distance(&mut self, focus_distance: Real) {
+        self.focus_distance = focus_distance;
+    }
+
+    fn calculate_exposure(&self, ray: &Ray) -> Real {
+        if self.vignetting <= 0.0 {
+            return self.exposure_scale;
+        }
+
+        let cos_theta = -(normalize!(*ray.direction()) ^ self.forward);
+        let falloff = cos_theta * cos_theta * cos_theta * cos_theta;
+
+        self.exposure_scale * (1.0 - self.vignetting + self.vignetting * falloff)
     }
 }
@@ -0,0 +1,41 @@
+use crate::common::Real;
+use rand::{Rng, RngCore};
+
+/// The interval of the scene's animation during which a camera's shutter is open. Every ray a camera casts is given a
+/// time within this interval so that moving geometry is blurred along its path. Moving geometry starts moving at time
+/// zero and stops at time one, which is all its bounding box covers, so the interval is clamped to that range.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Shutter {
+    pub open: Real,
+    pub close: Real,
+}
+
+impl Shutter {
+    /// Creates a shutter that is open between two times, which are clamped to the interval moving geometry moves in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use yart::cameras::shutter::*;
+    /// #
+    /// let shutter = Shutter::new(-0.5, 2.0);
+    ///
+    /// assert_eq!(0.0, shutter.open);
+    /// assert_eq!(1.0, shutter.close);
+    /// ```
+    pub fn new(open: Real, close: Real) -> Self {
+        Self {
+            open: open.clamp(0.0, 1.0),
+            close: close.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Picks a uniformly distributed time while the shutter is open.
+    pub fn sample_time(&self, rng: &mut dyn RngCore) -> Real {
+        if self.close <= self.open {
+            return self.open;
+        }
+
+        self.open + rng.gen::<Real>() * (self.close - self.open)
+    }
+}
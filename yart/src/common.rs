@@ -1,20 +1,42 @@
-pub type Real = f64;
-
-pub const NORMAL_BUMP: Real = 0.0001;
-pub const EPSILON: Real = 0.0001;
-
-pub const PI: Real =
-    3.1415926535897932384626433832795028841971693993751058209749445923078164062862089986280348253421170679821480865132;
-pub const TWO_PI: Real =
-    6.2831853071795864769252867665590057683943387987502116419498891846156328125724179972560696506842341359642961730265;
-pub const FOUR_PI: Real =
-    12.566370614359172953850573533118011536788677597500423283899778369231265625144835994512139301368468271928592346053;
-pub const EIGHT_PI: Real = 25.132741228718345907701147066236023073577355195000846567799556738;
-
-pub const ONE_OVER_PI: Real =
-    0.3183098861837906715377675267450287240689192914809128974953346881177935952684530701802276055325061719121456854535;
-pub const ONE_OVER_TWO_PI: Real =
-    0.1591549430918953357688837633725143620344596457404564487476673440588967976342265350901138027662530859560728427267;
-pub const ONE_OVER_FOUR_PI: Real =
-    0.0795774715459476678844418816862571810172298228702282243738336720294483988171132675450569013831265429780364213633;
-pub const ONE_OVER_EIGHT_PI: Real = 0.0397887357729738339422209408431285905086149114351141121869168360;
+/// Defines [`Real`] and the constants that depend on its precision. The precision is picked with the `f32` feature,
+/// which trades accuracy for speed and memory, and is `f64` otherwise.
+macro_rules! define_precision {
+    ($real:ident, epsilon: $epsilon:expr, minimum_surface_error: $minimum_surface_error:expr) => {
+        pub type Real = $real;
+
+        pub const EPSILON: Real = $epsilon;
+
+        /// The absolute error assumed for positions on surfaces close to the origin, where the error relative to
+        /// the position alone would be too small to cover the errors made while intersecting the surface.
+        pub const MINIMUM_SURFACE_ERROR: Real = $minimum_surface_error;
+
+        pub const PI: Real = std::$real::consts::PI;
+        pub const TWO_PI: Real = std::$real::consts::TAU;
+        pub const FOUR_PI: Real = 4.0 * std::$real::consts::PI;
+        pub const EIGHT_PI: Real = 8.0 * std::$real::consts::PI;
+
+        pub const ONE_OVER_PI: Real = std::$real::consts::FRAC_1_PI;
+        pub const ONE_OVER_TWO_PI: Real = 0.5 * std::$real::consts::FRAC_1_PI;
+        pub const ONE_OVER_FOUR_PI: Real = 0.25 * std::$real::consts::FRAC_1_PI;
+        pub const ONE_OVER_EIGHT_PI: Real = 0.125 * std::$real::consts::FRAC_1_PI;
+    };
+}
+
+#[cfg(not(feature = "f32"))]
+define_precision!(f64, epsilon: 0.0001, minimum_surface_error: 1.0e-9);
+
+#[cfg(feature = "f32")]
+define_precision!(f32, epsilon: 0.001, minimum_surface_error: 1.0e-5);
+
+/// The largest relative error of rounding the result of a single operation.
+pub const MACHINE_EPSILON: Real = Real::EPSILON * 0.5;
+
+/// Returns a bound on the relative error of a result computed with `operation_count` rounded operations in a row.
+pub const fn gamma(operation_count: u32) -> Real {
+    let error = operation_count as Real * MACHINE_EPSILON;
+    error / (1.0 - error)
+}
+
+/// The error of a position on a surface relative to its magnitude. Positions on surfaces are found by intersecting
+/// rays with them, which takes a few dozen operations for the more involved geometries and their transforms.
+pub const SURFACE_ERROR_SCALE: Real = gamma(64);
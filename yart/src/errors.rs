@@ -20,15 +20,20 @@ impl Error {
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self.kind {
-            ErrorKind::YamlParserError { type_name, node } => {
+            ErrorKind::YamlParserError { type_name, .. } => {
                 write!(f, "An error occurred while parsing {}.", type_name)
             }
-            _ => write!(f, "An error occurred."),
         }
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.inner_error
+            .as_ref()
+            .map(|x| x.as_ref() as &(dyn std::error::Error + 'static))
+    }
+}
 
 #[derive(Debug, Clone)]
 pub enum ErrorKind {
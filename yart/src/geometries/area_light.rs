@@ -2,7 +2,7 @@ use crate::{common::Real, math::vector3::Vector3, scene::Scene};
 use rand::RngCore;
 use std::fmt::Debug;
 
-pub trait AreaLight: Debug {
+pub trait AreaLight: Debug + Send + Sync {
     fn get_direction_towards_light(
         &self,
         rng: &mut dyn RngCore,
@@ -19,6 +19,7 @@ pub trait AreaLight: Debug {
         hit_position: &Vector3,
         hit_normal: &Vector3,
         point_on_light: &Vector3,
+        time: Real,
     ) -> bool;
 
     fn calculate_inverse_pdf(
@@ -1,7 +1,20 @@
-use crate::geometries::bounding_box::BoundingBox;
+use crate::geometries::{bounding_box::BoundingBox, intersectable::Intersectable};
+use std::sync::Arc;
 
 pub trait BoundByBox {
     fn calculate_bounding_box(&self) -> BoundingBox {
         BoundingBox::new_infinity()
     }
 }
+
+impl BoundByBox for Arc<dyn Intersectable> {
+    fn calculate_bounding_box(&self) -> BoundingBox {
+        self.as_ref().calculate_bounding_box()
+    }
+}
+
+impl BoundByBox for Box<dyn Intersectable> {
+    fn calculate_bounding_box(&self) -> BoundingBox {
+        self.as_ref().calculate_bounding_box()
+    }
+}
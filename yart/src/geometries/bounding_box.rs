@@ -1,7 +1,7 @@
 use crate::{
     common::Real,
     geometries::{bound_by_box::BoundByBox, bounding_volume::BoundingVolume, intersectable::Intersectable, ray::Ray},
-    math::{vector::Vector, vector3::Vector3},
+    math::{matrix4::Matrix4, vector::Vector, vector3::Vector3},
 };
 
 /// Represents an AABB (Axis Aligned Bounding Box) that encloses points or geometry.
@@ -325,7 +325,84 @@ impl BoundingBox {
         self.minimum + (self.maximum - self.minimum) * 0.5
     }
 
-    /// Determines if a [`Ray`] intersects with the [`BoundingBox`].
+    /// Creates a new [`BoundingBox`] that encloses this one after it has been transformed. Boxes that extend to
+    /// infinity stay infinite since their corners can't be transformed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use yart::geometries::bounding_box::BoundingBox;
+    /// # use yart::math::{matrix4::Matrix4, vector::Vector, vector3::Vector3};
+    /// #
+    /// let bounding_box = BoundingBox::new(&Vector3::from_value(-1.0), &Vector3::from_value(1.0));
+    /// let transformation = Matrix4::from_translation(&Vector3::new(5.0, 0.0, 0.0)) *
+    ///     Matrix4::from_scale(&Vector3::new(2.0, 1.0, 1.0));
+    ///
+    /// let result = bounding_box.transform(&transformation);
+    ///
+    /// assert_eq!(Vector3::new(3.0, -1.0, -1.0), result.minimum);
+    /// assert_eq!(Vector3::new(7.0, 1.0, 1.0), result.maximum);
+    /// ```
+    pub fn transform(&self, transformation: &Matrix4) -> BoundingBox {
+        if !self.is_finite() {
+            return BoundingBox::new_infinity();
+        }
+
+        let corners = (0..8)
+            .map(|corner| {
+                let x = if corner & 1 == 0 {
+                    self.minimum.x
+                } else {
+                    self.maximum.x
+                };
+                let y = if corner & 2 == 0 {
+                    self.minimum.y
+                } else {
+                    self.maximum.y
+                };
+                let z = if corner & 4 == 0 {
+                    self.minimum.z
+                } else {
+                    self.maximum.z
+                };
+
+                transformation.transform_point(&Vector3::new(x, y, z))
+            })
+            .collect::<Vec<_>>();
+
+        BoundingBox::from_points(corners.iter())
+    }
+
+    /// Calculates the surface area of the [`BoundingBox`]. Empty boxes, where the minimum is greater than the
+    /// maximum, have no area.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use yart::geometries::bounding_box::BoundingBox;
+    /// # use yart::math::vector3::Vector3;
+    /// #
+    /// let bounding_box = BoundingBox::new(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(1.0, 2.0, 3.0));
+    ///
+    /// assert_eq!(22.0, bounding_box.calculate_surface_area());
+    /// assert_eq!(0.0, BoundingBox::new_inverse_infinity().calculate_surface_area());
+    /// ```
+    pub fn calculate_surface_area(&self) -> Real {
+        let size = self.maximum - self.minimum;
+
+        if size.x < 0.0 || size.y < 0.0 || size.z < 0.0 {
+            return 0.0;
+        }
+
+        2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+
+    /// Determines if all the extents of the [`BoundingBox`] are finite.
+    pub fn is_finite(&self) -> bool {
+        (0..3).all(|i| self.minimum[i].is_finite() && self.maximum[i].is_finite())
+    }
+
+    /// Determines if a [`Ray`] intersects with the [`BoundingBox`] within the ray's extent.
     ///
     /// # Examples
     ///
@@ -346,6 +423,9 @@ impl BoundingBox {
     /// assert_eq!(true, bounding_box.ray_intersects(&ray_hits));
     /// assert_eq!(false, bounding_box.ray_intersects(&ray_misses_facing_away));
     /// assert_eq!(false, bounding_box.ray_intersects(&ray_misses_completely));
+    ///
+    /// let ray_stops_short = ray_hits.with_extent(0.0, 1.0);
+    /// assert_eq!(false, bounding_box.ray_intersects(&ray_stops_short));
     /// ```
     pub fn ray_intersects(&self, ray: &Ray) -> bool {
         let min = Vector3::component_mul(&(self.minimum - ray.position()), ray.inverse_direction());
@@ -361,12 +441,14 @@ impl BoundingBox {
             Real::min(min.z, max.z),
         );
 
-        exit_distance >= 0.0 && entrance_distance <= exit_distance
+        exit_distance >= ray.minimum_distance()
+            && entrance_distance <= ray.maximum_distance()
+            && entrance_distance <= exit_distance
     }
 }
 
 impl BoundingVolume for BoundingBox {
-    /// Determines if a [`Ray`] intersects with the [`BoundingBox`].
+    /// Determines if a [`Ray`] intersects with the [`BoundingBox`] within the ray's extent.
     ///
     /// # Examples
     ///
@@ -402,7 +484,9 @@ impl BoundingVolume for BoundingBox {
             Real::min(min.z, max.z),
         );
 
-        exit_distance >= 0.0 && entrance_distance <= exit_distance
+        exit_distance >= ray.minimum_distance()
+            && entrance_distance <= ray.maximum_distance()
+            && entrance_distance <= exit_distance
     }
 }
 
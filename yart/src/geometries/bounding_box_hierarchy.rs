@@ -1,90 +1,585 @@
-use crate::geometries::{
-    bound_by_box::BoundByBox, bounding_box::BoundingBox, intersectable::Intersectable,
-    intersectable_collection::IntersectableCollection, intersection::Intersection, ray::Ray,
+use crate::{
+    common::Real,
+    geometries::{
+        bound_by_box::BoundByBox, bounding_box::BoundingBox, intersectable::Intersectable,
+        intersectable_collection::IntersectableCollection, intersection::Intersection, ray::Ray,
+        wide_bounding_box_hierarchy::WideBoundingBoxHierarchy,
+    },
+    math::vector3::Vector3,
 };
-use itertools::Itertools;
-use std::{cmp::Ordering, rc::Rc};
+use std::ops::Range;
 
-#[derive(Debug)]
-pub struct BoundingBoxLeaf {
-    bounding_box: BoundingBox,
-    child: Box<dyn Intersectable>,
+/// The deepest a hierarchy can be. Traversal keeps the nodes it still has to visit on a fixed-size stack, which gains
+/// at most one entry per level.
+pub const MAXIMUM_DEPTH: u16 = 64;
+
+/// A node of a flattened hierarchy. Nodes are stored depth first so the first child of an inner node directly follows
+/// it, and only the index of the second child needs to be stored. The bounds are stored in single precision, rounded
+/// outward, so that a node fits in 32 bytes and two of them share a cache line.
+#[derive(Debug, Copy, Clone)]
+pub struct BoundingBoxHierarchyNode {
+    minimum: [f32; 3],
+    maximum: [f32; 3],
+
+    /// The index of the first primitive of a leaf, or of the second child of an inner node.
+    offset: u32,
+
+    /// The number of primitives in a leaf, or zero for an inner node.
+    count: u32,
+}
+
+const _: () = assert!(std::mem::size_of::<BoundingBoxHierarchyNode>() == 32);
+
+impl BoundingBoxHierarchyNode {
+    fn new(bounding_box: &BoundingBox, offset: u32, count: u32) -> Self {
+        Self {
+            minimum: [0, 1, 2].map(|axis| round_down(bounding_box.minimum[axis])),
+            maximum: [0, 1, 2].map(|axis| round_up(bounding_box.maximum[axis])),
+            offset,
+            count,
+        }
+    }
+
+    pub fn is_leaf(&self) -> bool {
+        self.count > 0
+    }
+
+    /// The smallest corner of the node's bounds, rounded outward to single precision.
+    pub fn minimum(&self) -> [f32; 3] {
+        self.minimum
+    }
+
+    /// The largest corner of the node's bounds, rounded outward to single precision.
+    pub fn maximum(&self) -> [f32; 3] {
+        self.maximum
+    }
+
+    /// The range of the primitives in a leaf. Empty for an inner node.
+    pub fn primitive_range(&self) -> Range<usize> {
+        if self.is_leaf() {
+            self.offset as usize..(self.offset + self.count) as usize
+        } else {
+            0..0
+        }
+    }
+
+    /// The index of the second child of an inner node. The first child directly follows the node.
+    pub fn second_child_index(&self) -> usize {
+        self.offset as usize
+    }
+
+    /// Returns the distance along the ray at which it enters the node's bounds, or `None` if it misses them within
+    /// its extent or only enters them beyond `maximum_distance`. The distance is negative if the ray starts inside the
+    /// bounds.
+    #[inline(always)]
+    fn calculate_entrance_distance(&self, ray: &Ray, maximum_distance: Real) -> Option<Real> {
+        let position = ray.position();
+        let inverse_direction = ray.inverse_direction();
+
+        let mut entrance_distance = Real::NEG_INFINITY;
+        let mut exit_distance = Real::INFINITY;
+
+        for axis in 0..3 {
+            let near = (self.minimum[axis] as Real - position[axis]) * inverse_direction[axis];
+            let far = (self.maximum[axis] as Real - position[axis]) * inverse_direction[axis];
+
+            entrance_distance = Real::max(entrance_distance, Real::min(near, far));
+            exit_distance = Real::min(exit_distance, Real::max(near, far));
+        }
+
+        if exit_distance >= ray.minimum_distance()
+            && entrance_distance <= exit_distance
+            && entrance_distance <= maximum_distance
+        {
+            Some(entrance_distance)
+        } else {
+            None
+        }
+    }
+
+    pub fn calculate_bounding_box(&self) -> BoundingBox {
+        BoundingBox::new(
+            &Vector3::new(
+                self.minimum[0] as Real,
+                self.minimum[1] as Real,
+                self.minimum[2] as Real,
+            ),
+            &Vector3::new(
+                self.maximum[0] as Real,
+                self.maximum[1] as Real,
+                self.maximum[2] as Real,
+            ),
+        )
+    }
+}
+
+/// Converts to single precision, rounding toward negative infinity. With the `f32` feature the
+/// value already is single precision and is returned unchanged.
+#[allow(clippy::unnecessary_cast)]
+fn round_down(value: Real) -> f32 {
+    let rounded = value as f32;
+
+    if rounded as Real > value {
+        rounded.next_down()
+    } else {
+        rounded
+    }
 }
 
-impl BoundingBoxLeaf {
-    pub fn new(bounding_box: BoundingBox, child: Box<dyn Intersectable>) -> Self {
-        Self { bounding_box, child }
+/// Converts to single precision, rounding toward positive infinity. With the `f32` feature the
+/// value already is single precision and is returned unchanged.
+#[allow(clippy::unnecessary_cast)]
+fn round_up(value: Real) -> f32 {
+    let rounded = value as f32;
+
+    if (rounded as Real) < value {
+        rounded.next_up()
+    } else {
+        rounded
     }
 }
 
-impl Intersectable for BoundingBoxLeaf {
-    fn intersect(&self, ray: &Ray) -> Option<Intersection> {
-        if self.bounding_box.ray_intersects(ray) {
-            return self.child.intersect(ray);
+/// A bounding box hierarchy over primitives of a single type, flattened into an array of nodes. The primitives are
+/// reordered so that those of each leaf are next to each other.
+///
+/// Traversal descends into the nearer child of each node first and skips every node that the ray only enters beyond
+/// the closest intersection found so far, so most nodes behind a hit are never visited. Primitives are intersected
+/// with the ray shortened to the closest intersection so they can skip hits that are further away.
+#[derive(Debug)]
+pub struct BoundingBoxHierarchy<T: BoundByBox> {
+    nodes: Vec<BoundingBoxHierarchyNode>,
+    primitives: Vec<T>,
+}
+
+impl<T: BoundByBox> BoundingBoxHierarchy<T> {
+    /// Builds a hierarchy by recursively splitting the primitives in two. Each split is chosen with the surface area
+    /// heuristic, which estimates the cost of a split by the probability of a ray hitting each side, taken as
+    /// proportional to the surface areas of their bounding boxes, times the number of primitives on that side. Rather
+    /// than trying every possible split the center points are sorted into bins and only splits between bins are
+    /// tried.
+    ///
+    /// Every primitive must have finite bounds.
+    pub fn new(primitives: Vec<T>, options: &BoundingBoxHierarchyOptions) -> Self {
+        let mut nodes = Vec::new();
+        let mut order = Vec::with_capacity(primitives.len());
+
+        if !primitives.is_empty() {
+            let items = primitives
+                .iter()
+                .enumerate()
+                .map(|(index, primitive)| {
+                    let bounding_box = primitive.calculate_bounding_box();
+
+                    BuildItem {
+                        bounding_box,
+                        center_point: bounding_box.calculate_center_point(),
+                        index,
+                    }
+                })
+                .collect();
+
+            build_node(items, 0, options, &mut nodes, &mut order);
+        }
+
+        let mut slots = primitives.into_iter().map(Some).collect::<Vec<_>>();
+        let primitives = order.into_iter().map(|index| slots[index].take().unwrap()).collect();
+
+        Self { nodes, primitives }
+    }
+
+    pub fn primitives(&self) -> &[T] {
+        &self.primitives
+    }
+
+    /// The nodes of the hierarchy, with the root first.
+    pub fn nodes(&self) -> &[BoundingBoxHierarchyNode] {
+        &self.nodes
+    }
+
+    pub fn into_primitives(self) -> Vec<T> {
+        self.primitives
+    }
+
+    /// Finds the closest intersection with the primitives, which are intersected by `intersect_primitive`. This lets
+    /// the owner of primitives that can't create intersections on their own, such as the triangles of a mesh,
+    /// intersect them on their behalf.
+    pub fn intersect_with<'a>(
+        &'a self,
+        ray: &Ray,
+        intersect_primitive: impl Fn(&'a T, &Ray) -> Option<Intersection<'a>>,
+    ) -> Option<Intersection<'a>> {
+        let root = self.nodes.first()?;
+        root.calculate_entrance_distance(ray, ray.maximum_distance())?;
+
+        let mut closest_intersection: Option<Intersection> = None;
+        let mut closest_distance = ray.maximum_distance();
+
+        // Shortened to the closest intersection found so far, so primitives can skip hits that are further away.
+        let mut culled_ray = ray.clone();
+
+        // The nodes still to be visited, together with the distance at which the ray enters them.
+        let mut stack = [(0u32, 0.0 as Real); MAXIMUM_DEPTH as usize];
+        let mut stack_size = 0;
+
+        let mut node_index = 0;
+
+        loop {
+            let node = &self.nodes[node_index];
+
+            if node.is_leaf() {
+                let start = node.offset as usize;
+
+                for primitive in &self.primitives[start..start + node.count as usize] {
+                    let Some(intersection) = intersect_primitive(primitive, &culled_ray) else {
+                        continue;
+                    };
+
+                    if intersection.entrance_distance <= closest_distance {
+                        closest_distance = intersection.entrance_distance;
+                        closest_intersection = Some(intersection);
+                        culled_ray = ray.with_maximum_distance(closest_distance);
+                    }
+                }
+            } else {
+                let first_index = node_index + 1;
+                let second_index = node.offset as usize;
+
+                let first_distance = self.nodes[first_index].calculate_entrance_distance(ray, closest_distance);
+                let second_distance = self.nodes[second_index].calculate_entrance_distance(ray, closest_distance);
+
+                match (first_distance, second_distance) {
+                    (Some(first_distance), Some(second_distance)) => {
+                        let (near_index, far_index, far_distance) = if first_distance <= second_distance {
+                            (first_index, second_index, second_distance)
+                        } else {
+                            (second_index, first_index, first_distance)
+                        };
+
+                        stack[stack_size] = (far_index as u32, far_distance);
+                        stack_size += 1;
+
+                        node_index = near_index;
+                        continue;
+                    }
+                    (Some(_), None) => {
+                        node_index = first_index;
+                        continue;
+                    }
+                    (None, Some(_)) => {
+                        node_index = second_index;
+                        continue;
+                    }
+                    (None, None) => {}
+                }
+            }
+
+            // Continue with the nearest node left on the stack, unless the ray only enters it beyond the closest
+            // intersection found since it was pushed.
+            loop {
+                if stack_size == 0 {
+                    return closest_intersection;
+                }
+
+                stack_size -= 1;
+                let (index, distance) = stack[stack_size];
+
+                if distance <= closest_distance {
+                    node_index = index as usize;
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Determines if `occluded_primitive` accepts a hit with any of the primitives whose bounds the ray passes
+    /// through.
+    pub fn occluded_with(&self, ray: &Ray, occluded_primitive: impl Fn(&T, &Ray) -> bool) -> bool {
+        if self.nodes.is_empty() {
+            return false;
         }
 
-        None
+        let mut stack = [0u32; MAXIMUM_DEPTH as usize + 1];
+        let mut stack_size = 1;
+
+        // Any accepted hit will do, so the order in which the children are visited doesn't matter.
+        while stack_size > 0 {
+            stack_size -= 1;
+            let node_index = stack[stack_size] as usize;
+            let node = &self.nodes[node_index];
+
+            if node.calculate_entrance_distance(ray, ray.maximum_distance()).is_none() {
+                continue;
+            }
+
+            if node.is_leaf() {
+                let start = node.offset as usize;
+
+                if self.primitives[start..start + node.count as usize]
+                    .iter()
+                    .any(|x| occluded_primitive(x, ray))
+                {
+                    return true;
+                }
+            } else {
+                stack[stack_size] = node_index as u32 + 1;
+                stack[stack_size + 1] = node.offset;
+                stack_size += 2;
+            }
+        }
+
+        false
+    }
+}
+
+impl<T: Intersectable> Intersectable for BoundingBoxHierarchy<T> {
+    fn intersect(&self, ray: &Ray) -> Option<Intersection<'_>> {
+        self.intersect_with(ray, |primitive, ray| primitive.intersect(ray))
+    }
+
+    fn occluded(&self, ray: &Ray, filter: &dyn Fn(&Intersection) -> bool) -> bool {
+        self.occluded_with(ray, |primitive, ray| primitive.occluded(ray, filter))
     }
 }
 
-impl BoundByBox for BoundingBoxLeaf {
+impl<T: BoundByBox> BoundByBox for BoundingBoxHierarchy<T> {
     fn calculate_bounding_box(&self) -> BoundingBox {
-        self.bounding_box
+        self.nodes.first().map_or_else(
+            BoundingBox::new_inverse_infinity,
+            BoundingBoxHierarchyNode::calculate_bounding_box,
+        )
+    }
+}
+
+/// Controls how a bounding box hierarchy is built.
+#[derive(Debug, Copy, Clone)]
+pub struct BoundingBoxHierarchyOptions {
+    /// The number of geometries above which a node is always split until the maximum depth is reached. Nodes with at
+    /// most this many geometries are only split when the surface area heuristic estimates splitting is cheaper than
+    /// testing every geometry, so leaves are usually smaller.
+    pub maximum_leaf_size: usize,
+
+    /// The depth beyond which nodes aren't split any further, regardless of how many geometries they contain. At most
+    /// 64.
+    pub maximum_depth: u16,
+
+    /// The number of bins along each axis that candidate splits are placed between.
+    pub bin_count: usize,
+}
+
+impl BoundingBoxHierarchyOptions {
+    pub fn new(maximum_leaf_size: usize, maximum_depth: u16, bin_count: usize) -> Self {
+        Self {
+            maximum_leaf_size: usize::max(1, maximum_leaf_size),
+            maximum_depth: u16::min(MAXIMUM_DEPTH, maximum_depth),
+            bin_count: usize::max(2, bin_count),
+        }
+    }
+}
+
+impl Default for BoundingBoxHierarchyOptions {
+    fn default() -> Self {
+        Self::new(4, 32, 16)
     }
 }
 
-fn build_bounding_box_hierarchy_split_by_long_axis_internal(
-    _current_depth: u16,
+/// The estimated cost of testing a ray against a bounding box, relative to testing it against a geometry.
+const TRAVERSAL_COST: Real = 1.0;
+const INTERSECTION_COST: Real = 1.0;
+
+/// The bounds of a primitive, which are calculated once up front since they're needed many times while building,
+/// together with the index of the primitive.
+struct BuildItem {
+    bounding_box: BoundingBox,
+    center_point: Vector3,
+    index: usize,
+}
+
+#[derive(Copy, Clone)]
+struct Bin {
+    bounding_box: BoundingBox,
+    count: usize,
+}
+
+/// A candidate split of a node's geometries into those whose center points are in the bins before `bin` and those in
+/// the bins from `bin` onward.
+struct Split {
+    axis: usize,
+    bin: usize,
+    cost: Real,
+}
+
+/// Builds a [`WideBoundingBoxHierarchy`] over geometries of any type. Geometries that extend to infinity, such as planes,
+/// can't be placed in a hierarchy so they're tested alongside its root.
+pub fn build_bounding_box_hierarchy(
     geometries: Vec<Box<dyn Intersectable>>,
+    options: &BoundingBoxHierarchyOptions,
 ) -> Box<dyn Intersectable> {
-    const MAXIMUM_LEAFS: usize = 8;
+    let (bounded_geometries, mut unbounded_geometries): (Vec<_>, Vec<_>) = geometries
+        .into_iter()
+        .partition(|geometry| geometry.calculate_bounding_box().is_finite());
 
-    let complete_bounding_box = BoundingBox::from_geometries(geometries.iter().map(|x| x.as_ref()));
+    let hierarchy = Box::new(WideBoundingBoxHierarchy::new(bounded_geometries, options));
 
-    // Determine which axis is the longest.
-    let axis_lengths = complete_bounding_box.maximum - complete_bounding_box.minimum;
+    if unbounded_geometries.is_empty() {
+        return hierarchy;
+    }
 
-    let longest_axis_index = if axis_lengths.x > axis_lengths.y { 0 } else { 1 };
-    let longest_axis_index = if axis_lengths[longest_axis_index] > axis_lengths.z {
-        longest_axis_index
-    } else {
-        2
+    if !hierarchy.primitives().is_empty() {
+        unbounded_geometries.push(hierarchy);
+    }
+
+    Box::new(IntersectableCollection::new(unbounded_geometries))
+}
+
+/// Appends the node for the given items, followed by the nodes below it, and appends the indices of the primitives in
+/// its leaves to `order`.
+fn build_node(
+    items: Vec<BuildItem>,
+    current_depth: u16,
+    options: &BoundingBoxHierarchyOptions,
+    nodes: &mut Vec<BoundingBoxHierarchyNode>,
+    order: &mut Vec<usize>,
+) {
+    let mut bounding_box = BoundingBox::new_inverse_infinity();
+
+    for item in &items {
+        bounding_box.add_bounding_box(&item.bounding_box);
+    }
+
+    let leaf_cost = items.len() as Real * INTERSECTION_COST;
+
+    if items.len() <= 1 || current_depth >= options.maximum_depth {
+        return build_leaf(&bounding_box, items, nodes, order);
+    }
+
+    let (left_items, right_items) = match find_best_split(&items, &bounding_box, options) {
+        Some(split) if split.cost < leaf_cost || items.len() > options.maximum_leaf_size => {
+            partition_by_split(items, &split, options)
+        }
+        Some(_) => return build_leaf(&bounding_box, items, nodes, order),
+        // Every center point is in the same place so no split can separate them. Halve the geometries instead
+        // when there are too many for one leaf.
+        None if items.len() > options.maximum_leaf_size => {
+            let mut left_items = items;
+            let right_items = left_items.split_off(left_items.len() / 2);
+
+            (left_items, right_items)
+        }
+        None => return build_leaf(&bounding_box, items, nodes, order),
     };
 
-    // Sort the geometries by the longest axis and chunk them together.
-    let geometries_per_level = usize::max(1, f64::ceil(geometries.len() as f64 / MAXIMUM_LEAFS as f64) as usize);
+    let node_index = nodes.len();
+    nodes.push(BoundingBoxHierarchyNode::new(&bounding_box, 0, 0));
 
-    let geometry_chunks = geometries
-        .into_iter()
-        .sorted_by(|a, b| {
-            let a_center_point = a.calculate_bounding_box().calculate_center_point();
-            let b_center_point = b.calculate_bounding_box().calculate_center_point();
+    build_node(left_items, current_depth + 1, options, nodes, order);
+    nodes[node_index].offset = nodes.len() as u32;
+    build_node(right_items, current_depth + 1, options, nodes, order);
+}
+
+fn build_leaf(
+    bounding_box: &BoundingBox,
+    items: Vec<BuildItem>,
+    nodes: &mut Vec<BoundingBoxHierarchyNode>,
+    order: &mut Vec<usize>,
+) {
+    nodes.push(BoundingBoxHierarchyNode::new(
+        bounding_box,
+        order.len() as u32,
+        items.len() as u32,
+    ));
+
+    order.extend(items.into_iter().map(|x| x.index));
+}
+
+fn find_best_split(
+    items: &[BuildItem],
+    bounding_box: &BoundingBox,
+    options: &BoundingBoxHierarchyOptions,
+) -> Option<Split> {
+    let center_bounding_box = BoundingBox::from_points(items.iter().map(|x| &x.center_point));
+    let recip_surface_area = Real::recip(bounding_box.calculate_surface_area());
 
-            a_center_point[longest_axis_index]
-                .partial_cmp(&b_center_point[longest_axis_index])
-                .unwrap_or(Ordering::Equal)
-        })
-        .chunks(geometries_per_level);
+    let mut best_split: Option<Split> = None;
 
-    let mut leaves: Vec<Box<dyn Intersectable>> = Vec::new();
+    for axis in 0..3 {
+        let minimum = center_bounding_box.minimum[axis];
+        let extent = center_bounding_box.maximum[axis] - minimum;
 
-    for geometry_chunk in &geometry_chunks {
-        let chunk_geometries = geometry_chunk.collect_vec();
+        if extent <= 0.0 {
+            continue;
+        }
+
+        let mut bins = vec![
+            Bin {
+                bounding_box: BoundingBox::new_inverse_infinity(),
+                count: 0,
+            };
+            options.bin_count
+        ];
+
+        for item in items {
+            let bin = &mut bins[calculate_bin_index(item.center_point[axis], minimum, extent, options.bin_count)];
+
+            bin.bounding_box.add_bounding_box(&item.bounding_box);
+            bin.count += 1;
+        }
+
+        // Sweep from the right to find the area and count of everything from each bin onward, then sweep from the
+        // left to combine them with everything before each bin.
+        let mut right_areas = vec![0.0; options.bin_count];
+        let mut right_counts = vec![0; options.bin_count];
+        let mut right_bounding_box = BoundingBox::new_inverse_infinity();
+        let mut right_count = 0;
+
+        for bin in (1..options.bin_count).rev() {
+            right_bounding_box.add_bounding_box(&bins[bin].bounding_box);
+            right_count += bins[bin].count;
+
+            right_areas[bin] = right_bounding_box.calculate_surface_area();
+            right_counts[bin] = right_count;
+        }
 
-        let leaf_bounding_box = BoundingBox::from_geometries(chunk_geometries.iter().map(|x| x.as_ref()));
-        let geometry = Box::new(IntersectableCollection::new(chunk_geometries));
+        let mut left_bounding_box = BoundingBox::new_inverse_infinity();
+        let mut left_count = 0;
 
-        // TODO: Take into account preferred leaf width and current depth.
+        for bin in 1..options.bin_count {
+            left_bounding_box.add_bounding_box(&bins[bin - 1].bounding_box);
+            left_count += bins[bin - 1].count;
 
-        let leaf = Box::new(BoundingBoxLeaf::new(leaf_bounding_box, geometry));
-        leaves.push(leaf);
+            if left_count == 0 || right_counts[bin] == 0 {
+                continue;
+            }
+
+            let cost = TRAVERSAL_COST
+                + INTERSECTION_COST
+                    * (left_bounding_box.calculate_surface_area() * left_count as Real
+                        + right_areas[bin] * right_counts[bin] as Real)
+                    * recip_surface_area;
+
+            if best_split.as_ref().is_none_or(|x| cost < x.cost) {
+                best_split = Some(Split { axis, bin, cost });
+            }
+        }
     }
 
-    Box::new(IntersectableCollection::new(leaves))
+    best_split
 }
 
-pub fn build_bounding_box_hierarchy_split_by_long_axis(
-    geometries: Vec<Box<dyn Intersectable>>,
-) -> Box<dyn Intersectable> {
-    build_bounding_box_hierarchy_split_by_long_axis_internal(1, geometries)
+fn partition_by_split(
+    items: Vec<BuildItem>,
+    split: &Split,
+    options: &BoundingBoxHierarchyOptions,
+) -> (Vec<BuildItem>, Vec<BuildItem>) {
+    let center_bounding_box = BoundingBox::from_points(items.iter().map(|x| &x.center_point));
+
+    let minimum = center_bounding_box.minimum[split.axis];
+    let extent = center_bounding_box.maximum[split.axis] - minimum;
+
+    items.into_iter().partition(|item| {
+        calculate_bin_index(item.center_point[split.axis], minimum, extent, options.bin_count) < split.bin
+    })
+}
+
+fn calculate_bin_index(value: Real, minimum: Real, extent: Real, bin_count: usize) -> usize {
+    usize::min(((value - minimum) / extent * bin_count as Real) as usize, bin_count - 1)
 }
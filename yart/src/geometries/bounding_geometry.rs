@@ -16,13 +16,17 @@ impl BoundingGeometry {
 }
 
 impl Intersectable for BoundingGeometry {
-    fn intersect(&self, ray: &Ray) -> Option<Intersection> {
+    fn intersect(&self, ray: &Ray) -> Option<Intersection<'_>> {
         if self.bounding_volume.ray_intersects(ray) {
             self.child.intersect(ray)
         } else {
             None
         }
     }
+
+    fn occluded(&self, ray: &Ray, filter: &dyn Fn(&Intersection) -> bool) -> bool {
+        self.bounding_volume.ray_intersects(ray) && self.child.occluded(ray, filter)
+    }
 }
 
 impl BoundByBox for BoundingGeometry {
@@ -1,6 +1,6 @@
 use crate::geometries::{bound_by_box::BoundByBox, ray::Ray};
 use std::fmt::Debug;
 
-pub trait BoundingVolume: Debug + BoundByBox {
+pub trait BoundingVolume: Debug + BoundByBox + Send + Sync {
     fn ray_intersects(&self, ray: &Ray) -> bool;
 }
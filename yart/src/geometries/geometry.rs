@@ -1,6 +1,4 @@
-use super::{
-    has_material::HasMaterial, intersectable::Intersectable, normal_calculator::NormalCalculator,
-};
+use super::{has_material::HasMaterial, intersectable::Intersectable, normal_calculator::NormalCalculator};
 
 pub trait Geometry: Intersectable + HasMaterial + NormalCalculator {}
 
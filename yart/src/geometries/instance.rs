@@ -0,0 +1,67 @@
+use crate::{
+    geometries::{
+        bound_by_box::BoundByBox, bounding_box::BoundingBox, intersectable::Intersectable, intersection::Intersection,
+        ray::Ray, transformed::Transformed,
+    },
+    materials::material::MaterialIndex,
+    math::matrix4::Matrix4,
+};
+use std::sync::Arc;
+
+/// Places a copy of shared geometry in the scene. Every instance of a definition points to the same geometry, and its
+/// bounding volume hierarchy, so only the transformation and material of each copy take up memory. Scenes with many
+/// instances get two levels of acceleration: a hierarchy over the instances and the hierarchy within the definition.
+#[derive(Debug)]
+pub struct Instance {
+    transformed: Transformed,
+
+    /// The material to use instead of the materials of the geometry, if any.
+    material_index_override: Option<MaterialIndex>,
+}
+
+impl Instance {
+    /// Creates a new [`Instance`]. Returns `None` if the transformation can't be inverted.
+    pub fn new(
+        definition: &Arc<dyn Intersectable>,
+        transformation: &Matrix4,
+        material_index_override: Option<MaterialIndex>,
+    ) -> Option<Self> {
+        Some(Self {
+            transformed: Transformed::new(transformation, Box::new(definition.clone()))?,
+            material_index_override,
+        })
+    }
+}
+
+impl Intersectable for Instance {
+    fn intersect(&self, ray: &Ray) -> Option<Intersection<'_>> {
+        let intersection = self.transformed.intersect(ray)?;
+
+        // When instances are nested the outermost override wins, the same way materials assigned further up a scene
+        // graph replace the ones below.
+        Some(Intersection {
+            material_index_override: self.material_index_override.or(intersection.material_index_override),
+            ..intersection
+        })
+    }
+
+    fn occluded(&self, ray: &Ray, filter: &dyn Fn(&Intersection) -> bool) -> bool {
+        if self.material_index_override.is_none() {
+            return self.transformed.occluded(ray, filter);
+        }
+
+        // The filter has to see the material the hit will actually be shaded with.
+        self.transformed.occluded(ray, &|intersection| {
+            filter(&Intersection {
+                material_index_override: self.material_index_override,
+                ..*intersection
+            })
+        })
+    }
+}
+
+impl BoundByBox for Instance {
+    fn calculate_bounding_box(&self) -> BoundingBox {
+        self.transformed.calculate_bounding_box()
+    }
+}
@@ -1,6 +1,35 @@
 use crate::geometries::{bound_by_box::BoundByBox, intersection::Intersection, ray::Ray};
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
-pub trait Intersectable: Debug + BoundByBox {
-    fn intersect(&self, ray: &Ray) -> Option<Intersection>;
+pub trait Intersectable: Debug + BoundByBox + Send + Sync {
+    fn intersect(&self, ray: &Ray) -> Option<Intersection<'_>>;
+
+    /// Determines if the ray hits anything accepted by `filter` within its extent. Unlike [`Self::intersect`] this
+    /// doesn't have to find the closest hit, so geometries made of several parts stop at the first accepted one.
+    fn occluded(&self, ray: &Ray, filter: &dyn Fn(&Intersection) -> bool) -> bool {
+        self.intersect(ray).is_some_and(|x| filter(&x))
+    }
+}
+
+/// Shared geometry, such as the definition behind several instances, intersects like the geometry it points to.
+impl Intersectable for Arc<dyn Intersectable> {
+    fn intersect(&self, ray: &Ray) -> Option<Intersection<'_>> {
+        self.as_ref().intersect(ray)
+    }
+
+    fn occluded(&self, ray: &Ray, filter: &dyn Fn(&Intersection) -> bool) -> bool {
+        self.as_ref().occluded(ray, filter)
+    }
+}
+
+/// Owned geometry of any type, such as the geometries of a bounding box hierarchy, intersects like the geometry it
+/// holds.
+impl Intersectable for Box<dyn Intersectable> {
+    fn intersect(&self, ray: &Ray) -> Option<Intersection<'_>> {
+        self.as_ref().intersect(ray)
+    }
+
+    fn occluded(&self, ray: &Ray, filter: &dyn Fn(&Intersection) -> bool) -> bool {
+        self.as_ref().occluded(ray, filter)
+    }
 }
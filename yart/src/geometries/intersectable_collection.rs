@@ -15,25 +15,27 @@ impl IntersectableCollection {
 }
 
 impl Intersectable for IntersectableCollection {
-    fn intersect(&self, ray: &Ray) -> Option<Intersection> {
+    fn intersect(&self, ray: &Ray) -> Option<Intersection<'_>> {
         let mut maybe_closest_intersection: Option<Intersection> = None;
 
+        // Shortened to the closest intersection found so far, so children can skip hits that are further away.
+        let mut culled_ray = ray.clone();
+
         for geometry in &self.children {
-            let Some(intersection) = geometry.intersect(ray) else {
+            let Some(intersection) = geometry.intersect(&culled_ray) else {
                 continue;
             };
 
-            if let Some(closest_intersection) = maybe_closest_intersection {
-                if intersection.entrance_distance < closest_intersection.entrance_distance {
-                    maybe_closest_intersection = Some(intersection);
-                }
-            } else {
-                maybe_closest_intersection = Some(intersection);
-            }
+            culled_ray = ray.with_maximum_distance(intersection.entrance_distance);
+            maybe_closest_intersection = Some(intersection);
         }
 
         maybe_closest_intersection
     }
+
+    fn occluded(&self, ray: &Ray, filter: &dyn Fn(&Intersection) -> bool) -> bool {
+        self.children.iter().any(|x| x.occluded(ray, filter))
+    }
 }
 
 impl BoundByBox for IntersectableCollection {
@@ -1,5 +1,5 @@
-use super::geometry::Geometry;
-use crate::{common::Real, materials::material::MaterialIndex};
+use super::{geometry::Geometry, surface_point::SurfacePoint};
+use crate::{common::Real, materials::material::MaterialIndex, math::vector2::Vector2};
 
 #[derive(Debug, Copy, Clone)]
 pub struct Intersection<'g> {
@@ -7,7 +7,23 @@ pub struct Intersection<'g> {
     pub entrance_distance: Real,
     pub exit_distance: Real,
     pub mix_amount: Real,
-    pub material_index_override: MaterialIndex,
+
+    /// The material the hit is shaded with instead of the material of the hit geometry, such as the material of an
+    /// instance.
+    pub material_index_override: Option<MaterialIndex>,
+
+    /// The coordinates of the hit position on the surface that fall out of intersecting it, such as the barycentric
+    /// coordinates of the second and third vertex of a triangle, so they don't have to be calculated again from the
+    /// hit position.
+    pub surface_coordinates: Vector2,
+
+    /// Identifies the primitive that was hit among the primitives of the hit geometry, such as the triangles of a
+    /// mesh, in the order they were given. Zero for geometries that are a single primitive.
+    pub primitive_id: u32,
+
+    /// The surface at the hit position when it has already been calculated. Wrappers that move the ray into the space
+    /// of their child set this since the child can only calculate its surface from the moved ray.
+    pub surface_override: Option<SurfacePoint>,
 }
 
 impl<'g> Intersection<'g> {
@@ -16,7 +32,7 @@ impl<'g> Intersection<'g> {
         entrance_distance: Real,
         exit_distance: Real,
         mix_amount: Real,
-        material_index_override: MaterialIndex,
+        material_index_override: Option<MaterialIndex>,
     ) -> Intersection<'g> {
         Self {
             hit_geometry,
@@ -24,6 +40,9 @@ impl<'g> Intersection<'g> {
             exit_distance,
             mix_amount,
             material_index_override,
+            surface_coordinates: Vector2::default(),
+            primitive_id: 0,
+            surface_override: None,
         }
     }
 }
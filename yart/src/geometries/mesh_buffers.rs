@@ -0,0 +1,45 @@
+use crate::{
+    math::{color3::Color3, matrix4::Matrix4, vector2::Vector2, vector3::Vector3},
+    normalize,
+};
+
+/// The vertex data shared by every triangle of a mesh. Triangles refer to entries in the buffers by index.
+#[derive(Debug, Clone, Default)]
+pub struct MeshBuffers {
+    pub positions: Vec<Vector3>,
+    pub normals: Vec<Vector3>,
+
+    /// Texture coordinates with `(0, 0)` at the upper left corner of an image, as in glTF. Loaders of formats where
+    /// `v` points up flip it.
+    pub uvs: Vec<Vector2>,
+
+    /// Per vertex colors, indexed the same way as the positions. Empty if the mesh doesn't have vertex colors.
+    pub colors: Vec<Color3>,
+}
+
+impl MeshBuffers {
+    pub fn new(positions: Vec<Vector3>, normals: Vec<Vector3>, uvs: Vec<Vector2>, colors: Vec<Color3>) -> Self {
+        Self {
+            positions,
+            normals,
+            uvs,
+            colors,
+        }
+    }
+
+    /// Transforms the positions and normals of the mesh. Returns `None` if the transformation can't be inverted,
+    /// which is needed to transform the normals.
+    pub fn transform(&mut self, transformation: &Matrix4) -> Option<()> {
+        let normal_matrix = transformation.normal_matrix()?;
+
+        for position in &mut self.positions {
+            *position = transformation.transform_point(position);
+        }
+
+        for normal in &mut self.normals {
+            *normal = normalize!(normal_matrix * *normal);
+        }
+
+        Some(())
+    }
+}
@@ -0,0 +1,168 @@
+use super::{bounding_box::BoundingBox, mesh_buffers::MeshBuffers, ray::Ray, surface_point::SurfacePoint};
+use crate::{
+    common::Real,
+    math::{color3::Color3, vector2::Vector2, vector3::Vector3},
+    normalize,
+};
+
+/// A triangle of a [`super::triangle_mesh::TriangleMesh`]. Rather than storing its vertices the triangle stores
+/// indices into the buffers of its mesh, which are passed to every method that needs them.
+#[derive(Debug, Copy, Clone)]
+pub struct MeshTriangle {
+    pub position_indices: [u32; 3],
+    pub normal_indices: Option<[u32; 3]>,
+    pub uv_indices: Option<[u32; 3]>,
+}
+
+impl MeshTriangle {
+    pub fn new(position_indices: [u32; 3], normal_indices: Option<[u32; 3]>, uv_indices: Option<[u32; 3]>) -> Self {
+        Self {
+            position_indices,
+            normal_indices,
+            uv_indices,
+        }
+    }
+
+    pub fn vertex(&self, buffers: &MeshBuffers, index: usize) -> Vector3 {
+        buffers.positions[self.position_indices[index] as usize]
+    }
+
+    /// Interpolates the texture coordinates of the triangle at a point described by barycentric coordinates. Returns
+    /// `None` if the mesh doesn't have texture coordinates.
+    pub fn calculate_uv(&self, buffers: &MeshBuffers, barycentric_coordinates: &Vector3) -> Option<Vector2> {
+        let uv_indices = self.uv_indices?;
+
+        let uv0 = buffers.uvs[uv_indices[0] as usize];
+        let uv1 = buffers.uvs[uv_indices[1] as usize];
+        let uv2 = buffers.uvs[uv_indices[2] as usize];
+
+        Some(uv0 * barycentric_coordinates.x + uv1 * barycentric_coordinates.y + uv2 * barycentric_coordinates.z)
+    }
+
+    /// Interpolates the vertex colors of the triangle at a point described by barycentric coordinates. Returns `None`
+    /// if the mesh doesn't have vertex colors.
+    pub fn calculate_color(&self, buffers: &MeshBuffers, barycentric_coordinates: &Vector3) -> Option<Color3> {
+        if buffers.colors.is_empty() {
+            return None;
+        }
+
+        let color0 = buffers.colors[self.position_indices[0] as usize];
+        let color1 = buffers.colors[self.position_indices[1] as usize];
+        let color2 = buffers.colors[self.position_indices[2] as usize];
+
+        Some(
+            color0 * barycentric_coordinates.x
+                + color1 * barycentric_coordinates.y
+                + color2 * barycentric_coordinates.z,
+        )
+    }
+
+    /// Interpolates the vertex normals of the triangle at a point described by barycentric coordinates, or returns the
+    /// face normal if the triangle doesn't have vertex normals.
+    fn calculate_vertex_normal(&self, buffers: &MeshBuffers, barycentric_coordinates: &Vector3) -> Vector3 {
+        match self.normal_indices {
+            Some(normal_indices) => normalize!(
+                buffers.normals[normal_indices[0] as usize] * barycentric_coordinates.x
+                    + buffers.normals[normal_indices[1] as usize] * barycentric_coordinates.y
+                    + buffers.normals[normal_indices[2] as usize] * barycentric_coordinates.z
+            ),
+            None => normalize!(
+                (self.vertex(buffers, 1) - self.vertex(buffers, 0))
+                    % (self.vertex(buffers, 2) - self.vertex(buffers, 0))
+            ),
+        }
+    }
+
+    /// Calculates the normal at a hit described by the barycentric coordinates of the second and third vertex, facing
+    /// against the ray.
+    pub fn calculate_normal(&self, buffers: &MeshBuffers, ray: &Ray, surface_coordinates: &Vector2) -> Vector3 {
+        let normal = self.calculate_vertex_normal(buffers, &to_barycentric_coordinates(surface_coordinates));
+
+        face_ray(normal, ray)
+    }
+
+    /// Calculates the surface at a hit described by the barycentric coordinates of the second and third vertex. Uses
+    /// the texture coordinates of the mesh when it has them, or the barycentric coordinates otherwise. The vertex
+    /// colors are interpolated as well when the mesh has them.
+    pub fn calculate_surface_point(
+        &self,
+        buffers: &MeshBuffers,
+        ray: &Ray,
+        hit_position: &Vector3,
+        surface_coordinates: &Vector2,
+    ) -> SurfacePoint {
+        let vertex0 = self.vertex(buffers, 0);
+
+        let edge1 = self.vertex(buffers, 1) - vertex0;
+        let edge2 = self.vertex(buffers, 2) - vertex0;
+
+        let barycentric_coordinates = to_barycentric_coordinates(surface_coordinates);
+
+        let face_normal = normalize!(edge1 % edge2);
+        let vertex_normal = match self.normal_indices {
+            Some(_) => self.calculate_vertex_normal(buffers, &barycentric_coordinates),
+            None => face_normal,
+        };
+
+        let (uv, dpdu, dpdv) = match self.uv_indices {
+            Some(uv_indices) => {
+                let uv0 = buffers.uvs[uv_indices[0] as usize];
+                let uv_edge1 = buffers.uvs[uv_indices[1] as usize] - uv0;
+                let uv_edge2 = buffers.uvs[uv_indices[2] as usize] - uv0;
+
+                let determinant = uv_edge1.x * uv_edge2.y - uv_edge1.y * uv_edge2.x;
+
+                // Texture coordinates that collapse the triangle to a line or a point don't define tangents, so fall
+                // back to the edges.
+                let (dpdu, dpdv) = if determinant.abs() > Real::EPSILON {
+                    let inverse_determinant = determinant.recip();
+
+                    (
+                        (edge1 * uv_edge2.y - edge2 * uv_edge1.y) * inverse_determinant,
+                        (edge2 * uv_edge1.x - edge1 * uv_edge2.x) * inverse_determinant,
+                    )
+                } else {
+                    (edge1, edge2)
+                };
+
+                (
+                    uv0 + uv_edge1 * surface_coordinates.x + uv_edge2 * surface_coordinates.y,
+                    dpdu,
+                    dpdv,
+                )
+            }
+            None => (*surface_coordinates, edge1, edge2),
+        };
+
+        SurfacePoint {
+            object_position: *hit_position,
+            uv,
+            geometric_normal: face_ray(face_normal, ray),
+            shading_normal: face_ray(vertex_normal, ray),
+            dpdu,
+            dpdv,
+            vertex_color: self.calculate_color(buffers, &barycentric_coordinates),
+        }
+    }
+
+    pub fn calculate_bounding_box(&self, buffers: &MeshBuffers) -> BoundingBox {
+        BoundingBox::from_points([0, 1, 2].map(|x| self.vertex(buffers, x)).iter())
+    }
+}
+
+/// Expands the barycentric coordinates of the second and third vertex to those of all three.
+fn to_barycentric_coordinates(surface_coordinates: &Vector2) -> Vector3 {
+    Vector3::new(
+        1.0 - surface_coordinates.x - surface_coordinates.y,
+        surface_coordinates.x,
+        surface_coordinates.y,
+    )
+}
+
+fn face_ray(normal: Vector3, ray: &Ray) -> Vector3 {
+    if ray.direction() ^ normal < 0.0 {
+        normal
+    } else {
+        -normal
+    }
+}
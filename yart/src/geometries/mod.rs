@@ -6,12 +6,21 @@ pub mod bounding_geometry;
 pub mod bounding_volume;
 pub mod geometry;
 pub mod has_material;
+pub mod instance;
 pub mod intersectable;
 pub mod intersectable_collection;
 pub mod intersection;
+pub mod mesh_buffers;
+pub mod mesh_triangle;
+pub mod moving_intersectable;
 pub mod normal_calculator;
 pub mod parallelogram;
 pub mod plane;
 pub mod ray;
 pub mod sphere;
+pub mod surface_point;
+pub mod transformed;
 pub mod triangle;
+pub mod triangle_mesh;
+pub mod triangle_packet;
+pub mod wide_bounding_box_hierarchy;
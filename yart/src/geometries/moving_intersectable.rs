@@ -0,0 +1,59 @@
+use crate::{
+    geometries::{
+        bound_by_box::BoundByBox, bounding_box::BoundingBox, intersectable::Intersectable, intersection::Intersection,
+        ray::Ray,
+    },
+    math::vector3::Vector3,
+};
+
+/// Moves a child, usually a group of geometries, linearly by `motion` between time zero and time one. Rays are moved
+/// into the space of the child at their time instead of moving the child itself.
+#[derive(Debug)]
+pub struct MovingIntersectable {
+    motion: Vector3,
+    child: Box<dyn Intersectable>,
+}
+
+impl MovingIntersectable {
+    pub fn new(motion: &Vector3, child: Box<dyn Intersectable>) -> Self {
+        Self { motion: *motion, child }
+    }
+}
+
+impl Intersectable for MovingIntersectable {
+    fn intersect(&self, ray: &Ray) -> Option<Intersection<'_>> {
+        let child_ray = ray.with_position(&(ray.position() - self.motion * ray.time()));
+        let intersection = self.child.intersect(&child_ray)?;
+
+        // The child can only calculate its surface from the moved ray so calculate it now while that ray is around.
+        let surface_point = intersection.surface_override.unwrap_or_else(|| {
+            let child_hit_position = child_ray.position_along(intersection.entrance_distance);
+            intersection
+                .hit_geometry
+                .calculate_surface_point(&child_ray, &child_hit_position, &intersection)
+        });
+
+        Some(Intersection {
+            surface_override: Some(surface_point),
+            ..intersection
+        })
+    }
+
+    fn occluded(&self, ray: &Ray, filter: &dyn Fn(&Intersection) -> bool) -> bool {
+        let child_ray = ray.with_position(&(ray.position() - self.motion * ray.time()));
+        self.child.occluded(&child_ray, filter)
+    }
+}
+
+impl BoundByBox for MovingIntersectable {
+    fn calculate_bounding_box(&self) -> BoundingBox {
+        let mut bounding_box = self.child.calculate_bounding_box();
+
+        let end_bounding_box = BoundingBox::new(
+            &(bounding_box.minimum + self.motion),
+            &(bounding_box.maximum + self.motion),
+        );
+
+        *bounding_box.add_bounding_box(&end_bounding_box)
+    }
+}
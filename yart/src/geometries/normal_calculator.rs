@@ -1,7 +1,13 @@
-use super::ray::Ray;
+use super::{intersection::Intersection, ray::Ray, surface_point::SurfacePoint};
 use crate::math::vector3::Vector3;
 use std::fmt::Debug;
 
 pub trait NormalCalculator: Debug {
-    fn calculate_normal(&self, ray: &Ray, hit_position: &Vector3) -> Vector3;
+    /// Calculates the normal at a hit position, facing against the ray. The intersection is the one the hit position
+    /// was found with, which identifies the primitive that was hit for geometries made of several.
+    fn calculate_normal(&self, ray: &Ray, hit_position: &Vector3, intersection: &Intersection) -> Vector3;
+
+    /// Calculates the texture coordinates, normals and tangents at a hit position. This is only done for the closest
+    /// intersection, so it's kept separate from intersecting.
+    fn calculate_surface_point(&self, ray: &Ray, hit_position: &Vector3, intersection: &Intersection) -> SurfacePoint;
 }
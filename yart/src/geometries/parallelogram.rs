@@ -3,9 +3,10 @@ use crate::{
     geometries::{
         area_light::AreaLight, bound_by_box::BoundByBox, bounding_box::BoundingBox, has_material::HasMaterial,
         intersectable::Intersectable, intersection::Intersection, normal_calculator::NormalCalculator, ray::Ray,
+        surface_point::SurfacePoint,
     },
     materials::material::MaterialIndex,
-    math::{vector::Vector, vector3::Vector3},
+    math::{vector::Vector, vector2::Vector2, vector3::Vector3},
     normalize,
     scene::Scene,
 };
@@ -41,17 +42,28 @@ impl HasMaterial for Parallelogram {
 }
 
 impl NormalCalculator for Parallelogram {
-    fn calculate_normal(&self, ray: &super::ray::Ray, _hit_position: &Vector3) -> Vector3 {
+    fn calculate_normal(&self, ray: &Ray, _hit_position: &Vector3, _intersection: &Intersection) -> Vector3 {
         if ray.direction() ^ self.normal < 0.0 {
             self.normal
         } else {
             -self.normal
         }
     }
+
+    /// The texture coordinates go from zero to one along each edge.
+    fn calculate_surface_point(&self, ray: &Ray, hit_position: &Vector3, intersection: &Intersection) -> SurfacePoint {
+        SurfacePoint::new(
+            hit_position,
+            &intersection.surface_coordinates,
+            &self.calculate_normal(ray, hit_position, intersection),
+            &self.edge1,
+            &self.edge2,
+        )
+    }
 }
 
 impl Intersectable for Parallelogram {
-    fn intersect(&self, ray: &Ray) -> Option<Intersection> {
+    fn intersect(&self, ray: &Ray) -> Option<Intersection<'_>> {
         let p = ray.direction() % self.edge2;
         let determinant = self.edge1 ^ p;
 
@@ -60,26 +72,23 @@ impl Intersectable for Parallelogram {
         let t = ray.position() - self.position;
         let a = inverse_determinant * (t ^ p);
 
-        if a < 0.0 || a > 1.0 {
+        if !(0.0..=1.0).contains(&a) {
             return None;
         }
 
         let q = t % self.edge1;
         let b = inverse_determinant * (ray.direction() ^ q);
 
-        if b < 0.0 || b > 1.0 {
+        if !(0.0..=1.0).contains(&b) {
             return None;
         }
 
         let distance = inverse_determinant * (self.edge2 ^ q);
 
-        if distance >= 0.0 {
+        if ray.contains_distance(distance) {
             Some(Intersection {
-                hit_geometry: self,
-                entrance_distance: distance,
-                exit_distance: distance,
-                mix_amount: 0.0,
-                material_index_override: 0,
+                surface_coordinates: Vector2::new(a, b),
+                ..Intersection::new(self, distance, distance, 0.0, None)
             })
         } else {
             None
@@ -95,9 +104,7 @@ impl AreaLight for Parallelogram {
         hit_normal: &Vector3,
     ) -> Vector3 {
         let point_on_light = self.get_point_on_light(rng, hit_position, hit_normal);
-        let direction_to_light = normalize!(point_on_light - hit_position);
-
-        direction_to_light
+        normalize!(point_on_light - hit_position)
     }
 
     fn get_point_on_light(&self, rng: &mut dyn RngCore, _hit_position: &Vector3, _hit_normal: &Vector3) -> Vector3 {
@@ -109,20 +116,16 @@ impl AreaLight for Parallelogram {
         _rng: &mut dyn RngCore,
         scene: &Scene,
         hit_position: &Vector3,
-        _hit_normal: &Vector3,
+        hit_normal: &Vector3,
         point_on_light: &Vector3,
+        time: Real,
     ) -> bool {
         let direction_to_light = point_on_light - hit_position;
         let distance_to_light = direction_to_light.length();
 
         let direction_to_light = normalize!(direction_to_light);
-        let ray = Ray::new(hit_position, &direction_to_light);
-
-        if let Some(distance) = scene.cast_ray_distance(&ray) {
-            !(distance >= distance_to_light - EPSILON)
-        } else {
-            false
-        }
+        let ray = Ray::new_from_surface(hit_position, hit_normal, &direction_to_light, time);
+        scene.occluded(&ray, distance_to_light - EPSILON)
     }
 
     fn calculate_inverse_pdf(
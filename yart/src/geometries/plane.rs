@@ -2,10 +2,11 @@ use crate::{
     common::Real,
     geometries::{
         bound_by_box::BoundByBox, has_material::HasMaterial, intersectable::Intersectable, intersection::Intersection,
-        normal_calculator::NormalCalculator, ray::Ray,
+        normal_calculator::NormalCalculator, ray::Ray, surface_point::SurfacePoint,
     },
     materials::material::MaterialIndex,
-    math::vector3::Vector3,
+    math::{vector2::Vector2, vector3::Vector3},
+    normalize,
 };
 
 #[derive(Debug)]
@@ -40,30 +41,39 @@ impl HasMaterial for Plane {
 }
 
 impl NormalCalculator for Plane {
-    fn calculate_normal(&self, ray: &super::ray::Ray, _hit_position: &Vector3) -> Vector3 {
+    fn calculate_normal(&self, ray: &Ray, _hit_position: &Vector3, _intersection: &Intersection) -> Vector3 {
         if ray.direction() ^ self.normal < 0.0 {
             self.normal
         } else {
             -self.normal
         }
     }
+
+    /// Projects the hit position onto two directions along the plane, so the texture coordinates are distances along
+    /// those directions.
+    fn calculate_surface_point(&self, ray: &Ray, hit_position: &Vector3, intersection: &Intersection) -> SurfacePoint {
+        let dpdu = normalize!(Vector3::build_perpendicular_vector(&self.normal));
+        let dpdv = self.normal % dpdu;
+
+        SurfacePoint::new(
+            hit_position,
+            &Vector2::new(hit_position ^ dpdu, hit_position ^ dpdv),
+            &self.calculate_normal(ray, hit_position, intersection),
+            &dpdu,
+            &dpdv,
+        )
+    }
 }
 
 impl Intersectable for Plane {
-    fn intersect(&self, ray: &Ray) -> Option<Intersection> {
+    fn intersect(&self, ray: &Ray) -> Option<Intersection<'_>> {
         let normal_dot_ray_direction = self.normal ^ ray.direction();
         let normal_dot_ray_position = self.normal ^ ray.position();
 
         let distance = -(self.distance + normal_dot_ray_position) * Real::recip(normal_dot_ray_direction);
 
-        if distance >= 0.0 {
-            Some(Intersection {
-                hit_geometry: self,
-                entrance_distance: distance,
-                exit_distance: distance,
-                mix_amount: 0.0,
-                material_index_override: 0,
-            })
+        if ray.contains_distance(distance) {
+            Some(Intersection::new(self, distance, distance, 0.0, None))
         } else {
             None
         }
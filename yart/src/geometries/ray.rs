@@ -1,13 +1,27 @@
 use crate::{
-    common::Real,
+    common::{Real, MINIMUM_SURFACE_ERROR, SURFACE_ERROR_SCALE},
     math::{vector::Vector, vector3::Vector3},
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Ray {
     position: Vector3,
     direction: Vector3,
     inverse_direction: Vector3,
+
+    /// The moment within the scene's animation at which the ray is cast. Moving geometry is positioned according to
+    /// this time which produces motion blur when the times of a pixel's rays are spread over the camera's shutter.
+    time: Real,
+
+    /// Only intersections between these distances along the ray count as hits.
+    minimum_distance: Real,
+    maximum_distance: Real,
+
+    /// Describes the cone of directions a camera ray stands for, so that textures can be filtered over the area the
+    /// ray covers rather than sampled at a single point. The cone is `cone_width` wide at the ray's position and
+    /// widens by `cone_spread` for every unit of distance. Both are zero for rays that don't track their cone.
+    cone_width: Real,
+    cone_spread: Real,
 }
 
 impl Ray {
@@ -16,15 +30,45 @@ impl Ray {
             position: *position,
             direction: *direction,
             inverse_direction: Vector3::reciprical(direction),
+            time: 0.0,
+            minimum_distance: 0.0,
+            maximum_distance: Real::INFINITY,
+            cone_width: 0.0,
+            cone_spread: 0.0,
+        }
+    }
+
+    pub fn new_at_time(position: &Vector3, direction: &Vector3, time: Real) -> Self {
+        Self {
+            position: *position,
+            direction: *direction,
+            inverse_direction: Vector3::reciprical(direction),
+            time,
+            minimum_distance: 0.0,
+            maximum_distance: Real::INFINITY,
+            cone_width: 0.0,
+            cone_spread: 0.0,
         }
     }
 
+    /// Creates a ray leaving a surface at `position`, which has `normal` as its normal on either side. The ray starts
+    /// just off the surface, on the side `direction` points to, so that it doesn't hit the surface it leaves because
+    /// of rounding errors in the position.
+    pub fn new_from_surface(position: &Vector3, normal: &Vector3, direction: &Vector3, time: Real) -> Self {
+        Self::new_at_time(&offset_from_surface(position, normal, direction), direction, time)
+    }
+
     // TODO: Better name?
     pub fn new2(position: &Vector3, direction: &Vector3, inverse_direction: &Vector3) -> Self {
         Self {
             position: *position,
             direction: *direction,
             inverse_direction: *inverse_direction,
+            time: 0.0,
+            minimum_distance: 0.0,
+            maximum_distance: Real::INFINITY,
+            cone_width: 0.0,
+            cone_spread: 0.0,
         }
     }
 
@@ -40,8 +84,99 @@ impl Ray {
         &self.inverse_direction
     }
 
+    pub fn time(&self) -> Real {
+        self.time
+    }
+
+    pub fn minimum_distance(&self) -> Real {
+        self.minimum_distance
+    }
+
+    pub fn maximum_distance(&self) -> Real {
+        self.maximum_distance
+    }
+
+    /// Returns the width of the ray's cone at a distance along the ray.
+    pub fn cone_width_at(&self, distance: Real) -> Real {
+        self.cone_width + self.cone_spread * distance
+    }
+
+    /// Returns whether a distance along the ray is within the ray's extent.
+    #[inline(always)]
+    pub fn contains_distance(&self, distance: Real) -> bool {
+        distance >= self.minimum_distance && distance <= self.maximum_distance
+    }
+
+    /// Creates a ray that starts at a different position but keeps this ray's direction and time.
+    pub fn with_position(&self, position: &Vector3) -> Self {
+        Self {
+            position: *position,
+            ..self.clone()
+        }
+    }
+
+    /// Creates a ray that keeps this ray's position, direction and time, but only counts intersections up to
+    /// `maximum_distance`. Used to cull geometry beyond the closest hit found so far.
+    pub fn with_maximum_distance(&self, maximum_distance: Real) -> Self {
+        Self {
+            maximum_distance,
+            ..self.clone()
+        }
+    }
+
+    /// Creates a ray that keeps this ray's position, direction and time, but has a different extent.
+    pub fn with_extent(&self, minimum_distance: Real, maximum_distance: Real) -> Self {
+        Self {
+            minimum_distance,
+            maximum_distance,
+            ..self.clone()
+        }
+    }
+
+    /// Creates a ray that keeps everything about this ray but the cone it stands for.
+    pub fn with_cone(&self, cone_width: Real, cone_spread: Real) -> Self {
+        Self {
+            cone_width,
+            cone_spread,
+            ..self.clone()
+        }
+    }
+
     #[inline(always)]
     pub fn position_along(&self, distance: Real) -> Vector3 {
         self.position + self.direction * distance
     }
 }
+
+/// Moves a position on a surface along the surface's normal, to the side `direction` points to, by a bound on the error
+/// in the position. The error grows with the magnitude of the position so a fixed distance would either be too small
+/// far away from the origin, particularly with `f32` precision, or needlessly large close to it.
+fn offset_from_surface(position: &Vector3, normal: &Vector3, direction: &Vector3) -> Vector3 {
+    let error = Vector3::abs(position) * SURFACE_ERROR_SCALE + Vector3::from_value(MINIMUM_SURFACE_ERROR);
+    let distance = Vector3::abs(normal) ^ error;
+
+    let offset = if (direction ^ normal) < 0.0 {
+        normal * -distance
+    } else {
+        normal * distance
+    };
+
+    let offset_position = position + offset;
+
+    // Rounding the sum could move the position back towards the surface, so round it away from it instead.
+    let round_away = |value: Real, offset: Real| {
+        if offset > 0.0 {
+            value.next_up()
+        } else if offset < 0.0 {
+            value.next_down()
+        } else {
+            value
+        }
+    };
+
+    Vector3::new(
+        round_away(offset_position.x, offset.x),
+        round_away(offset_position.y, offset.y),
+        round_away(offset_position.z, offset.z),
+    )
+}
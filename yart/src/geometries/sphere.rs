@@ -1,30 +1,44 @@
 use super::{
     has_material::HasMaterial, intersectable::Intersectable, intersection::Intersection,
-    normal_calculator::NormalCalculator, ray::Ray,
+    normal_calculator::NormalCalculator, ray::Ray, surface_point::SurfacePoint,
 };
 use crate::{
-    common::Real,
+    common::{Real, ONE_OVER_PI, ONE_OVER_TWO_PI, PI, TWO_PI},
     geometries::{bound_by_box::BoundByBox, bounding_box::BoundingBox},
     materials::material::MaterialIndex,
-    math::vector3::Vector3,
+    math::{vector2::Vector2, vector3::Vector3},
     normalize,
 };
 
 #[derive(Debug)]
 pub struct Sphere {
     position: Vector3,
+
+    /// How far the sphere moves between time zero and time one.
+    motion: Vector3,
+
     radius: Real,
     material_index: MaterialIndex,
 }
 
 impl Sphere {
     pub fn new(position: &Vector3, radius: Real, material_index: MaterialIndex) -> Self {
+        Self::new_moving(position, &Vector3::default(), radius, material_index)
+    }
+
+    /// Creates a sphere that is at `position` at time zero and moves linearly by `motion` until time one.
+    pub fn new_moving(position: &Vector3, motion: &Vector3, radius: Real, material_index: MaterialIndex) -> Self {
         Self {
             position: *position,
+            motion: *motion,
             radius,
             material_index,
         }
     }
+
+    pub fn position_at_time(&self, time: Real) -> Vector3 {
+        self.position + self.motion * time
+    }
 }
 
 impl HasMaterial for Sphere {
@@ -34,14 +48,53 @@ impl HasMaterial for Sphere {
 }
 
 impl NormalCalculator for Sphere {
-    fn calculate_normal(&self, _ray: &Ray, hit_position: &Vector3) -> Vector3 {
-        normalize!(hit_position - self.position)
+    fn calculate_normal(&self, ray: &Ray, hit_position: &Vector3, _intersection: &Intersection) -> Vector3 {
+        normalize!(hit_position - self.position_at_time(ray.time()))
+    }
+
+    /// Maps the sphere like an environment map: `u` goes around the `y` axis starting at the `+x` axis and `v` goes
+    /// from the `+y` pole to the `-y` pole.
+    fn calculate_surface_point(&self, ray: &Ray, hit_position: &Vector3, intersection: &Intersection) -> SurfacePoint {
+        let normal = self.calculate_normal(ray, hit_position, intersection);
+        let offset = normal * self.radius;
+
+        let u = (Real::atan2(offset.z, offset.x) * ONE_OVER_TWO_PI).rem_euclid(1.0);
+        let v = Real::acos(Real::clamp(normal.y, -1.0, 1.0)) * ONE_OVER_PI;
+
+        let distance_from_axis = Real::sqrt(offset.x * offset.x + offset.z * offset.z);
+
+        let (dpdu, dpdv) = if distance_from_axis > 0.0 {
+            (
+                Vector3::new(-offset.z, 0.0, offset.x) * TWO_PI,
+                Vector3::new(
+                    offset.y * offset.x / distance_from_axis,
+                    -distance_from_axis,
+                    offset.y * offset.z / distance_from_axis,
+                ) * PI,
+            )
+        } else {
+            // The tangents are undefined at the poles, so pick any that are perpendicular to the normal.
+            let tangent = normalize!(Vector3::build_perpendicular_vector(&normal));
+            (
+                tangent * (self.radius * TWO_PI),
+                (normal % tangent) * (self.radius * PI),
+            )
+        };
+
+        SurfacePoint::new(
+            &(hit_position - self.motion * ray.time()),
+            &Vector2::new(u, v),
+            &normal,
+            &dpdu,
+            &dpdv,
+        )
     }
 }
 
 impl Intersectable for Sphere {
-    fn intersect(&self, ray: &Ray) -> Option<Intersection> {
-        let v = ray.position() - self.position;
+    #[allow(clippy::eq_op)]
+    fn intersect(&self, ray: &Ray) -> Option<Intersection<'_>> {
+        let v = ray.position() - self.position_at_time(ray.time());
 
         let a = ray.direction() ^ ray.direction();
         let b = v ^ ray.direction();
@@ -57,19 +110,30 @@ impl Intersectable for Sphere {
         let reciprocal_a = Real::recip(a);
         let negative_b = -b;
 
-        let exit_distance = (negative_b + discriminant_sqrt) * reciprocal_a;
-        if exit_distance < 0.0 {
-            return None;
-        }
-
         let entrance_distance = (negative_b - discriminant_sqrt) * reciprocal_a;
+        let exit_distance = (negative_b + discriminant_sqrt) * reciprocal_a;
 
-        Some(Intersection::new(self, entrance_distance, exit_distance, 0.0, 0))
+        if ray.contains_distance(entrance_distance) {
+            Some(Intersection::new(self, entrance_distance, exit_distance, 0.0, None))
+        } else if ray.contains_distance(exit_distance) {
+            // The ray starts inside the sphere so the first surface it crosses is where it exits.
+            Some(Intersection::new(self, exit_distance, exit_distance, 0.0, None))
+        } else {
+            None
+        }
     }
 }
 
 impl BoundByBox for Sphere {
     fn calculate_bounding_box(&self) -> BoundingBox {
-        BoundingBox::new(&(self.position - self.radius), &(self.position + self.radius))
+        let end_position = self.position_at_time(1.0);
+
+        let mut bounding_box = BoundingBox::new(&(self.position - self.radius), &(self.position + self.radius));
+        bounding_box.add_bounding_box(&BoundingBox::new(
+            &(end_position - self.radius),
+            &(end_position + self.radius),
+        ));
+
+        bounding_box
     }
 }
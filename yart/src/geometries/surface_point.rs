@@ -0,0 +1,59 @@
+use crate::{
+    math::{color3::Color3, matrix3::Matrix3, matrix4::Matrix4, vector2::Vector2, vector3::Vector3},
+    normalize,
+};
+
+/// Describes the surface of a geometry at a hit position, for shading and texturing. The normals are oriented the same
+/// way as the one from [`super::normal_calculator::NormalCalculator::calculate_normal`].
+#[derive(Debug, Copy, Clone)]
+pub struct SurfacePoint {
+    /// The hit position in the space of the geometry itself, before it's transformed or moved. Solid textures are
+    /// evaluated here so that they stay on the geometry rather than slide through it.
+    pub object_position: Vector3,
+
+    /// The texture coordinates of the hit position.
+    pub uv: Vector2,
+
+    /// The normal of the surface itself, such as the face normal of a triangle.
+    pub geometric_normal: Vector3,
+
+    /// The normal used for shading, such as the interpolated vertex normals of a triangle.
+    pub shading_normal: Vector3,
+
+    /// How the position changes along the `u` and `v` texture coordinates. Together with the shading normal they
+    /// form the tangent frame normal maps are defined in.
+    pub dpdu: Vector3,
+    pub dpdv: Vector3,
+
+    /// The interpolated vertex colors of meshes that have them.
+    pub vertex_color: Option<Color3>,
+}
+
+impl SurfacePoint {
+    /// Creates a surface point for a surface that doesn't distinguish its shading normal from its geometric normal.
+    pub fn new(object_position: &Vector3, uv: &Vector2, normal: &Vector3, dpdu: &Vector3, dpdv: &Vector3) -> Self {
+        Self {
+            object_position: *object_position,
+            uv: *uv,
+            geometric_normal: *normal,
+            shading_normal: *normal,
+            dpdu: *dpdu,
+            dpdv: *dpdv,
+            vertex_color: None,
+        }
+    }
+
+    /// Moves the surface point into the space described by a transformation and its normal matrix. The object position
+    /// stays in the space of the geometry.
+    pub fn transform(&self, transformation: &Matrix4, normal_matrix: &Matrix3) -> Self {
+        Self {
+            object_position: self.object_position,
+            uv: self.uv,
+            geometric_normal: normalize!(normal_matrix * self.geometric_normal),
+            shading_normal: normalize!(normal_matrix * self.shading_normal),
+            dpdu: transformation.transform_vector(&self.dpdu),
+            dpdv: transformation.transform_vector(&self.dpdv),
+            vertex_color: self.vertex_color,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{
+    common::Real,
+    geometries::{
+        bound_by_box::BoundByBox, bounding_box::BoundingBox, intersectable::Intersectable, intersection::Intersection,
+        ray::Ray,
+    },
+    math::{matrix3::Matrix3, matrix4::Matrix4, vector::Vector},
+};
+
+/// Places a child in the scene with an affine transformation. Rays are transformed into the space of the child
+/// rather than transforming the child itself, so any geometry can be scaled, rotated or sheared.
+#[derive(Debug)]
+pub struct Transformed {
+    transformation: Matrix4,
+    inverse_transformation: Matrix4,
+    normal_matrix: Matrix3,
+
+    child: Box<dyn Intersectable>,
+}
+
+impl Transformed {
+    /// Creates a new [`Transformed`]. Returns `None` if the transformation can't be inverted.
+    pub fn new(transformation: &Matrix4, child: Box<dyn Intersectable>) -> Option<Self> {
+        Some(Self {
+            transformation: *transformation,
+            inverse_transformation: transformation.inverse()?,
+            normal_matrix: transformation.normal_matrix()?,
+            child,
+        })
+    }
+
+    pub fn transformation(&self) -> &Matrix4 {
+        &self.transformation
+    }
+
+    /// Transforms a ray into the space of the child. Returns the transformed ray along with how much longer distances
+    /// along it are than the corresponding distances along the original ray.
+    fn create_child_ray(&self, ray: &Ray) -> (Ray, Real) {
+        let child_direction = self.inverse_transformation.transform_vector(ray.direction());
+
+        // Children expect normalized directions, so distances along the original ray have to be scaled to distances
+        // along the child ray and back.
+        let child_direction_length = child_direction.length();
+
+        let child_ray = Ray::new_at_time(
+            &self.inverse_transformation.transform_point(ray.position()),
+            &(child_direction / child_direction_length),
+            ray.time(),
+        )
+        .with_extent(
+            ray.minimum_distance() * child_direction_length,
+            ray.maximum_distance() * child_direction_length,
+        );
+
+        (child_ray, child_direction_length)
+    }
+}
+
+impl Intersectable for Transformed {
+    fn intersect(&self, ray: &Ray) -> Option<Intersection<'_>> {
+        let (child_ray, child_direction_length) = self.create_child_ray(ray);
+
+        let intersection = self.child.intersect(&child_ray)?;
+
+        // The child can only calculate its surface from the transformed ray so calculate it now while that ray is
+        // around.
+        let child_surface_point = intersection.surface_override.unwrap_or_else(|| {
+            let child_hit_position = child_ray.position_along(intersection.entrance_distance);
+            intersection
+                .hit_geometry
+                .calculate_surface_point(&child_ray, &child_hit_position, &intersection)
+        });
+
+        Some(Intersection {
+            entrance_distance: intersection.entrance_distance / child_direction_length,
+            exit_distance: intersection.exit_distance / child_direction_length,
+            surface_override: Some(child_surface_point.transform(&self.transformation, &self.normal_matrix)),
+            ..intersection
+        })
+    }
+
+    fn occluded(&self, ray: &Ray, filter: &dyn Fn(&Intersection) -> bool) -> bool {
+        let (child_ray, _) = self.create_child_ray(ray);
+        self.child.occluded(&child_ray, filter)
+    }
+}
+
+impl BoundByBox for Transformed {
+    fn calculate_bounding_box(&self) -> BoundingBox {
+        self.child.calculate_bounding_box().transform(&self.transformation)
+    }
+}
@@ -2,10 +2,10 @@ use crate::{
     common::Real,
     geometries::{
         bound_by_box::BoundByBox, bounding_box::BoundingBox, has_material::HasMaterial, intersectable::Intersectable,
-        intersection::Intersection, normal_calculator::NormalCalculator, ray::Ray,
+        intersection::Intersection, normal_calculator::NormalCalculator, ray::Ray, surface_point::SurfacePoint,
     },
     materials::material::MaterialIndex,
-    math::vector3::Vector3,
+    math::{vector2::Vector2, vector3::Vector3},
     normalize,
 };
 
@@ -19,6 +19,9 @@ pub struct Triangle {
     normal1: Vector3,
     normal2: Vector3,
 
+    /// How far the triangle moves between time zero and time one.
+    motion: Vector3,
+
     material_index: MaterialIndex,
 }
 
@@ -31,6 +34,30 @@ impl Triangle {
         normal1: &Vector3,
         normal2: &Vector3,
         material_index: MaterialIndex,
+    ) -> Self {
+        Self::new_moving(
+            vertex0,
+            vertex1,
+            vertex2,
+            normal0,
+            normal1,
+            normal2,
+            &Vector3::default(),
+            material_index,
+        )
+    }
+
+    /// Creates a triangle that is at the given vertices at time zero and moves linearly by `motion` until time one.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_moving(
+        vertex0: &Vector3,
+        vertex1: &Vector3,
+        vertex2: &Vector3,
+        normal0: &Vector3,
+        normal1: &Vector3,
+        normal2: &Vector3,
+        motion: &Vector3,
+        material_index: MaterialIndex,
     ) -> Self {
         Self {
             vertex0: *vertex0,
@@ -41,10 +68,13 @@ impl Triangle {
             normal1: *normal1,
             normal2: *normal2,
 
+            motion: *motion,
+
             material_index,
         }
     }
 
+    #[allow(clippy::eq_op)]
     pub fn barycentric_coordinates(&self, point_in_triangle: &Vector3) -> Vector3 {
         let v0 = self.vertex1 - self.vertex0;
         let v1 = self.vertex2 - self.vertex0;
@@ -82,8 +112,9 @@ impl HasMaterial for Triangle {
 }
 
 impl NormalCalculator for Triangle {
-    fn calculate_normal(&self, ray: &Ray, hit_position: &Vector3) -> Vector3 {
-        let barycentric_coordinates = self.barycentric_coordinates(hit_position);
+    fn calculate_normal(&self, ray: &Ray, hit_position: &Vector3, _intersection: &Intersection) -> Vector3 {
+        // Move the hit position back to where it would be on the triangle at time zero.
+        let barycentric_coordinates = self.barycentric_coordinates(&(hit_position - self.motion * ray.time()));
 
         let normal = normalize!(
             self.normal0 * barycentric_coordinates.x
@@ -97,10 +128,40 @@ impl NormalCalculator for Triangle {
             -normal
         }
     }
+
+    /// The texture coordinates are the barycentric coordinates of the second and third vertex, so they go from zero to
+    /// one along the edges from the first vertex.
+    fn calculate_surface_point(&self, ray: &Ray, hit_position: &Vector3, intersection: &Intersection) -> SurfacePoint {
+        let edge1 = self.vertex1 - self.vertex0;
+        let edge2 = self.vertex2 - self.vertex0;
+
+        let uv = intersection.surface_coordinates;
+
+        let face_normal = normalize!(edge1 % edge2);
+        let vertex_normal = normalize!(self.normal0 * (1.0 - uv.x - uv.y) + self.normal1 * uv.x + self.normal2 * uv.y);
+
+        let face_ray = |normal: Vector3| {
+            if ray.direction() ^ normal < 0.0 {
+                normal
+            } else {
+                -normal
+            }
+        };
+
+        SurfacePoint {
+            object_position: hit_position - self.motion * ray.time(),
+            uv,
+            geometric_normal: face_ray(face_normal),
+            shading_normal: face_ray(vertex_normal),
+            dpdu: edge1,
+            dpdv: edge2,
+            vertex_color: None,
+        }
+    }
 }
 
 impl Intersectable for Triangle {
-    fn intersect(&self, ray: &Ray) -> Option<Intersection> {
+    fn intersect(&self, ray: &Ray) -> Option<Intersection<'_>> {
         let edge1 = self.vertex1 - self.vertex0;
         let edge2 = self.vertex2 - self.vertex0;
 
@@ -110,10 +171,10 @@ impl Intersectable for Triangle {
         // Normally you would check for a parallel ray here but we'll skip that check.
 
         let f = Real::recip(a);
-        let s = ray.position() - self.vertex0;
+        let s = ray.position() - self.motion * ray.time() - self.vertex0;
         let u = f * (s ^ h);
 
-        if u < 0.0 || u > 1.0 {
+        if !(0.0..=1.0).contains(&u) {
             return None;
         }
 
@@ -126,13 +187,10 @@ impl Intersectable for Triangle {
 
         let distance = f * (edge2 ^ q);
 
-        if distance >= 0.0 {
+        if ray.contains_distance(distance) {
             Some(Intersection {
-                hit_geometry: self,
-                entrance_distance: distance,
-                exit_distance: distance,
-                mix_amount: 0.0,
-                material_index_override: 0,
+                surface_coordinates: Vector2::new(u, v),
+                ..Intersection::new(self, distance, distance, 0.0, None)
             })
         } else {
             None
@@ -142,6 +200,16 @@ impl Intersectable for Triangle {
 
 impl BoundByBox for Triangle {
     fn calculate_bounding_box(&self) -> BoundingBox {
-        BoundingBox::from_points([self.vertex0, self.vertex1, self.vertex2].iter())
+        BoundingBox::from_points(
+            [
+                self.vertex0,
+                self.vertex1,
+                self.vertex2,
+                self.vertex0 + self.motion,
+                self.vertex1 + self.motion,
+                self.vertex2 + self.motion,
+            ]
+            .iter(),
+        )
     }
 }
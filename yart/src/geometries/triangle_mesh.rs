@@ -0,0 +1,210 @@
+use super::{
+    bound_by_box::BoundByBox,
+    bounding_box::BoundingBox,
+    bounding_box_hierarchy::{BoundingBoxHierarchy, BoundingBoxHierarchyNode, BoundingBoxHierarchyOptions},
+    has_material::HasMaterial,
+    intersectable::Intersectable,
+    intersection::Intersection,
+    mesh_buffers::MeshBuffers,
+    mesh_triangle::MeshTriangle,
+    normal_calculator::NormalCalculator,
+    ray::Ray,
+    surface_point::SurfacePoint,
+    triangle_packet::{TrianglePacket, PACKET_WIDTH},
+    wide_bounding_box_hierarchy::WideBoundingBoxHierarchy,
+};
+use crate::{
+    common::Real,
+    materials::material::MaterialIndex,
+    math::{vector2::Vector2, vector3::Vector3},
+};
+use std::{ops::Range, sync::Arc};
+
+/// A mesh of triangles sharing a single set of vertex buffers and a material. The triangles only store indices into
+/// the buffers and are organized in their own bounding box hierarchy so that a mesh can be placed in a scene like any
+/// other geometry.
+///
+/// The mesh itself is the geometry its intersections refer to, with the primitive id of an intersection being the
+/// index of the triangle that was hit.
+#[derive(Debug)]
+pub struct TriangleMesh {
+    buffers: Arc<MeshBuffers>,
+    triangles: Vec<MeshTriangle>,
+    material_index: MaterialIndex,
+
+    hierarchy: WideBoundingBoxHierarchy<TrianglePacket>,
+}
+
+impl TriangleMesh {
+    pub fn new(buffers: &Arc<MeshBuffers>, triangles: Vec<MeshTriangle>, material_index: MaterialIndex) -> Self {
+        let default_options = BoundingBoxHierarchyOptions::default();
+
+        let triangle_bounds = triangles
+            .iter()
+            .enumerate()
+            .map(|(index, triangle)| TriangleBounds {
+                primitive_id: index as u32,
+                bounding_box: triangle.calculate_bounding_box(buffers),
+            })
+            .collect();
+
+        // Group triangles that are close together into packets with a hierarchy over the triangles, then build the
+        // hierarchy that is actually traversed over the packets.
+        let grouping = BoundingBoxHierarchy::new(triangle_bounds, &default_options);
+
+        let mut packet_ranges = Vec::new();
+
+        if !grouping.nodes().is_empty() {
+            collect_packet_ranges(grouping.nodes(), 0, &mut packet_ranges);
+        }
+
+        let triangle_bounds = grouping.into_primitives();
+
+        let packets = packet_ranges
+            .into_iter()
+            .map(|range| {
+                let primitive_ids = triangle_bounds[range]
+                    .iter()
+                    .map(|x| x.primitive_id)
+                    .collect::<Vec<_>>();
+                TrianglePacket::new(buffers, &triangles, &primitive_ids)
+            })
+            .collect();
+
+        Self {
+            buffers: buffers.clone(),
+            triangles,
+            material_index,
+            hierarchy: WideBoundingBoxHierarchy::new(
+                packets,
+                &BoundingBoxHierarchyOptions::new(1, default_options.maximum_depth, default_options.bin_count),
+            ),
+        }
+    }
+
+    pub fn buffers(&self) -> &MeshBuffers {
+        &self.buffers
+    }
+
+    pub fn triangles(&self) -> &[MeshTriangle] {
+        &self.triangles
+    }
+
+    /// Creates the intersection for a hit of a triangle at `distance`, where `surface_coordinates` are the barycentric
+    /// coordinates of the second and third vertex.
+    fn create_intersection(
+        &self,
+        primitive_id: u32,
+        distance: Real,
+        surface_coordinates: &Vector2,
+    ) -> Intersection<'_> {
+        Intersection {
+            surface_coordinates: *surface_coordinates,
+            primitive_id,
+            ..Intersection::new(self, distance, distance, 0.0, None)
+        }
+    }
+}
+
+/// The bounds of a triangle, which the triangles are grouped into packets by.
+#[derive(Debug)]
+struct TriangleBounds {
+    primitive_id: u32,
+    bounding_box: BoundingBox,
+}
+
+impl BoundByBox for TriangleBounds {
+    fn calculate_bounding_box(&self) -> BoundingBox {
+        self.bounding_box
+    }
+}
+
+/// Appends the ranges of the triangles that make up each packet below a node of the grouping hierarchy, and returns
+/// the range of all the triangles below the node. The hierarchy is stored depth first so the triangles below a node
+/// are next to each other, and every node with few enough of them becomes a single packet.
+fn collect_packet_ranges(
+    nodes: &[BoundingBoxHierarchyNode],
+    node_index: usize,
+    packet_ranges: &mut Vec<Range<usize>>,
+) -> Range<usize> {
+    let node = &nodes[node_index];
+
+    if node.is_leaf() {
+        let range = node.primitive_range();
+
+        packet_ranges.extend(
+            range
+                .clone()
+                .step_by(PACKET_WIDTH)
+                .map(|start| start..usize::min(start + PACKET_WIDTH, range.end)),
+        );
+
+        return range;
+    }
+
+    let first_range = collect_packet_ranges(nodes, node_index + 1, packet_ranges);
+    let second_range = collect_packet_ranges(nodes, node.second_child_index(), packet_ranges);
+
+    let range = first_range.start..second_range.end;
+
+    if range.len() <= PACKET_WIDTH {
+        // Replace the packets of the children, which were the last to be added.
+        while packet_ranges.last().is_some_and(|x| x.start >= range.start) {
+            packet_ranges.pop();
+        }
+
+        packet_ranges.push(range.clone());
+    }
+
+    range
+}
+
+impl HasMaterial for TriangleMesh {
+    fn material_index(&self) -> MaterialIndex {
+        self.material_index
+    }
+}
+
+impl NormalCalculator for TriangleMesh {
+    fn calculate_normal(&self, ray: &Ray, _hit_position: &Vector3, intersection: &Intersection) -> Vector3 {
+        self.triangles[intersection.primitive_id as usize].calculate_normal(
+            &self.buffers,
+            ray,
+            &intersection.surface_coordinates,
+        )
+    }
+
+    fn calculate_surface_point(&self, ray: &Ray, hit_position: &Vector3, intersection: &Intersection) -> SurfacePoint {
+        self.triangles[intersection.primitive_id as usize].calculate_surface_point(
+            &self.buffers,
+            ray,
+            hit_position,
+            &intersection.surface_coordinates,
+        )
+    }
+}
+
+impl Intersectable for TriangleMesh {
+    fn intersect(&self, ray: &Ray) -> Option<Intersection<'_>> {
+        self.hierarchy.intersect_with(ray, |packet, ray| {
+            let (primitive_id, distance, surface_coordinates) = packet.intersect(ray)?;
+            Some(self.create_intersection(primitive_id, distance, &surface_coordinates))
+        })
+    }
+
+    fn occluded(&self, ray: &Ray, filter: &dyn Fn(&Intersection) -> bool) -> bool {
+        self.hierarchy.occluded_with(ray, |packet, ray| {
+            packet
+                .intersect_all(ray)
+                .any(|(primitive_id, distance, surface_coordinates)| {
+                    filter(&self.create_intersection(primitive_id, distance, &surface_coordinates))
+                })
+        })
+    }
+}
+
+impl BoundByBox for TriangleMesh {
+    fn calculate_bounding_box(&self) -> BoundingBox {
+        self.hierarchy.calculate_bounding_box()
+    }
+}
@@ -0,0 +1,221 @@
+use super::{
+    bound_by_box::BoundByBox, bounding_box::BoundingBox, mesh_buffers::MeshBuffers, mesh_triangle::MeshTriangle,
+    ray::Ray,
+};
+use crate::{common::Real, math::vector2::Vector2};
+#[cfg(feature = "simd")]
+use std::simd::{cmp::SimdPartialOrd, num::SimdFloat, Select, Simd};
+
+/// The most triangles a [`TrianglePacket`] holds.
+pub const PACKET_WIDTH: usize = 4;
+
+/// Up to [`PACKET_WIDTH`] triangles of a mesh that are close together. Their vertices are copied out of the mesh's
+/// buffers and stored axis by axis rather than triangle by triangle so that a ray can be tested against all of them at
+/// once. Without the `simd` feature the triangles are tested one after another instead.
+///
+/// A packet only knows where its triangles are, so the mesh turns its hits into intersections.
+#[derive(Debug)]
+pub struct TrianglePacket {
+    /// The first vertex and the edges from it to the other two of each triangle, per axis. Unused entries are zero,
+    /// which no ray can hit.
+    vertex0: [[Real; PACKET_WIDTH]; 3],
+    edge1: [[Real; PACKET_WIDTH]; 3],
+    edge2: [[Real; PACKET_WIDTH]; 3],
+
+    /// The index of each triangle within its mesh.
+    primitive_ids: [u32; PACKET_WIDTH],
+    triangle_count: usize,
+
+    bounding_box: BoundingBox,
+}
+
+impl TrianglePacket {
+    /// Creates a packet of at most [`PACKET_WIDTH`] of the triangles of a mesh, identified by their index.
+    pub fn new(buffers: &MeshBuffers, triangles: &[MeshTriangle], primitive_ids: &[u32]) -> Self {
+        assert!(primitive_ids.len() <= PACKET_WIDTH);
+
+        let mut vertex0 = [[0.0; PACKET_WIDTH]; 3];
+        let mut edge1 = [[0.0; PACKET_WIDTH]; 3];
+        let mut edge2 = [[0.0; PACKET_WIDTH]; 3];
+
+        let mut bounding_box = BoundingBox::new_inverse_infinity();
+
+        for (lane, &primitive_id) in primitive_ids.iter().enumerate() {
+            let triangle = &triangles[primitive_id as usize];
+
+            let first_vertex = triangle.vertex(buffers, 0);
+            let first_edge = triangle.vertex(buffers, 1) - first_vertex;
+            let second_edge = triangle.vertex(buffers, 2) - first_vertex;
+
+            bounding_box.add_bounding_box(&triangle.calculate_bounding_box(buffers));
+
+            for axis in 0..3 {
+                vertex0[axis][lane] = first_vertex[axis];
+                edge1[axis][lane] = first_edge[axis];
+                edge2[axis][lane] = second_edge[axis];
+            }
+        }
+
+        Self {
+            vertex0,
+            edge1,
+            edge2,
+            primitive_ids: std::array::from_fn(|lane| primitive_ids.get(lane).copied().unwrap_or(0)),
+            triangle_count: primitive_ids.len(),
+            bounding_box,
+        }
+    }
+
+    /// The indices within the mesh of the triangles of the packet.
+    pub fn primitive_ids(&self) -> &[u32] {
+        &self.primitive_ids[..self.triangle_count]
+    }
+
+    /// Finds the closest triangle hit by the ray within its extent. Returns the index of the triangle within its mesh,
+    /// the distance to the hit and the barycentric coordinates of the second and third vertex.
+    pub fn intersect(&self, ray: &Ray) -> Option<(u32, Real, Vector2)> {
+        let (lane, (distance, surface_coordinates)) = self
+            .calculate_hits(ray)
+            .into_iter()
+            .enumerate()
+            .min_by(|(_, (a, _)), (_, (b, _))| a.total_cmp(b))?;
+
+        if distance < Real::INFINITY {
+            Some((self.primitive_ids[lane], distance, surface_coordinates))
+        } else {
+            None
+        }
+    }
+
+    /// Finds every triangle hit by the ray within its extent, in no particular order.
+    pub fn intersect_all(&self, ray: &Ray) -> impl Iterator<Item = (u32, Real, Vector2)> + '_ {
+        self.calculate_hits(ray)
+            .into_iter()
+            .enumerate()
+            .filter(|(_, (distance, _))| *distance < Real::INFINITY)
+            .map(|(lane, (distance, surface_coordinates))| (self.primitive_ids[lane], distance, surface_coordinates))
+    }
+
+    /// Returns the distance to each triangle hit by the ray within its extent, or infinity for the triangles it
+    /// misses, along with the barycentric coordinates of the second and third vertex of each hit.
+    #[cfg(feature = "simd")]
+    #[inline(always)]
+    fn calculate_hits(&self, ray: &Ray) -> [(Real, Vector2); PACKET_WIDTH] {
+        type Lanes = Simd<Real, PACKET_WIDTH>;
+        type Vector = [Lanes; 3];
+
+        fn splat(value: &crate::math::vector3::Vector3) -> Vector {
+            [Lanes::splat(value.x), Lanes::splat(value.y), Lanes::splat(value.z)]
+        }
+
+        fn dot(a: &Vector, b: &Vector) -> Lanes {
+            a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+        }
+
+        fn cross(a: &Vector, b: &Vector) -> Vector {
+            [
+                a[1] * b[2] - a[2] * b[1],
+                a[2] * b[0] - a[0] * b[2],
+                a[0] * b[1] - a[1] * b[0],
+            ]
+        }
+
+        let vertex0 = self.vertex0.map(Lanes::from_array);
+        let edge1 = self.edge1.map(Lanes::from_array);
+        let edge2 = self.edge2.map(Lanes::from_array);
+
+        let direction = splat(ray.direction());
+        let position = splat(ray.position());
+
+        // The Möller-Trumbore test, for every lane at once.
+        let h = cross(&direction, &edge2);
+        let f = dot(&edge1, &h).recip();
+
+        let s = [
+            position[0] - vertex0[0],
+            position[1] - vertex0[1],
+            position[2] - vertex0[2],
+        ];
+        let u = f * dot(&s, &h);
+
+        let q = cross(&s, &edge1);
+        let v = f * dot(&direction, &q);
+
+        let distance = f * dot(&edge2, &q);
+
+        let zero = Lanes::splat(0.0);
+        let one = Lanes::splat(1.0);
+
+        let hits = u.simd_ge(zero)
+            & u.simd_le(one)
+            & v.simd_ge(zero)
+            & (u + v).simd_le(one)
+            & distance.simd_ge(Lanes::splat(ray.minimum_distance()))
+            & distance.simd_le(Lanes::splat(ray.maximum_distance()));
+
+        let distances = hits.select(distance, Lanes::splat(Real::INFINITY)).to_array();
+        let (u, v) = (u.to_array(), v.to_array());
+
+        std::array::from_fn(|lane| (distances[lane], Vector2::new(u[lane], v[lane])))
+    }
+
+    /// Returns the distance to each triangle hit by the ray within its extent, or infinity for the triangles it
+    /// misses, along with the barycentric coordinates of the second and third vertex of each hit.
+    #[cfg(not(feature = "simd"))]
+    #[inline(always)]
+    fn calculate_hits(&self, ray: &Ray) -> [(Real, Vector2); PACKET_WIDTH] {
+        let mut hits = [(Real::INFINITY, Vector2::default()); PACKET_WIDTH];
+
+        for (lane, hit) in hits.iter_mut().enumerate().take(self.triangle_count) {
+            let vertex0 = [0, 1, 2].map(|axis| self.vertex0[axis][lane]);
+            let edge1 = [0, 1, 2].map(|axis| self.edge1[axis][lane]);
+            let edge2 = [0, 1, 2].map(|axis| self.edge2[axis][lane]);
+
+            let direction = ray.direction();
+            let position = ray.position();
+
+            let h = [
+                direction.y * edge2[2] - direction.z * edge2[1],
+                direction.z * edge2[0] - direction.x * edge2[2],
+                direction.x * edge2[1] - direction.y * edge2[0],
+            ];
+            let f = Real::recip(edge1[0] * h[0] + edge1[1] * h[1] + edge1[2] * h[2]);
+
+            let s = [
+                position.x - vertex0[0],
+                position.y - vertex0[1],
+                position.z - vertex0[2],
+            ];
+            let u = f * (s[0] * h[0] + s[1] * h[1] + s[2] * h[2]);
+
+            if !(0.0..=1.0).contains(&u) {
+                continue;
+            }
+
+            let q = [
+                s[1] * edge1[2] - s[2] * edge1[1],
+                s[2] * edge1[0] - s[0] * edge1[2],
+                s[0] * edge1[1] - s[1] * edge1[0],
+            ];
+            let v = f * (direction.x * q[0] + direction.y * q[1] + direction.z * q[2]);
+
+            if v < 0.0 || u + v > 1.0 {
+                continue;
+            }
+
+            let distance = f * (edge2[0] * q[0] + edge2[1] * q[1] + edge2[2] * q[2]);
+
+            if ray.contains_distance(distance) {
+                *hit = (distance, Vector2::new(u, v));
+            }
+        }
+
+        hits
+    }
+}
+
+impl BoundByBox for TrianglePacket {
+    fn calculate_bounding_box(&self) -> BoundingBox {
+        self.bounding_box
+    }
+}
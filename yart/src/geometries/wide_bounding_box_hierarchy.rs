@@ -0,0 +1,356 @@
+use crate::{
+    common::Real,
+    geometries::{
+        bound_by_box::BoundByBox,
+        bounding_box::BoundingBox,
+        bounding_box_hierarchy::{
+            BoundingBoxHierarchy, BoundingBoxHierarchyNode, BoundingBoxHierarchyOptions, MAXIMUM_DEPTH,
+        },
+        intersectable::Intersectable,
+        intersection::Intersection,
+        ray::Ray,
+    },
+};
+#[cfg(feature = "simd")]
+use std::simd::{cmp::SimdPartialOrd, num::SimdFloat, Select, Simd};
+
+/// The number of children of each node of a [`WideBoundingBoxHierarchy`].
+pub const NODE_WIDTH: usize = 4;
+
+/// The most entries the traversal stack can hold. Visiting a node removes one entry and adds at most one per child,
+/// once for every level of the hierarchy.
+const STACK_SIZE: usize = (NODE_WIDTH - 1) * MAXIMUM_DEPTH as usize + 1;
+
+/// A node with up to [`NODE_WIDTH`] children. The bounds of the children are stored axis by axis rather than child by
+/// child so that a ray can be tested against all of them at once.
+#[derive(Debug, Copy, Clone)]
+struct WideNode {
+    /// The bounds of the children, per axis. Unused children have inverted bounds that no ray can enter.
+    minimum: [[f32; NODE_WIDTH]; 3],
+    maximum: [[f32; NODE_WIDTH]; 3],
+
+    /// The index of the node of each child, or of its first primitive if it's a leaf.
+    offsets: [u32; NODE_WIDTH],
+
+    /// The number of primitives of each child that is a leaf, or zero for the other children.
+    counts: [u32; NODE_WIDTH],
+}
+
+impl WideNode {
+    fn new() -> Self {
+        Self {
+            minimum: [[f32::INFINITY; NODE_WIDTH]; 3],
+            maximum: [[f32::NEG_INFINITY; NODE_WIDTH]; 3],
+            offsets: [0; NODE_WIDTH],
+            counts: [0; NODE_WIDTH],
+        }
+    }
+
+    fn set_child(&mut self, child: usize, node: &BoundingBoxHierarchyNode, offset: u32, count: u32) {
+        for axis in 0..3 {
+            self.minimum[axis][child] = node.minimum()[axis];
+            self.maximum[axis][child] = node.maximum()[axis];
+        }
+
+        self.offsets[child] = offset;
+        self.counts[child] = count;
+    }
+
+    /// Returns the distance at which the ray enters the bounds of each child, or infinity for the children it misses
+    /// within its extent or only enters beyond `maximum_distance`.
+    #[cfg(feature = "simd")]
+    #[inline(always)]
+    fn calculate_entrance_distances(&self, ray: &Ray, maximum_distance: Real) -> [Real; NODE_WIDTH] {
+        type Lanes = Simd<Real, NODE_WIDTH>;
+
+        let mut entrance_distances = Lanes::splat(ray.minimum_distance());
+        let mut exit_distances = Lanes::splat(maximum_distance);
+
+        for axis in 0..3 {
+            let minimum = Simd::<f32, NODE_WIDTH>::from_array(self.minimum[axis]).cast::<Real>();
+            let maximum = Simd::<f32, NODE_WIDTH>::from_array(self.maximum[axis]).cast::<Real>();
+
+            // Picking the planes by the sign of the direction, rather than taking the minimum and maximum of the
+            // distances to them, keeps the inverted bounds of unused children from being hit.
+            let (near, far) = if ray.inverse_direction()[axis] < 0.0 {
+                (maximum, minimum)
+            } else {
+                (minimum, maximum)
+            };
+
+            let position = Lanes::splat(ray.position()[axis]);
+            let inverse_direction = Lanes::splat(ray.inverse_direction()[axis]);
+
+            entrance_distances = entrance_distances.simd_max((near - position) * inverse_direction);
+            exit_distances = exit_distances.simd_min((far - position) * inverse_direction);
+        }
+
+        entrance_distances
+            .simd_le(exit_distances)
+            .select(entrance_distances, Lanes::splat(Real::INFINITY))
+            .to_array()
+    }
+
+    /// Returns the distance at which the ray enters the bounds of each child, or infinity for the children it misses
+    /// within its extent or only enters beyond `maximum_distance`.
+    #[cfg(not(feature = "simd"))]
+    #[inline(always)]
+    fn calculate_entrance_distances(&self, ray: &Ray, maximum_distance: Real) -> [Real; NODE_WIDTH] {
+        let mut entrance_distances = [ray.minimum_distance(); NODE_WIDTH];
+        let mut exit_distances = [maximum_distance; NODE_WIDTH];
+
+        for axis in 0..3 {
+            let position = ray.position()[axis];
+            let inverse_direction = ray.inverse_direction()[axis];
+
+            // Picking the planes by the sign of the direction, rather than taking the minimum and maximum of the
+            // distances to them, keeps the inverted bounds of unused children from being hit.
+            let (near, far) = if inverse_direction < 0.0 {
+                (&self.maximum[axis], &self.minimum[axis])
+            } else {
+                (&self.minimum[axis], &self.maximum[axis])
+            };
+
+            for child in 0..NODE_WIDTH {
+                entrance_distances[child] = Real::max(
+                    entrance_distances[child],
+                    (near[child] as Real - position) * inverse_direction,
+                );
+                exit_distances[child] = Real::min(
+                    exit_distances[child],
+                    (far[child] as Real - position) * inverse_direction,
+                );
+            }
+        }
+
+        for child in 0..NODE_WIDTH {
+            if entrance_distances[child] > exit_distances[child] {
+                entrance_distances[child] = Real::INFINITY;
+            }
+        }
+
+        entrance_distances
+    }
+}
+
+/// A bounding box hierarchy whose nodes have up to [`NODE_WIDTH`] children, which a ray is tested against all at once.
+/// Compared to a binary hierarchy there are fewer levels to descend through and more work to do at each, which suits
+/// SIMD instructions. Without the `simd` feature the children are tested one after another instead.
+///
+/// Like [`BoundingBoxHierarchy`] the children hit by a ray are visited nearest first, and nodes the ray only enters
+/// beyond the closest intersection found so far are skipped.
+#[derive(Debug)]
+pub struct WideBoundingBoxHierarchy<T: BoundByBox> {
+    nodes: Vec<WideNode>,
+    bounding_box: BoundingBox,
+    primitives: Vec<T>,
+}
+
+impl<T: BoundByBox> WideBoundingBoxHierarchy<T> {
+    /// Builds a binary hierarchy with the surface area heuristic and collapses it into a wide one.
+    ///
+    /// Every primitive must have finite bounds.
+    pub fn new(primitives: Vec<T>, options: &BoundingBoxHierarchyOptions) -> Self {
+        Self::from_hierarchy(BoundingBoxHierarchy::new(primitives, options))
+    }
+
+    /// Collapses a binary hierarchy by pulling the grandchildren of each node up into it until it has as many
+    /// children as fit. The children with the largest bounds are opened up first since rays are most likely to hit
+    /// them.
+    pub fn from_hierarchy(hierarchy: BoundingBoxHierarchy<T>) -> Self {
+        let mut nodes = Vec::new();
+        let bounding_box = hierarchy.calculate_bounding_box();
+
+        if !hierarchy.nodes().is_empty() {
+            collapse_node(hierarchy.nodes(), 0, &mut nodes);
+        }
+
+        Self {
+            nodes,
+            bounding_box,
+            primitives: hierarchy.into_primitives(),
+        }
+    }
+
+    pub fn primitives(&self) -> &[T] {
+        &self.primitives
+    }
+}
+
+/// Appends the wide node for a binary node, followed by the wide nodes below it. Returns the index of the wide node.
+fn collapse_node(binary_nodes: &[BoundingBoxHierarchyNode], binary_index: usize, nodes: &mut Vec<WideNode>) -> usize {
+    let children_of = |index: usize| [index + 1, binary_nodes[index].second_child_index()];
+
+    let mut children = if binary_nodes[binary_index].is_leaf() {
+        vec![binary_index]
+    } else {
+        children_of(binary_index).to_vec()
+    };
+
+    while children.len() < NODE_WIDTH {
+        let largest_inner_child = children
+            .iter()
+            .enumerate()
+            .filter(|(_, &x)| !binary_nodes[x].is_leaf())
+            .max_by(|(_, &a), (_, &b)| {
+                let area_a = binary_nodes[a].calculate_bounding_box().calculate_surface_area();
+                let area_b = binary_nodes[b].calculate_bounding_box().calculate_surface_area();
+
+                area_a.total_cmp(&area_b)
+            });
+
+        let Some((position, &child)) = largest_inner_child else {
+            break;
+        };
+
+        children.swap_remove(position);
+        children.extend(children_of(child));
+    }
+
+    let node_index = nodes.len();
+    nodes.push(WideNode::new());
+
+    for (child, &binary_child) in children.iter().enumerate() {
+        let binary_node = &binary_nodes[binary_child];
+
+        let (offset, count) = if binary_node.is_leaf() {
+            let range = binary_node.primitive_range();
+            (range.start as u32, range.len() as u32)
+        } else {
+            (collapse_node(binary_nodes, binary_child, nodes) as u32, 0)
+        };
+
+        nodes[node_index].set_child(child, binary_node, offset, count);
+    }
+
+    node_index
+}
+
+impl<T: BoundByBox> WideBoundingBoxHierarchy<T> {
+    /// Finds the closest intersection with the primitives, which are intersected by `intersect_primitive`. This lets
+    /// the owner of primitives that can't create intersections on their own, such as the triangle packets of a mesh,
+    /// intersect them on their behalf.
+    pub fn intersect_with<'a>(
+        &'a self,
+        ray: &Ray,
+        intersect_primitive: impl Fn(&'a T, &Ray) -> Option<Intersection<'a>>,
+    ) -> Option<Intersection<'a>> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+
+        let mut closest_intersection: Option<Intersection> = None;
+        let mut closest_distance = ray.maximum_distance();
+
+        // Shortened to the closest intersection found so far, so primitives can skip hits that are further away.
+        let mut culled_ray = ray.clone();
+
+        // The children still to be visited as their offset, primitive count and the distance at which the ray enters
+        // them. The root is the inner node at index zero.
+        let mut stack = [(0u32, 0u32, 0.0 as Real); STACK_SIZE];
+        stack[0] = (0, 0, ray.minimum_distance());
+        let mut stack_size = 1;
+
+        while stack_size > 0 {
+            stack_size -= 1;
+            let (offset, count, distance) = stack[stack_size];
+
+            // A closer intersection may have been found since the child was pushed.
+            if distance > closest_distance {
+                continue;
+            }
+
+            if count > 0 {
+                let start = offset as usize;
+
+                for primitive in &self.primitives[start..start + count as usize] {
+                    let Some(intersection) = intersect_primitive(primitive, &culled_ray) else {
+                        continue;
+                    };
+
+                    if intersection.entrance_distance <= closest_distance {
+                        closest_distance = intersection.entrance_distance;
+                        closest_intersection = Some(intersection);
+                        culled_ray = ray.with_maximum_distance(closest_distance);
+                    }
+                }
+
+                continue;
+            }
+
+            let node = &self.nodes[offset as usize];
+            let distances = node.calculate_entrance_distances(ray, closest_distance);
+
+            // Push the children from the furthest to the nearest so the nearest is visited first.
+            let mut children: [usize; NODE_WIDTH] = std::array::from_fn(|x| x);
+            children.sort_unstable_by(|&a, &b| distances[b].total_cmp(&distances[a]));
+
+            for child in children {
+                if distances[child] < Real::INFINITY {
+                    stack[stack_size] = (node.offsets[child], node.counts[child], distances[child]);
+                    stack_size += 1;
+                }
+            }
+        }
+
+        closest_intersection
+    }
+
+    /// Determines if `occluded_primitive` accepts a hit with any of the primitives whose bounds the ray passes
+    /// through.
+    pub fn occluded_with(&self, ray: &Ray, occluded_primitive: impl Fn(&T, &Ray) -> bool) -> bool {
+        if self.nodes.is_empty() {
+            return false;
+        }
+
+        let mut stack = [(0u32, 0u32); STACK_SIZE];
+        let mut stack_size = 1;
+
+        // Any accepted hit will do, so the order in which the children are visited doesn't matter.
+        while stack_size > 0 {
+            stack_size -= 1;
+            let (offset, count) = stack[stack_size];
+
+            if count > 0 {
+                let start = offset as usize;
+
+                if self.primitives[start..start + count as usize]
+                    .iter()
+                    .any(|x| occluded_primitive(x, ray))
+                {
+                    return true;
+                }
+
+                continue;
+            }
+
+            let node = &self.nodes[offset as usize];
+            let distances = node.calculate_entrance_distances(ray, ray.maximum_distance());
+
+            for (child, distance) in distances.into_iter().enumerate() {
+                if distance < Real::INFINITY {
+                    stack[stack_size] = (node.offsets[child], node.counts[child]);
+                    stack_size += 1;
+                }
+            }
+        }
+
+        false
+    }
+}
+
+impl<T: Intersectable> Intersectable for WideBoundingBoxHierarchy<T> {
+    fn intersect(&self, ray: &Ray) -> Option<Intersection<'_>> {
+        self.intersect_with(ray, |primitive, ray| primitive.intersect(ray))
+    }
+
+    fn occluded(&self, ray: &Ray, filter: &dyn Fn(&Intersection) -> bool) -> bool {
+        self.occluded_with(ray, |primitive, ray| primitive.occluded(ray, filter))
+    }
+}
+
+impl<T: BoundByBox> BoundByBox for WideBoundingBoxHierarchy<T> {
+    fn calculate_bounding_box(&self) -> BoundingBox {
+        self.bounding_box
+    }
+}
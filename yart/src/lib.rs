@@ -1,13 +1,16 @@
-#![feature(trait_upcasting)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 
 pub mod cameras;
 pub mod common;
 pub mod errors;
 pub mod geometries;
 pub mod lights;
+pub mod loaders;
 pub mod materials;
 pub mod math;
 pub mod miss_shaders;
+pub mod output;
 pub mod rendering;
 pub mod scene;
+pub mod textures;
 pub mod yaml;
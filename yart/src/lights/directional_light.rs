@@ -1,5 +1,6 @@
 use super::light::Light;
 use crate::{
+    common::Real,
     geometries::ray::Ray,
     math::{color3::Color3, vector3::Vector3},
     scene::Scene,
@@ -45,15 +46,11 @@ impl Light for DirectionalLight {
         _rng: &mut dyn RngCore,
         scene: &Scene,
         hit_position: &Vector3,
-        _hit_normal: &Vector3,
+        hit_normal: &Vector3,
         _direction_to_light: &Vector3,
+        time: Real,
     ) -> bool {
-        let ray = Ray::new(hit_position, &self.reversed_direction);
-        let distance = scene.cast_ray_distance(&ray);
-
-        match distance {
-            Some(_) => true,
-            None => false,
-        }
+        let ray = Ray::new_from_surface(hit_position, hit_normal, &self.reversed_direction, time);
+        scene.occluded(&ray, Real::INFINITY)
     }
 }
@@ -1,13 +1,20 @@
 use crate::{
+    common::Real,
     math::{color3::Color3, vector3::Vector3},
     scene::Scene,
 };
 use rand::RngCore;
 use std::fmt::Debug;
 
-pub trait Light: Debug {
+pub trait Light: Debug + Send + Sync {
     fn color(&self) -> Color3;
 
+    /// Calculates how much light reaches a position, before shadows and the angle of the surface are accounted for.
+    /// Lights that don't fall off with distance deliver their color everywhere.
+    fn calculate_color_at(&self, _hit_position: &Vector3) -> Color3 {
+        self.color()
+    }
+
     fn get_direction_towards_light(&self, hit_position: &Vector3, hit_normal: &Vector3) -> Vector3;
 
     fn is_in_shadow(
@@ -17,5 +24,6 @@ pub trait Light: Debug {
         hit_position: &Vector3,
         hit_normal: &Vector3,
         direction_to_light: &Vector3,
+        time: Real,
     ) -> bool;
 }
@@ -1,3 +1,5 @@
 pub mod directional_light;
 pub mod light;
 pub mod point_light;
+pub mod spot_light;
+pub mod sun_light;
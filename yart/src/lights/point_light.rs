@@ -1,6 +1,6 @@
 use super::light::Light;
 use crate::{
-    common::EPSILON,
+    common::{Real, EPSILON},
     geometries::ray::Ray,
     math::{color3::Color3, vector::Vector, vector3::Vector3},
     normalize,
@@ -12,6 +12,10 @@ use rand::RngCore;
 pub struct PointLight {
     pub color: Color3,
     pub position: Vector3,
+
+    /// Whether the color is an intensity that falls off with the square of the distance, like that of a physical
+    /// light, rather than reaching every position undiminished.
+    pub inverse_square_falloff: bool,
 }
 
 impl PointLight {
@@ -19,6 +23,7 @@ impl PointLight {
         Self {
             color: *color,
             position: *position,
+            inverse_square_falloff: false,
         }
     }
 }
@@ -28,6 +33,14 @@ impl Light for PointLight {
         self.color
     }
 
+    fn calculate_color_at(&self, hit_position: &Vector3) -> Color3 {
+        if self.inverse_square_falloff {
+            self.color / Vector3::distance_squared(&self.position, hit_position)
+        } else {
+            self.color
+        }
+    }
+
     fn get_direction_towards_light(&self, hit_position: &Vector3, _hit_normal: &Vector3) -> Vector3 {
         normalize!(self.position - hit_position)
     }
@@ -37,20 +50,16 @@ impl Light for PointLight {
         _rng: &mut dyn RngCore,
         scene: &Scene,
         hit_position: &Vector3,
-        _hit_normal: &Vector3,
+        hit_normal: &Vector3,
         _direction_to_light: &Vector3,
+        time: Real,
     ) -> bool {
         let actual_direction_to_light = self.position - hit_position;
         let distance_to_light = actual_direction_to_light.length();
 
         let normalized_actual_direction_to_light = normalize!(actual_direction_to_light);
 
-        let ray = Ray::new(hit_position, &normalized_actual_direction_to_light);
-        let maybe_distance = scene.cast_ray_distance(&ray);
-
-        match maybe_distance {
-            Some(distance) => distance + EPSILON <= distance_to_light,
-            None => false,
-        }
+        let ray = Ray::new_from_surface(hit_position, hit_normal, &normalized_actual_direction_to_light, time);
+        scene.occluded(&ray, distance_to_light - EPSILON)
     }
 }
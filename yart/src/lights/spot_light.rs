@@ -0,0 +1,108 @@
+use super::light::Light;
+use crate::{
+    common::{Real, EPSILON},
+    geometries::ray::Ray,
+    math::{color3::Color3, vector::Vector, vector3::Vector3},
+    normalize,
+    scene::Scene,
+};
+use rand::{Rng, RngCore};
+
+/// A point light that only shines within a cone. The light falls off smoothly between the inner and outer cone
+/// angles.
+#[derive(Debug)]
+pub struct SpotLight {
+    pub color: Color3,
+    pub position: Vector3,
+    pub direction: Vector3,
+
+    /// Whether the color is an intensity that falls off with the square of the distance, like that of a physical
+    /// light, rather than reaching every position undiminished.
+    pub inverse_square_falloff: bool,
+
+    cos_inner_cone_angle: Real,
+    cos_outer_cone_angle: Real,
+}
+
+impl SpotLight {
+    pub fn new(
+        color: &Color3,
+        position: &Vector3,
+        direction: &Vector3,
+        inner_cone_angle: Real,
+        outer_cone_angle: Real,
+    ) -> SpotLight {
+        Self {
+            color: *color,
+            position: *position,
+            direction: normalize!(direction),
+            inverse_square_falloff: false,
+            cos_inner_cone_angle: Real::cos(Real::min(inner_cone_angle, outer_cone_angle)),
+            cos_outer_cone_angle: Real::cos(outer_cone_angle),
+        }
+    }
+
+    /// Calculates how much of the light reaches a position, from one inside the inner cone to zero outside the outer
+    /// cone.
+    pub fn calculate_falloff(&self, hit_position: &Vector3) -> Real {
+        let cos_angle = self.direction ^ normalize!(hit_position - self.position);
+
+        if self.cos_inner_cone_angle <= self.cos_outer_cone_angle {
+            return if cos_angle >= self.cos_outer_cone_angle {
+                1.0
+            } else {
+                0.0
+            };
+        }
+
+        let t = Real::clamp(
+            (cos_angle - self.cos_outer_cone_angle) / (self.cos_inner_cone_angle - self.cos_outer_cone_angle),
+            0.0,
+            1.0,
+        );
+
+        t * t * (3.0 - 2.0 * t)
+    }
+}
+
+impl Light for SpotLight {
+    fn color(&self) -> Color3 {
+        self.color
+    }
+
+    fn calculate_color_at(&self, hit_position: &Vector3) -> Color3 {
+        if self.inverse_square_falloff {
+            self.color / Vector3::distance_squared(&self.position, hit_position)
+        } else {
+            self.color
+        }
+    }
+
+    fn get_direction_towards_light(&self, hit_position: &Vector3, _hit_normal: &Vector3) -> Vector3 {
+        normalize!(self.position - hit_position)
+    }
+
+    fn is_in_shadow(
+        &self,
+        rng: &mut dyn RngCore,
+        scene: &Scene,
+        hit_position: &Vector3,
+        hit_normal: &Vector3,
+        _direction_to_light: &Vector3,
+        time: Real,
+    ) -> bool {
+        // Lights only have a single color, so the falloff is applied stochastically by treating the position as
+        // shadowed in proportion to how far outside the inner cone it is.
+        if rng.gen::<Real>() >= self.calculate_falloff(hit_position) {
+            return true;
+        }
+
+        let actual_direction_to_light = self.position - hit_position;
+        let distance_to_light = actual_direction_to_light.length();
+
+        let normalized_actual_direction_to_light = normalize!(actual_direction_to_light);
+
+        let ray = Ray::new_from_surface(hit_position, hit_normal, &normalized_actual_direction_to_light, time);
+        scene.occluded(&ray, distance_to_light - EPSILON)
+    }
+}
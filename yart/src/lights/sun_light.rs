@@ -0,0 +1,104 @@
+use crate::{
+    common::{Real, TWO_PI},
+    geometries::{area_light::AreaLight, ray::Ray},
+    math::{color3::Color3, vector3::Vector3},
+    normalize,
+    scene::Scene,
+};
+use rand::{Rng, RngCore};
+
+/// The distance at which points on the sun are placed when the sun is treated as an area light.
+const SUN_DISTANCE: Real = 1.0e8;
+
+/// The disk of the sun as seen from the ground. Directions towards the sun are sampled uniformly within the cone the
+/// disk subtends which lets sky miss shaders expose their sun as an [`AreaLight`].
+#[derive(Debug, Copy, Clone)]
+pub struct SunLight {
+    direction_to_sun: Vector3,
+    cos_angular_radius: Real,
+    solid_angle: Real,
+}
+
+impl SunLight {
+    /// Creates a new sun. The `angular_radius` is half of the apparent angular diameter of the sun in radians.
+    pub fn new(direction_to_sun: &Vector3, angular_radius: Real) -> Self {
+        let cos_angular_radius = Real::cos(angular_radius);
+
+        Self {
+            direction_to_sun: normalize!(*direction_to_sun),
+            cos_angular_radius,
+            solid_angle: TWO_PI * (1.0 - cos_angular_radius),
+        }
+    }
+
+    pub fn direction_to_sun(&self) -> Vector3 {
+        self.direction_to_sun
+    }
+
+    /// Returns true if the normalized direction points inside the disk of the sun.
+    pub fn contains_direction(&self, direction: &Vector3) -> bool {
+        (direction ^ self.direction_to_sun) >= self.cos_angular_radius
+    }
+
+    /// Converts the irradiance the sun delivers to a surface facing it into the radiance of its disk.
+    pub fn calculate_disk_radiance(&self, irradiance: &Color3) -> Color3 {
+        irradiance / self.solid_angle
+    }
+
+    fn sample_direction(&self, rng: &mut dyn RngCore) -> Vector3 {
+        // Uniformly sample the cone around the +y axis and then rotate it to be around the direction to the sun.
+        let cos_theta = 1.0 - rng.gen::<Real>() * (1.0 - self.cos_angular_radius);
+        let sin_theta = Real::sqrt(Real::max(0.0, 1.0 - cos_theta * cos_theta));
+        let phi = TWO_PI * rng.gen::<Real>();
+
+        let tangent = normalize!(Vector3::build_perpendicular_vector(&self.direction_to_sun));
+        let bitangent = self.direction_to_sun % tangent;
+
+        normalize!(
+            tangent * (sin_theta * Real::cos(phi))
+                + bitangent * (sin_theta * Real::sin(phi))
+                + self.direction_to_sun * cos_theta
+        )
+    }
+}
+
+impl AreaLight for SunLight {
+    fn get_direction_towards_light(
+        &self,
+        rng: &mut dyn RngCore,
+        _hit_position: &Vector3,
+        _hit_normal: &Vector3,
+    ) -> Vector3 {
+        self.sample_direction(rng)
+    }
+
+    fn get_point_on_light(&self, rng: &mut dyn RngCore, hit_position: &Vector3, _hit_normal: &Vector3) -> Vector3 {
+        hit_position + self.sample_direction(rng) * SUN_DISTANCE
+    }
+
+    fn is_in_shadow(
+        &self,
+        _rng: &mut dyn RngCore,
+        scene: &Scene,
+        hit_position: &Vector3,
+        hit_normal: &Vector3,
+        point_on_light: &Vector3,
+        time: Real,
+    ) -> bool {
+        let direction_to_light = normalize!(point_on_light - hit_position);
+        let ray = Ray::new_from_surface(hit_position, hit_normal, &direction_to_light, time);
+
+        scene.occluded(&ray, Real::INFINITY)
+    }
+
+    fn calculate_inverse_pdf(
+        &self,
+        _rng: &mut dyn RngCore,
+        _hit_position: &Vector3,
+        _hit_normal: &Vector3,
+        _incoming_direction: &Vector3,
+        _outgoing_direction: &Vector3,
+    ) -> Real {
+        self.solid_angle
+    }
+}
@@ -0,0 +1,654 @@
+use super::{
+    indexed_mesh::IndexedMesh,
+    json::{parse_json, JsonValue},
+};
+use crate::{
+    common::{Real, PI},
+    geometries::mesh_buffers::MeshBuffers,
+    math::{color3::Color3, matrix4::Matrix4, quaternion::Quaternion, vector2::Vector2, vector3::Vector3},
+    normalize,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const GLB_MAGIC: &[u8] = b"glTF";
+const GLB_JSON_CHUNK: u32 = 0x4E4F534A;
+const GLB_BIN_CHUNK: u32 = 0x004E4942;
+
+const TRIANGLES_MODE: usize = 4;
+
+/// A glTF 2.0 scene flattened into world space. Every mesh, camera and light has had the transforms of its node and
+/// the node's ancestors applied, and has been converted from glTF's right handed coordinate system to yart's left
+/// handed one by mirroring the z axis.
+#[derive(Debug, Clone, Default)]
+pub struct GltfScene {
+    pub meshes: Vec<GltfMesh>,
+    pub materials: Vec<GltfMaterial>,
+    pub cameras: Vec<GltfCamera>,
+    pub lights: Vec<GltfLight>,
+}
+
+/// A triangle primitive of a mesh node. A node with a multi-primitive mesh produces one of these per primitive.
+#[derive(Debug, Clone)]
+pub struct GltfMesh {
+    pub name: String,
+    pub mesh: IndexedMesh,
+
+    /// The index into [`GltfScene::materials`], or `None` to use the default material.
+    pub material: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub enum GltfImage {
+    File(PathBuf),
+    Embedded { mime_type: String, data: Vec<u8> },
+}
+
+#[derive(Debug, Clone)]
+pub struct GltfTexture {
+    pub image: GltfImage,
+
+    /// The set of texture coordinates used to look up the texture, such as 0 for `TEXCOORD_0`.
+    pub texture_coordinate_set: usize,
+
+    /// The strength of a normal map, or one for other textures.
+    pub scale: Real,
+}
+
+/// A metallic-roughness material. The constant values are the factors the textures are multiplied by.
+#[derive(Debug, Clone)]
+pub struct GltfMaterial {
+    /// The name scene materials can replace the material by, if it has one.
+    pub name: Option<String>,
+
+    pub base_color: Color3,
+    pub metallic: Real,
+    pub roughness: Real,
+    pub emissive_color: Color3,
+
+    pub base_color_texture: Option<GltfTexture>,
+    pub metallic_roughness_texture: Option<GltfTexture>,
+    pub normal_texture: Option<GltfTexture>,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum GltfProjection {
+    /// A perspective projection with the vertical field of view in radians.
+    Perspective { y_fov: Real, aspect_ratio: Option<Real> },
+
+    /// An orthographic projection with half the width and height of the view.
+    Orthographic { x_mag: Real, y_mag: Real },
+}
+
+#[derive(Debug, Clone)]
+pub struct GltfCamera {
+    pub name: String,
+    pub projection: GltfProjection,
+
+    pub position: Vector3,
+    pub look_at: Vector3,
+    pub up: Vector3,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum GltfLightType {
+    Directional,
+    Point,
+
+    /// A spot light with its cone angles in radians.
+    Spot {
+        inner_cone_angle: Real,
+        outer_cone_angle: Real,
+    },
+}
+
+/// A light from the `KHR_lights_punctual` extension.
+#[derive(Debug, Clone)]
+pub struct GltfLight {
+    pub name: String,
+    pub light_type: GltfLightType,
+
+    /// The color of the light multiplied by its intensity, which is an illuminance in lux for directional lights and a
+    /// luminous intensity in candela, falling off with the square of the distance, for point and spot lights.
+    pub color: Color3,
+
+    pub position: Vector3,
+    pub direction: Vector3,
+}
+
+/// Loads the default scene of a `.gltf` or `.glb` file and places it in the world with a transformation. External
+/// buffers and images are resolved relative to the file. Only triangle primitives are loaded. Returns `None` if the
+/// file can't be read or is malformed.
+pub fn load_gltf(path: &Path, transformation: &Matrix4) -> Option<GltfScene> {
+    let data = fs::read(path).ok()?;
+    let directory = path.parent().unwrap_or(Path::new(""));
+
+    let (document, binary_chunk) = if data.starts_with(GLB_MAGIC) {
+        parse_glb(&data)?
+    } else {
+        (parse_json(std::str::from_utf8(&data).ok()?)?, None)
+    };
+
+    let buffers = load_buffers(&document, directory, binary_chunk)?;
+
+    let mut loader = GltfLoader {
+        document: &document,
+        buffers: &buffers,
+        directory,
+        scene: GltfScene::default(),
+    };
+
+    loader.load_materials()?;
+    loader.load_nodes(&(transformation * create_mirror_z()))?;
+
+    Some(loader.scene)
+}
+
+fn parse_glb(data: &[u8]) -> Option<(JsonValue, Option<Vec<u8>>)> {
+    let read_u32 = |offset: usize| Some(u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?));
+
+    let length = usize::min(read_u32(8)? as usize, data.len());
+
+    let mut document = None;
+    let mut binary_chunk = None;
+    let mut offset = 12;
+
+    while offset + 8 <= length {
+        let chunk_length = read_u32(offset)? as usize;
+        let chunk_type = read_u32(offset + 4)?;
+        let chunk = data.get(offset + 8..offset + 8 + chunk_length)?;
+
+        match chunk_type {
+            GLB_JSON_CHUNK => document = Some(parse_json(std::str::from_utf8(chunk).ok()?)?),
+            GLB_BIN_CHUNK => binary_chunk = Some(chunk.to_vec()),
+            _ => {}
+        }
+
+        offset += 8 + chunk_length;
+    }
+
+    Some((document?, binary_chunk))
+}
+
+fn load_buffers(document: &JsonValue, directory: &Path, mut binary_chunk: Option<Vec<u8>>) -> Option<Vec<Vec<u8>>> {
+    let Some(buffers) = document["buffers"].as_vec() else {
+        return Some(Vec::new());
+    };
+
+    buffers
+        .iter()
+        .map(|buffer| match buffer["uri"].as_str() {
+            Some(uri) => load_uri(uri, directory).map(|(_, data)| data),
+            // Only the first buffer of a GLB file may leave out the URI and refer to the binary chunk.
+            None => binary_chunk.take(),
+        })
+        .collect()
+}
+
+/// Loads the contents of a data URI or a file relative to the glTF file, along with its MIME type if known.
+fn load_uri(uri: &str, directory: &Path) -> Option<(Option<String>, Vec<u8>)> {
+    match uri.strip_prefix("data:") {
+        Some(data_uri) => {
+            let (header, payload) = data_uri.split_once(',')?;
+            let mime_type = header.strip_suffix(";base64")?;
+
+            Some((Some(mime_type.to_string()), decode_base64(payload)?))
+        }
+        None => Some((None, fs::read(directory.join(decode_percent_encoding(uri))).ok()?)),
+    }
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let decode_character = |character: u8| match character {
+        b'A'..=b'Z' => Some(character - b'A'),
+        b'a'..=b'z' => Some(character - b'a' + 26),
+        b'0'..=b'9' => Some(character - b'0' + 52),
+        b'+' | b'-' => Some(62),
+        b'/' | b'_' => Some(63),
+        _ => None,
+    };
+
+    let mut data = Vec::with_capacity(text.len() * 3 / 4);
+    let mut accumulator = 0u32;
+    let mut bit_count = 0;
+
+    for character in text.bytes().filter(|x| *x != b'=' && !x.is_ascii_whitespace()) {
+        accumulator = (accumulator << 6) | decode_character(character)? as u32;
+        bit_count += 6;
+
+        if bit_count >= 8 {
+            bit_count -= 8;
+            data.push((accumulator >> bit_count) as u8);
+        }
+    }
+
+    Some(data)
+}
+
+fn decode_percent_encoding(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok())
+            .flatten()
+            .and_then(|x| u8::from_str_radix(x, 16).ok());
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Converts from glTF's right handed coordinate system to yart's left handed one.
+fn create_mirror_z() -> Matrix4 {
+    Matrix4::from_scale(&Vector3::new(1.0, 1.0, -1.0))
+}
+
+fn parse_node_matrix(node: &JsonValue) -> Option<Matrix4> {
+    if let Some(values) = node["matrix"].as_reals() {
+        if values.len() != 16 {
+            return None;
+        }
+
+        // glTF stores matrices in column major order.
+        let mut matrix = Matrix4::identity();
+
+        for (i, value) in values.iter().enumerate() {
+            matrix.m[i % 4][i / 4] = *value;
+        }
+
+        return Some(matrix);
+    }
+
+    let translation = node["translation"].as_reals().unwrap_or(vec![0.0, 0.0, 0.0]);
+    let rotation = node["rotation"].as_reals().unwrap_or(vec![0.0, 0.0, 0.0, 1.0]);
+    let scale = node["scale"].as_reals().unwrap_or(vec![1.0, 1.0, 1.0]);
+
+    if translation.len() != 3 || rotation.len() != 4 || scale.len() != 3 {
+        return None;
+    }
+
+    Some(Matrix4::from_scale_rotation_translation(
+        &Vector3::new(scale[0], scale[1], scale[2]),
+        &Quaternion::new(rotation[0], rotation[1], rotation[2], rotation[3]),
+        &Vector3::new(translation[0], translation[1], translation[2]),
+    ))
+}
+
+struct GltfLoader<'a> {
+    document: &'a JsonValue,
+    buffers: &'a [Vec<u8>],
+    directory: &'a Path,
+    scene: GltfScene,
+}
+
+impl GltfLoader<'_> {
+    fn load_materials(&mut self) -> Option<()> {
+        let Some(materials) = self.document["materials"].as_vec() else {
+            return Some(());
+        };
+
+        for material in materials {
+            let pbr = &material["pbrMetallicRoughness"];
+
+            let base_color = pbr["baseColorFactor"].as_reals().unwrap_or(vec![1.0, 1.0, 1.0, 1.0]);
+            let emissive_color = material["emissiveFactor"].as_reals().unwrap_or(vec![0.0, 0.0, 0.0]);
+            let emissive_strength = material["extensions"]["KHR_materials_emissive_strength"]["emissiveStrength"]
+                .as_real()
+                .unwrap_or(1.0);
+
+            if base_color.len() < 3 || emissive_color.len() != 3 {
+                return None;
+            }
+
+            self.scene.materials.push(GltfMaterial {
+                name: material["name"].as_str().map(|x| x.to_string()),
+                base_color: Color3::new(base_color[0], base_color[1], base_color[2]),
+                metallic: pbr["metallicFactor"].as_real().unwrap_or(1.0),
+                roughness: pbr["roughnessFactor"].as_real().unwrap_or(1.0),
+                emissive_color: Color3::new(emissive_color[0], emissive_color[1], emissive_color[2])
+                    * emissive_strength,
+                base_color_texture: self.load_texture(&pbr["baseColorTexture"]),
+                metallic_roughness_texture: self.load_texture(&pbr["metallicRoughnessTexture"]),
+                normal_texture: self.load_texture(&material["normalTexture"]),
+            });
+        }
+
+        Some(())
+    }
+
+    fn load_texture(&self, texture_info: &JsonValue) -> Option<GltfTexture> {
+        let texture = &self.document["textures"][texture_info["index"].as_usize()?];
+        let image = &self.document["images"][texture["source"].as_usize()?];
+
+        let image = match image["uri"].as_str() {
+            Some(uri) if !uri.starts_with("data:") => {
+                GltfImage::File(self.directory.join(decode_percent_encoding(uri)))
+            }
+            Some(uri) => {
+                let (mime_type, data) = load_uri(uri, self.directory)?;
+                GltfImage::Embedded {
+                    mime_type: mime_type.unwrap_or_default(),
+                    data,
+                }
+            }
+            None => GltfImage::Embedded {
+                mime_type: image["mimeType"].as_str().unwrap_or_default().to_string(),
+                data: self.read_buffer_view(image["bufferView"].as_usize()?)?.to_vec(),
+            },
+        };
+
+        Some(GltfTexture {
+            image,
+            texture_coordinate_set: texture_info["texCoord"].as_usize().unwrap_or(0),
+            scale: texture_info["scale"].as_real().unwrap_or(1.0),
+        })
+    }
+
+    fn load_nodes(&mut self, root_matrix: &Matrix4) -> Option<()> {
+        let scene_index = self.document["scene"].as_usize().unwrap_or(0);
+
+        let root_nodes = match self.document["scenes"][scene_index]["nodes"].as_vec() {
+            Some(nodes) => nodes.iter().map(|x| x.as_usize()).collect::<Option<Vec<_>>>()?,
+            None => {
+                // Without a scene every node that isn't a child of another node is a root.
+                let nodes = self.document["nodes"].as_vec().map_or(&[][..], |x| x.as_slice());
+                let children = nodes
+                    .iter()
+                    .flat_map(|node| node["children"].as_vec().into_iter().flatten())
+                    .filter_map(|x| x.as_usize())
+                    .collect::<Vec<_>>();
+
+                (0..nodes.len()).filter(|x| !children.contains(x)).collect()
+            }
+        };
+
+        for node_index in root_nodes {
+            self.load_node(node_index, root_matrix, 0)?;
+        }
+
+        Some(())
+    }
+
+    fn load_node(&mut self, node_index: usize, parent_matrix: &Matrix4, depth: usize) -> Option<()> {
+        // The node hierarchy has to be a tree, so a hierarchy this deep means there's a cycle.
+        if depth > 256 {
+            return None;
+        }
+
+        let node = &self.document["nodes"][node_index];
+        let matrix = parent_matrix * parse_node_matrix(node)?;
+        let name = node["name"]
+            .as_str()
+            .map_or_else(|| format!("node{}", node_index), |x| x.to_string());
+
+        if let Some(mesh_index) = node["mesh"].as_usize() {
+            self.load_mesh(&name, mesh_index, &matrix)?;
+        }
+
+        if let Some(camera_index) = node["camera"].as_usize() {
+            self.load_camera(&name, camera_index, &matrix)?;
+        }
+
+        if let Some(light_index) = node["extensions"]["KHR_lights_punctual"]["light"].as_usize() {
+            self.load_light(&name, light_index, &matrix)?;
+        }
+
+        if let Some(children) = node["children"].as_vec() {
+            for child in children {
+                self.load_node(child.as_usize()?, &matrix, depth + 1)?;
+            }
+        }
+
+        Some(())
+    }
+
+    fn load_mesh(&mut self, name: &str, mesh_index: usize, matrix: &Matrix4) -> Option<()> {
+        let normal_matrix = matrix.normal_matrix()?;
+
+        let primitives = self.document["meshes"][mesh_index]["primitives"].as_vec()?;
+
+        for primitive in primitives {
+            if primitive["mode"].as_usize().unwrap_or(TRIANGLES_MODE) != TRIANGLES_MODE {
+                continue;
+            }
+
+            let attributes = &primitive["attributes"];
+
+            let positions = self.read_vectors(&attributes["POSITION"], 3)?;
+            let normals = self.read_optional_vectors(&attributes["NORMAL"], 3)?;
+            let uvs = self.read_optional_vectors(&attributes["TEXCOORD_0"], 2)?;
+            let colors = self.read_optional_vectors(&attributes["COLOR_0"], 3)?;
+
+            let indices = match primitive["indices"].as_usize() {
+                Some(accessor) => self.read_indices(accessor)?,
+                None => (0..positions.len() as u32).collect(),
+            };
+
+            if indices.iter().any(|x| *x as usize >= positions.len()) {
+                return None;
+            }
+
+            let buffers = MeshBuffers::new(
+                positions
+                    .iter()
+                    .map(|x| matrix.transform_point(&Vector3::new(x[0], x[1], x[2])))
+                    .collect(),
+                normals
+                    .iter()
+                    .map(|x| normalize!(normal_matrix * Vector3::new(x[0], x[1], x[2])))
+                    .collect(),
+                uvs.iter().map(|x| Vector2::new(x[0], x[1])).collect(),
+                colors.iter().map(|x| Color3::new(x[0], x[1], x[2])).collect(),
+            );
+
+            self.scene.meshes.push(GltfMesh {
+                name: name.to_string(),
+                mesh: IndexedMesh::new(buffers, indices.chunks_exact(3).map(|x| [x[0], x[1], x[2]]).collect()),
+                material: primitive["material"].as_usize(),
+            });
+        }
+
+        Some(())
+    }
+
+    fn load_camera(&mut self, name: &str, camera_index: usize, matrix: &Matrix4) -> Option<()> {
+        let camera = &self.document["cameras"][camera_index];
+
+        let projection = match camera["type"].as_str()? {
+            "perspective" => GltfProjection::Perspective {
+                y_fov: camera["perspective"]["yfov"].as_real()?,
+                aspect_ratio: camera["perspective"]["aspectRatio"].as_real(),
+            },
+            "orthographic" => GltfProjection::Orthographic {
+                x_mag: camera["orthographic"]["xmag"].as_real()?,
+                y_mag: camera["orthographic"]["ymag"].as_real()?,
+            },
+            _ => return None,
+        };
+
+        // glTF cameras look down their local negative z axis.
+        let position = matrix.transform_point(&Vector3::default());
+        let forward = normalize!(matrix.transform_vector(&Vector3::new(0.0, 0.0, -1.0)));
+        let up = normalize!(matrix.transform_vector(&Vector3::new(0.0, 1.0, 0.0)));
+
+        self.scene.cameras.push(GltfCamera {
+            name: name.to_string(),
+            projection,
+            position,
+            look_at: position + forward,
+            up,
+        });
+
+        Some(())
+    }
+
+    fn load_light(&mut self, name: &str, light_index: usize, matrix: &Matrix4) -> Option<()> {
+        let light = &self.document["extensions"]["KHR_lights_punctual"]["lights"][light_index];
+
+        let light_type = match light["type"].as_str()? {
+            "directional" => GltfLightType::Directional,
+            "point" => GltfLightType::Point,
+            "spot" => GltfLightType::Spot {
+                inner_cone_angle: light["spot"]["innerConeAngle"].as_real().unwrap_or(0.0),
+                outer_cone_angle: light["spot"]["outerConeAngle"].as_real().unwrap_or(PI * 0.25),
+            },
+            _ => return None,
+        };
+
+        let color = light["color"].as_reals().unwrap_or(vec![1.0, 1.0, 1.0]);
+        let intensity = light["intensity"].as_real().unwrap_or(1.0);
+
+        if color.len() != 3 {
+            return None;
+        }
+
+        // Lights shine down their local negative z axis.
+        self.scene.lights.push(GltfLight {
+            name: name.to_string(),
+            light_type,
+            color: Color3::new(color[0], color[1], color[2]) * intensity,
+            position: matrix.transform_point(&Vector3::default()),
+            direction: normalize!(matrix.transform_vector(&Vector3::new(0.0, 0.0, -1.0))),
+        });
+
+        Some(())
+    }
+
+    fn read_buffer_view(&self, buffer_view_index: usize) -> Option<&[u8]> {
+        let buffer_view = &self.document["bufferViews"][buffer_view_index];
+        let buffer = self.buffers.get(buffer_view["buffer"].as_usize()?)?;
+
+        let offset = buffer_view["byteOffset"].as_usize().unwrap_or(0);
+        let length = buffer_view["byteLength"].as_usize()?;
+
+        buffer.get(offset..offset + length)
+    }
+
+    fn read_optional_vectors(&self, accessor: &JsonValue, minimum_size: usize) -> Option<Vec<Vec<Real>>> {
+        if accessor.is_null() {
+            Some(Vec::new())
+        } else {
+            self.read_vectors(accessor, minimum_size)
+        }
+    }
+
+    fn read_vectors(&self, accessor: &JsonValue, minimum_size: usize) -> Option<Vec<Vec<Real>>> {
+        let (values, component_count) = self.read_accessor(accessor.as_usize()?)?;
+
+        if component_count < minimum_size {
+            return None;
+        }
+
+        Some(values.chunks_exact(component_count).map(|x| x.to_vec()).collect())
+    }
+
+    /// Reads the values of an accessor along with the number of components per element. Normalized integers are
+    /// converted to the range they represent.
+    fn read_accessor(&self, accessor_index: usize) -> Option<(Vec<Real>, usize)> {
+        let normalized = self.document["accessors"][accessor_index]["normalized"]
+            .as_bool()
+            .unwrap_or(false);
+
+        self.read_components(accessor_index, |component_type, bytes| {
+            let (value, normalization) = match component_type {
+                5120 => (bytes[0] as i8 as Real, 127.0),
+                5121 => (bytes[0] as Real, 255.0),
+                5122 => (i16::from_le_bytes([bytes[0], bytes[1]]) as Real, 32767.0),
+                5123 => (u16::from_le_bytes([bytes[0], bytes[1]]) as Real, 65535.0),
+                5125 => (u32::from_le_bytes(bytes.try_into().ok()?) as Real, 1.0),
+                _ => (f32::from_le_bytes(bytes.try_into().ok()?) as Real, 1.0),
+            };
+
+            Some(if normalized {
+                Real::max(value / normalization, -1.0)
+            } else {
+                value
+            })
+        })
+    }
+
+    /// Reads the values of an accessor of vertex indices. They're read as integers rather than through [`Real`], which
+    /// can't represent indices beyond 2^24 exactly with single precision.
+    fn read_indices(&self, accessor_index: usize) -> Option<Vec<u32>> {
+        let (indices, component_count) =
+            self.read_components(accessor_index, |component_type, bytes| match component_type {
+                5121 => Some(bytes[0] as u32),
+                5123 => Some(u16::from_le_bytes([bytes[0], bytes[1]]) as u32),
+                5125 => Some(u32::from_le_bytes(bytes.try_into().ok()?)),
+                _ => None,
+            })?;
+
+        (component_count == 1).then_some(indices)
+    }
+
+    /// Reads every component of the elements of an accessor with `read_component`, which is given the component type
+    /// and the bytes of a component. The number of components per element is returned along with them.
+    fn read_components<T: Clone + Default>(
+        &self,
+        accessor_index: usize,
+        read_component: impl Fn(usize, &[u8]) -> Option<T>,
+    ) -> Option<(Vec<T>, usize)> {
+        let accessor = &self.document["accessors"][accessor_index];
+
+        let count = accessor["count"].as_usize()?;
+
+        let component_count = match accessor["type"].as_str()? {
+            "SCALAR" => 1,
+            "VEC2" => 2,
+            "VEC3" => 3,
+            "VEC4" => 4,
+            "MAT2" => 4,
+            "MAT3" => 9,
+            "MAT4" => 16,
+            _ => return None,
+        };
+
+        let component_type = accessor["componentType"].as_usize()?;
+
+        let component_size = match component_type {
+            5120 | 5121 => 1,
+            5122 | 5123 => 2,
+            5125 | 5126 => 4,
+            _ => return None,
+        };
+
+        // An accessor without a buffer view is initialized with zeros.
+        let Some(buffer_view_index) = accessor["bufferView"].as_usize() else {
+            return Some((vec![T::default(); count * component_count], component_count));
+        };
+
+        let data = self.read_buffer_view(buffer_view_index)?;
+        let offset = accessor["byteOffset"].as_usize().unwrap_or(0);
+        let stride = self.document["bufferViews"][buffer_view_index]["byteStride"]
+            .as_usize()
+            .unwrap_or(component_size * component_count);
+
+        let mut values = Vec::with_capacity(count * component_count);
+
+        for element in 0..count {
+            for component in 0..component_count {
+                let start = offset + element * stride + component * component_size;
+                let bytes = data.get(start..start + component_size)?;
+
+                values.push(read_component(component_type, bytes)?);
+            }
+        }
+
+        Some((values, component_count))
+    }
+}
@@ -0,0 +1,20 @@
+use crate::geometries::mesh_buffers::MeshBuffers;
+
+/// A triangle mesh where every buffer is indexed by the same per-triangle indices. This is the layout used by PLY and
+/// STL files, which unlike OBJ files don't index their attributes separately.
+#[derive(Debug, Clone, Default)]
+pub struct IndexedMesh {
+    /// The vertex data. The normals and colors are either empty or have the same length as the positions.
+    pub buffers: MeshBuffers,
+    pub indices: Vec<[u32; 3]>,
+}
+
+impl IndexedMesh {
+    pub fn new(buffers: MeshBuffers, indices: Vec<[u32; 3]>) -> Self {
+        Self { buffers, indices }
+    }
+
+    pub fn has_normals(&self) -> bool {
+        !self.buffers.normals.is_empty()
+    }
+}
@@ -0,0 +1,287 @@
+use crate::common::Real;
+use std::ops::Index;
+
+/// A parsed JSON value. Indexing with a key or an index that doesn't exist returns [`JsonValue::Null`] so that nested
+/// lookups can be chained, the same way `yaml_rust::Yaml` works.
+///
+/// Numbers are stored with double precision regardless of the precision of [`Real`], so that integers such as byte
+/// offsets and counts stay exact.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+static NULL: JsonValue = JsonValue::Null;
+
+impl JsonValue {
+    pub fn is_null(&self) -> bool {
+        *self == JsonValue::Null
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    pub fn as_real(&self) -> Option<Real> {
+        match self {
+            JsonValue::Number(x) => Some(*x as Real),
+            _ => None,
+        }
+    }
+
+    pub fn as_usize(&self) -> Option<usize> {
+        match self {
+            JsonValue::Number(x) if *x >= 0.0 && x.fract() == 0.0 => Some(*x as usize),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn as_vec(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Returns the array as numbers, or `None` if it isn't an array of numbers.
+    pub fn as_reals(&self) -> Option<Vec<Real>> {
+        self.as_vec()?.iter().map(|x| x.as_real()).collect()
+    }
+}
+
+impl Index<&str> for JsonValue {
+    type Output = JsonValue;
+
+    fn index(&self, key: &str) -> &Self::Output {
+        match self {
+            JsonValue::Object(members) => members.iter().find(|(x, _)| x == key).map_or(&NULL, |(_, value)| value),
+            _ => &NULL,
+        }
+    }
+}
+
+impl Index<usize> for JsonValue {
+    type Output = JsonValue;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self {
+            JsonValue::Array(values) => values.get(index).unwrap_or(&NULL),
+            _ => &NULL,
+        }
+    }
+}
+
+/// Parses a JSON document. Returns `None` if the document is malformed.
+///
+/// # Examples
+///
+/// ```
+/// # use yart::loaders::json::*;
+/// #
+/// let text = r#"{ "name": "Boxé", "size": [1, 2.5e1, -3], "offset": 16777217, "nested": { "flag": true } }"#;
+/// let json = parse_json(text).unwrap();
+///
+/// assert_eq!(Some("Boxé"), json["name"].as_str());
+/// assert_eq!(Some(25.0), json["size"][1].as_real());
+/// assert_eq!(Some(16777217), json["offset"].as_usize());
+/// assert_eq!(Some(true), json["nested"]["flag"].as_bool());
+/// assert!(json["missing"]["key"].is_null());
+/// ```
+pub fn parse_json(text: &str) -> Option<JsonValue> {
+    let mut parser = JsonParser {
+        bytes: text.as_bytes(),
+        offset: 0,
+    };
+
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+
+    if parser.offset == parser.bytes.len() {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+struct JsonParser<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl JsonParser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.offset).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.offset += 1;
+
+        Some(byte)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|x| x.is_ascii_whitespace()) {
+            self.offset += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: &[u8]) -> Option<()> {
+        if self.bytes.get(self.offset..self.offset + expected.len())? == expected {
+            self.offset += expected.len();
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn parse_value(&mut self) -> Option<JsonValue> {
+        self.skip_whitespace();
+
+        match self.peek()? {
+            b'{' => self.parse_object(),
+            b'[' => self.parse_array(),
+            b'"' => self.parse_string().map(JsonValue::String),
+            b't' => self.expect(b"true").map(|_| JsonValue::Bool(true)),
+            b'f' => self.expect(b"false").map(|_| JsonValue::Bool(false)),
+            b'n' => self.expect(b"null").map(|_| JsonValue::Null),
+            _ => self.parse_number(),
+        }
+    }
+
+    fn parse_object(&mut self) -> Option<JsonValue> {
+        self.expect(b"{")?;
+
+        let mut members = Vec::new();
+
+        self.skip_whitespace();
+
+        if self.peek()? == b'}' {
+            self.offset += 1;
+            return Some(JsonValue::Object(members));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+
+            self.skip_whitespace();
+            self.expect(b":")?;
+
+            members.push((key, self.parse_value()?));
+
+            self.skip_whitespace();
+
+            match self.next()? {
+                b',' => continue,
+                b'}' => return Some(JsonValue::Object(members)),
+                _ => return None,
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Option<JsonValue> {
+        self.expect(b"[")?;
+
+        let mut values = Vec::new();
+
+        self.skip_whitespace();
+
+        if self.peek()? == b']' {
+            self.offset += 1;
+            return Some(JsonValue::Array(values));
+        }
+
+        loop {
+            values.push(self.parse_value()?);
+
+            self.skip_whitespace();
+
+            match self.next()? {
+                b',' => continue,
+                b']' => return Some(JsonValue::Array(values)),
+                _ => return None,
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Option<String> {
+        self.expect(b"\"")?;
+
+        let mut bytes = Vec::new();
+
+        loop {
+            match self.next()? {
+                b'"' => return String::from_utf8(bytes).ok(),
+                b'\\' => {
+                    let escaped = match self.next()? {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.parse_unicode_escape()?,
+                        _ => return None,
+                    };
+
+                    bytes.extend_from_slice(escaped.encode_utf8(&mut [0u8; 4]).as_bytes());
+                }
+                byte => bytes.push(byte),
+            }
+        }
+    }
+
+    fn parse_unicode_escape(&mut self) -> Option<char> {
+        let high = self.parse_hex4()?;
+
+        // Characters outside the basic multilingual plane are escaped as a surrogate pair.
+        if (0xD800..0xDC00).contains(&high) {
+            self.expect(b"\\u")?;
+            let low = self.parse_hex4()?;
+
+            char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low.checked_sub(0xDC00)?))
+        } else {
+            char::from_u32(high)
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Option<u32> {
+        let digits = std::str::from_utf8(self.bytes.get(self.offset..self.offset + 4)?).ok()?;
+        self.offset += 4;
+
+        u32::from_str_radix(digits, 16).ok()
+    }
+
+    fn parse_number(&mut self) -> Option<JsonValue> {
+        let start = self.offset;
+
+        while self
+            .peek()
+            .is_some_and(|x| x.is_ascii_digit() || matches!(x, b'-' | b'+' | b'.' | b'e' | b'E'))
+        {
+            self.offset += 1;
+        }
+
+        let text = std::str::from_utf8(&self.bytes[start..self.offset]).ok()?;
+
+        text.parse::<f64>().ok().map(JsonValue::Number)
+    }
+}
@@ -0,0 +1,8 @@
+pub mod gltf_loader;
+pub mod indexed_mesh;
+pub mod json;
+pub mod mtl_loader;
+pub mod obj_loader;
+pub mod ply_loader;
+pub mod smooth_normals;
+pub mod stl_loader;
@@ -0,0 +1,97 @@
+use crate::{common::Real, math::color3::Color3};
+use std::{fs, path::Path};
+
+/// A material read from a Wavefront MTL file. Only the properties that map onto the renderer's materials are kept.
+#[derive(Debug, Clone)]
+pub struct MtlMaterial {
+    pub name: String,
+
+    /// The diffuse color (`Kd`).
+    pub diffuse_color: Color3,
+
+    /// The specular color (`Ks`).
+    pub specular_color: Color3,
+
+    /// The emissive color (`Ke`).
+    pub emissive_color: Color3,
+
+    /// The specular exponent (`Ns`).
+    pub shininess: Real,
+
+    /// The index of refraction (`Ni`).
+    pub index_of_refraction: Real,
+
+    /// How opaque the material is (`d`, or one minus `Tr`).
+    pub dissolve: Real,
+}
+
+impl MtlMaterial {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            diffuse_color: Color3::from_value(0.8),
+            specular_color: Color3::default(),
+            emissive_color: Color3::default(),
+            shininess: 0.0,
+            index_of_refraction: 1.0,
+            dissolve: 1.0,
+        }
+    }
+}
+
+/// Loads a Wavefront MTL file. Returns `None` if the file can't be read or is malformed.
+pub fn load_mtl(path: &Path) -> Option<Vec<MtlMaterial>> {
+    let contents = fs::read_to_string(path).ok()?;
+    parse_mtl(&contents)
+}
+
+/// Parses the contents of a Wavefront MTL file.
+pub fn parse_mtl(contents: &str) -> Option<Vec<MtlMaterial>> {
+    let mut materials: Vec<MtlMaterial> = Vec::new();
+
+    for line in contents.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let mut tokens = line.split_whitespace();
+
+        let Some(keyword) = tokens.next() else {
+            continue;
+        };
+
+        if keyword == "newmtl" {
+            materials.push(MtlMaterial::new(&tokens.collect::<Vec<_>>().join(" ")));
+            continue;
+        }
+
+        // Properties before the first material have nothing to apply to.
+        let Some(material) = materials.last_mut() else {
+            continue;
+        };
+
+        match keyword {
+            "Kd" => material.diffuse_color = parse_color3(&mut tokens)?,
+            "Ks" => material.specular_color = parse_color3(&mut tokens)?,
+            "Ke" => material.emissive_color = parse_color3(&mut tokens)?,
+            "Ns" => material.shininess = tokens.next()?.parse().ok()?,
+            "Ni" => material.index_of_refraction = tokens.next()?.parse().ok()?,
+            "d" => material.dissolve = tokens.next()?.parse().ok()?,
+            "Tr" => material.dissolve = 1.0 - tokens.next()?.parse::<Real>().ok()?,
+            _ => {}
+        }
+    }
+
+    Some(materials)
+}
+
+fn parse_color3<'a>(tokens: &mut impl Iterator<Item = &'a str>) -> Option<Color3> {
+    let r = tokens.next()?.parse::<Real>().ok()?;
+
+    // A single value sets every channel.
+    let Some(g) = tokens.next() else {
+        return Some(Color3::from_value(r));
+    };
+
+    let g = g.parse::<Real>().ok()?;
+    let b = tokens.next()?.parse::<Real>().ok()?;
+
+    Some(Color3::new(r, g, b))
+}
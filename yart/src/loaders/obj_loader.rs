@@ -0,0 +1,170 @@
+use crate::{
+    common::Real,
+    geometries::mesh_buffers::MeshBuffers,
+    math::{vector2::Vector2, vector3::Vector3},
+};
+use std::{fs, path::Path};
+
+/// A triangle read from an OBJ file. Polygons with more than three vertices are split into a fan of triangles.
+#[derive(Debug, Clone)]
+pub struct ObjTriangle {
+    pub position_indices: [u32; 3],
+    pub normal_indices: Option<[u32; 3]>,
+    pub uv_indices: Option<[u32; 3]>,
+
+    /// The index into [`ObjModel::material_names`] of the material selected with `usemtl`.
+    pub material: Option<usize>,
+}
+
+/// The contents of a Wavefront OBJ file. Objects and groups are merged into a single mesh.
+#[derive(Debug, Clone, Default)]
+pub struct ObjModel {
+    pub buffers: MeshBuffers,
+    pub triangles: Vec<ObjTriangle>,
+    pub material_names: Vec<String>,
+
+    /// The MTL files referenced with `mtllib`, relative to the OBJ file.
+    pub material_libraries: Vec<String>,
+}
+
+/// Loads a Wavefront OBJ file. Returns `None` if the file can't be read or is malformed.
+pub fn load_obj(path: &Path) -> Option<ObjModel> {
+    let contents = fs::read_to_string(path).ok()?;
+    parse_obj(&contents)
+}
+
+/// Parses the contents of a Wavefront OBJ file.
+///
+/// # Examples
+///
+/// ```
+/// # use yart::loaders::obj_loader::*;
+/// #
+/// let model = parse_obj("v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nusemtl Red\nf 1 2 3 4\n").unwrap();
+///
+/// assert_eq!(4, model.buffers.positions.len());
+/// assert_eq!(2, model.triangles.len());
+/// assert_eq!([0, 2, 3], model.triangles[1].position_indices);
+/// assert_eq!(vec!["Red".to_string()], model.material_names);
+/// ```
+pub fn parse_obj(contents: &str) -> Option<ObjModel> {
+    let mut model = ObjModel::default();
+    let mut current_material = None;
+
+    for line in contents.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let mut tokens = line.split_whitespace();
+
+        let Some(keyword) = tokens.next() else {
+            continue;
+        };
+
+        match keyword {
+            "v" => model.buffers.positions.push(parse_vector3(&mut tokens)?),
+            "vn" => model.buffers.normals.push(parse_vector3(&mut tokens)?),
+            "vt" => {
+                let u = tokens.next()?.parse::<Real>().ok()?;
+                let v = tokens.next().map_or(Some(0.0), |x| x.parse::<Real>().ok())?;
+
+                // OBJ texture coordinates have `v` pointing up.
+                model.buffers.uvs.push(Vector2::new(u, 1.0 - v));
+            }
+            "f" => {
+                let vertices = tokens
+                    .map(|x| parse_face_vertex(x, &model.buffers))
+                    .collect::<Option<Vec<_>>>()?;
+
+                add_face(&mut model, &vertices, current_material);
+            }
+            "usemtl" => {
+                let name = tokens.collect::<Vec<_>>().join(" ");
+
+                current_material = Some(match model.material_names.iter().position(|x| *x == name) {
+                    Some(index) => index,
+                    None => {
+                        model.material_names.push(name);
+                        model.material_names.len() - 1
+                    }
+                });
+            }
+            "mtllib" => model.material_libraries.extend(tokens.map(|x| x.to_string())),
+            _ => {}
+        }
+    }
+
+    Some(model)
+}
+
+fn parse_vector3<'a>(tokens: &mut impl Iterator<Item = &'a str>) -> Option<Vector3> {
+    let x = tokens.next()?.parse::<Real>().ok()?;
+    let y = tokens.next()?.parse::<Real>().ok()?;
+    let z = tokens.next()?.parse::<Real>().ok()?;
+
+    Some(Vector3::new(x, y, z))
+}
+
+/// A single `position/uv/normal` reference of a face with the indices converted to be zero based.
+#[derive(Debug, Copy, Clone)]
+struct FaceVertex {
+    position: u32,
+    uv: Option<u32>,
+    normal: Option<u32>,
+}
+
+fn parse_face_vertex(token: &str, buffers: &MeshBuffers) -> Option<FaceVertex> {
+    let mut parts = token.split('/');
+
+    let position = resolve_index(parts.next()?, buffers.positions.len())?;
+
+    let uv = match parts.next() {
+        Some(x) if !x.is_empty() => Some(resolve_index(x, buffers.uvs.len())?),
+        _ => None,
+    };
+
+    let normal = match parts.next() {
+        Some(x) if !x.is_empty() => Some(resolve_index(x, buffers.normals.len())?),
+        _ => None,
+    };
+
+    Some(FaceVertex { position, uv, normal })
+}
+
+/// Converts a one based OBJ index, or a negative index relative to the end of the buffer, into a zero based index.
+fn resolve_index(token: &str, buffer_length: usize) -> Option<u32> {
+    let index = token.parse::<i64>().ok()?;
+
+    let resolved = if index < 0 {
+        buffer_length as i64 + index
+    } else {
+        index - 1
+    };
+
+    if (0..buffer_length as i64).contains(&resolved) {
+        Some(resolved as u32)
+    } else {
+        None
+    }
+}
+
+fn add_face(model: &mut ObjModel, vertices: &[FaceVertex], material: Option<usize>) {
+    for i in 1..vertices.len().saturating_sub(1) {
+        let corners = [vertices[0], vertices[i], vertices[i + 1]];
+
+        let normal_indices = match (corners[0].normal, corners[1].normal, corners[2].normal) {
+            (Some(a), Some(b), Some(c)) => Some([a, b, c]),
+            _ => None,
+        };
+
+        let uv_indices = match (corners[0].uv, corners[1].uv, corners[2].uv) {
+            (Some(a), Some(b), Some(c)) => Some([a, b, c]),
+            _ => None,
+        };
+
+        model.triangles.push(ObjTriangle {
+            position_indices: [corners[0].position, corners[1].position, corners[2].position],
+            normal_indices,
+            uv_indices,
+            material,
+        });
+    }
+}
@@ -0,0 +1,384 @@
+use super::indexed_mesh::IndexedMesh;
+use crate::{
+    common::Real,
+    geometries::mesh_buffers::MeshBuffers,
+    math::{color3::Color3, vector2::Vector2, vector3::Vector3},
+};
+use std::{fs, path::Path};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum PlyFormat {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum PlyScalarType {
+    Int8,
+    UInt8,
+    Int16,
+    UInt16,
+    Int32,
+    UInt32,
+    Float32,
+    Float64,
+}
+
+impl PlyScalarType {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "char" | "int8" => Some(Self::Int8),
+            "uchar" | "uint8" => Some(Self::UInt8),
+            "short" | "int16" => Some(Self::Int16),
+            "ushort" | "uint16" => Some(Self::UInt16),
+            "int" | "int32" => Some(Self::Int32),
+            "uint" | "uint32" => Some(Self::UInt32),
+            "float" | "float32" => Some(Self::Float32),
+            "double" | "float64" => Some(Self::Float64),
+            _ => None,
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Self::Int8 | Self::UInt8 => 1,
+            Self::Int16 | Self::UInt16 => 2,
+            Self::Int32 | Self::UInt32 | Self::Float32 => 4,
+            Self::Float64 => 8,
+        }
+    }
+
+    /// The value that maps to one when the type is used to store a color channel.
+    fn color_scale(&self) -> Real {
+        match self {
+            Self::UInt8 => 255.0,
+            Self::UInt16 => 65535.0,
+            _ => 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum PlyPropertyType {
+    Scalar(PlyScalarType),
+    List(PlyScalarType, PlyScalarType),
+}
+
+#[derive(Debug, Clone)]
+struct PlyProperty {
+    name: String,
+    property_type: PlyPropertyType,
+}
+
+#[derive(Debug, Clone)]
+struct PlyElement {
+    name: String,
+    count: usize,
+    properties: Vec<PlyProperty>,
+}
+
+impl PlyElement {
+    fn find_property(&self, name: &str) -> Option<usize> {
+        self.properties.iter().position(|x| x.name == name)
+    }
+}
+
+/// Reads the values of the body of a PLY file one at a time, regardless of whether it's stored as text or binary.
+struct PlyReader<'a> {
+    format: PlyFormat,
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl PlyReader<'_> {
+    fn read(&mut self, scalar_type: PlyScalarType) -> Option<Real> {
+        if self.format == PlyFormat::Ascii {
+            return self.read_token()?.parse::<Real>().ok();
+        }
+
+        let bytes = self.read_bytes(scalar_type)?;
+
+        let value = match scalar_type {
+            PlyScalarType::Float32 => f32::from_le_bytes(bytes[..4].try_into().ok()?) as Real,
+            PlyScalarType::Float64 => f64::from_le_bytes(bytes) as Real,
+            _ => integer_from_le_bytes(scalar_type, &bytes)? as Real,
+        };
+
+        Some(value)
+    }
+
+    /// Reads a value as an integer, such as a vertex index, which a single precision [`Real`] can't represent exactly
+    /// beyond 2^24. Values of floating point types are truncated.
+    fn read_integer(&mut self, scalar_type: PlyScalarType) -> Option<i64> {
+        if self.format == PlyFormat::Ascii {
+            let token = self.read_token()?;
+            return token
+                .parse::<i64>()
+                .ok()
+                .or_else(|| Some(token.parse::<f64>().ok()? as i64));
+        }
+
+        let bytes = self.read_bytes(scalar_type)?;
+
+        match scalar_type {
+            PlyScalarType::Float32 => Some(f32::from_le_bytes(bytes[..4].try_into().ok()?) as i64),
+            PlyScalarType::Float64 => Some(f64::from_le_bytes(bytes) as i64),
+            _ => integer_from_le_bytes(scalar_type, &bytes),
+        }
+    }
+
+    /// Reads the bytes of a binary value, in little endian order regardless of the format.
+    fn read_bytes(&mut self, scalar_type: PlyScalarType) -> Option<[u8; 8]> {
+        let size = scalar_type.size();
+        let mut bytes = [0u8; 8];
+
+        bytes[..size].copy_from_slice(self.data.get(self.offset..self.offset + size)?);
+        self.offset += size;
+
+        if self.format == PlyFormat::BinaryBigEndian {
+            bytes[..size].reverse();
+        }
+
+        Some(bytes)
+    }
+
+    fn read_token(&mut self) -> Option<&str> {
+        while self.data.get(self.offset)?.is_ascii_whitespace() {
+            self.offset += 1;
+        }
+
+        let start = self.offset;
+
+        while self.offset < self.data.len() && !self.data[self.offset].is_ascii_whitespace() {
+            self.offset += 1;
+        }
+
+        std::str::from_utf8(&self.data[start..self.offset]).ok()
+    }
+
+    /// Reads every property of one element, reading each value with `read_value`. Lists are returned with all of
+    /// their items.
+    fn read_element<T>(
+        &mut self,
+        element: &PlyElement,
+        read_value: fn(&mut Self, PlyScalarType) -> Option<T>,
+    ) -> Option<Vec<Vec<T>>> {
+        element
+            .properties
+            .iter()
+            .map(|property| match property.property_type {
+                PlyPropertyType::Scalar(scalar_type) => Some(vec![read_value(self, scalar_type)?]),
+                PlyPropertyType::List(count_type, item_type) => {
+                    let count = usize::try_from(self.read_integer(count_type)?).ok()?;
+                    (0..count).map(|_| read_value(self, item_type)).collect()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Converts the little endian bytes of a value of an integer type.
+fn integer_from_le_bytes(scalar_type: PlyScalarType, bytes: &[u8; 8]) -> Option<i64> {
+    let value = match scalar_type {
+        PlyScalarType::Int8 => i8::from_le_bytes([bytes[0]]) as i64,
+        PlyScalarType::UInt8 => bytes[0] as i64,
+        PlyScalarType::Int16 => i16::from_le_bytes([bytes[0], bytes[1]]) as i64,
+        PlyScalarType::UInt16 => u16::from_le_bytes([bytes[0], bytes[1]]) as i64,
+        PlyScalarType::Int32 => i32::from_le_bytes(bytes[..4].try_into().ok()?) as i64,
+        PlyScalarType::UInt32 => u32::from_le_bytes(bytes[..4].try_into().ok()?) as i64,
+        PlyScalarType::Float32 | PlyScalarType::Float64 => return None,
+    };
+
+    Some(value)
+}
+
+/// Loads a PLY file in either the ASCII or binary format. Vertex normals, colors and texture coordinates are loaded
+/// when present and polygons are split into fans of triangles. Returns `None` if the file can't be read or is
+/// malformed.
+pub fn load_ply(path: &Path) -> Option<IndexedMesh> {
+    let data = fs::read(path).ok()?;
+    parse_ply(&data)
+}
+
+/// Parses the contents of a PLY file.
+///
+/// # Examples
+///
+/// ```
+/// # use yart::loaders::ply_loader::*;
+/// #
+/// let ply = "ply
+/// format ascii 1.0
+/// element vertex 4
+/// property float x
+/// property float y
+/// property float z
+/// property uchar red
+/// property uchar green
+/// property uchar blue
+/// element face 1
+/// property list uchar int vertex_indices
+/// end_header
+/// 0 0 0 255 0 0
+/// 1 0 0 0 255 0
+/// 1 1 0 0 0 255
+/// 0 1 0 255 255 255
+/// 4 0 1 2 3
+/// ";
+///
+/// let mesh = parse_ply(ply.as_bytes()).unwrap();
+///
+/// assert_eq!(4, mesh.buffers.colors.len());
+/// assert_eq!(vec![[0, 1, 2], [0, 2, 3]], mesh.indices);
+/// ```
+pub fn parse_ply(data: &[u8]) -> Option<IndexedMesh> {
+    let (format, elements, body_offset) = parse_header(data)?;
+
+    let mut reader = PlyReader {
+        format,
+        data,
+        offset: body_offset,
+    };
+
+    let mut buffers = MeshBuffers::default();
+    let mut indices = Vec::new();
+
+    for element in &elements {
+        match element.name.as_str() {
+            "vertex" => read_vertices(&mut reader, element, &mut buffers)?,
+            "face" => read_faces(&mut reader, element, buffers.positions.len(), &mut indices)?,
+            _ => {
+                for _ in 0..element.count {
+                    reader.read_element(element, PlyReader::read)?;
+                }
+            }
+        }
+    }
+
+    Some(IndexedMesh::new(buffers, indices))
+}
+
+fn parse_header(data: &[u8]) -> Option<(PlyFormat, Vec<PlyElement>, usize)> {
+    let mut format = None;
+    let mut elements: Vec<PlyElement> = Vec::new();
+    let mut offset = 0;
+
+    let mut lines = data.split(|x| *x == b'\n');
+
+    let first_line = lines.next()?;
+
+    if std::str::from_utf8(first_line).ok()?.trim() != "ply" {
+        return None;
+    }
+
+    offset += first_line.len() + 1;
+
+    for line in lines {
+        offset += line.len() + 1;
+
+        let line = std::str::from_utf8(line).ok()?;
+        let tokens = line.split_whitespace().collect::<Vec<_>>();
+
+        match tokens.as_slice() {
+            ["format", name, _] => {
+                format = Some(match *name {
+                    "ascii" => PlyFormat::Ascii,
+                    "binary_little_endian" => PlyFormat::BinaryLittleEndian,
+                    "binary_big_endian" => PlyFormat::BinaryBigEndian,
+                    _ => return None,
+                })
+            }
+            ["element", name, count] => elements.push(PlyElement {
+                name: name.to_string(),
+                count: count.parse().ok()?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count_type, item_type, name] => elements.last_mut()?.properties.push(PlyProperty {
+                name: name.to_string(),
+                property_type: PlyPropertyType::List(
+                    PlyScalarType::parse(count_type)?,
+                    PlyScalarType::parse(item_type)?,
+                ),
+            }),
+            ["property", scalar_type, name] => elements.last_mut()?.properties.push(PlyProperty {
+                name: name.to_string(),
+                property_type: PlyPropertyType::Scalar(PlyScalarType::parse(scalar_type)?),
+            }),
+            ["end_header"] => return Some((format?, elements, offset)),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn read_vertices(reader: &mut PlyReader, element: &PlyElement, buffers: &mut MeshBuffers) -> Option<()> {
+    let find = |names: &[&str]| names.iter().find_map(|name| element.find_property(name));
+
+    let position = [find(&["x"])?, find(&["y"])?, find(&["z"])?];
+    let normal = [find(&["nx"]), find(&["ny"]), find(&["nz"])];
+    let uv = [find(&["u", "s", "texture_u"]), find(&["v", "t", "texture_v"])];
+    let color = [find(&["red", "r"]), find(&["green", "g"]), find(&["blue", "b"])];
+
+    let color_scale = color[0].map_or(1.0, |index| match element.properties[index].property_type {
+        PlyPropertyType::Scalar(scalar_type) => scalar_type.color_scale(),
+        PlyPropertyType::List(..) => 1.0,
+    });
+
+    for _ in 0..element.count {
+        let values = reader.read_element(element, PlyReader::read)?;
+        let value = |index: usize| values[index].first().copied();
+
+        buffers.positions.push(Vector3::new(
+            value(position[0])?,
+            value(position[1])?,
+            value(position[2])?,
+        ));
+
+        if let [Some(x), Some(y), Some(z)] = normal {
+            buffers.normals.push(Vector3::new(value(x)?, value(y)?, value(z)?));
+        }
+
+        if let [Some(u), Some(v)] = uv {
+            // PLY texture coordinates have `v` pointing up, like OBJ ones.
+            buffers.uvs.push(Vector2::new(value(u)?, 1.0 - value(v)?));
+        }
+
+        if let [Some(r), Some(g), Some(b)] = color {
+            buffers.colors.push(Color3::new(
+                value(r)? / color_scale,
+                value(g)? / color_scale,
+                value(b)? / color_scale,
+            ));
+        }
+    }
+
+    Some(())
+}
+
+fn read_faces(
+    reader: &mut PlyReader,
+    element: &PlyElement,
+    vertex_count: usize,
+    indices: &mut Vec<[u32; 3]>,
+) -> Option<()> {
+    let vertex_indices = element
+        .find_property("vertex_indices")
+        .or_else(|| element.find_property("vertex_index"))?;
+
+    for _ in 0..element.count {
+        let values = reader.read_element(element, PlyReader::read_integer)?;
+        let polygon = &values[vertex_indices];
+
+        if polygon.iter().any(|x| !(0..vertex_count as i64).contains(x)) {
+            return None;
+        }
+
+        for i in 1..polygon.len().saturating_sub(1) {
+            indices.push([polygon[0] as u32, polygon[i] as u32, polygon[i + 1] as u32]);
+        }
+    }
+
+    Some(())
+}
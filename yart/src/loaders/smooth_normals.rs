@@ -0,0 +1,125 @@
+use crate::{
+    common::Real,
+    math::{vector::Vector, vector3::Vector3},
+    normalize,
+};
+
+/// Generates vertex normals for a mesh by averaging the normals of the faces surrounding each vertex. Faces only
+/// contribute to each other's normals when the angle between them is at most `angle_threshold` radians, which keeps
+/// hard edges sharp while smoothing curved surfaces. Faces are weighted by their area.
+///
+/// Returns the normals and, for every triangle, the indices of the normals of its corners. Corners sharing both a
+/// vertex and a normal share an index.
+///
+/// # Examples
+///
+/// ```
+/// # use yart::common::*;
+/// # use yart::loaders::smooth_normals::*;
+/// # use yart::math::vector::*;
+/// # use yart::math::vector3::*;
+/// #
+/// // Two faces of a cube meeting at a right angle.
+/// let positions = vec![
+///     Vector3::new(0.0, 0.0, 0.0),
+///     Vector3::new(0.0, 0.0, 1.0),
+///     Vector3::new(1.0, 0.0, 0.0),
+///     Vector3::new(0.0, 1.0, 0.0),
+/// ];
+/// let indices = vec![[0, 1, 2], [0, 3, 1]];
+///
+/// let (sharp_normals, _) = generate_smooth_normals(&positions, &indices, (30.0 as Real).to_radians());
+/// let (smooth_normals, _) = generate_smooth_normals(&positions, &indices, (100.0 as Real).to_radians());
+///
+/// assert_eq!(6, sharp_normals.len());
+/// assert_eq!(4, smooth_normals.len());
+///
+/// // A threshold of zero never smooths but still gives every corner its own face's normal.
+/// let positions = vec![
+///     Vector3::new(0.0, 0.0, 0.0),
+///     Vector3::new(1.0, 0.2, 0.3),
+///     Vector3::new(0.3, 1.0, 0.1),
+///     Vector3::new(0.2, 0.4, 1.0),
+/// ];
+/// let indices = vec![[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]];
+///
+/// let (flat_normals, _) = generate_smooth_normals(&positions, &indices, 0.0);
+///
+/// assert_eq!(12, flat_normals.len());
+///
+/// assert!(flat_normals.iter().all(|normal| (normal.length() - 1.0).abs() < 1e-6));
+/// ```
+pub fn generate_smooth_normals(
+    positions: &[Vector3],
+    indices: &[[u32; 3]],
+    angle_threshold: Real,
+) -> (Vec<Vector3>, Vec<[u32; 3]>) {
+    let cos_threshold = Real::cos(angle_threshold);
+
+    // The length of the cross product is twice the area of the triangle, which provides the weighting for free.
+    let face_normals = indices
+        .iter()
+        .map(|triangle| {
+            let vertex0 = positions[triangle[0] as usize];
+            (positions[triangle[1] as usize] - vertex0) % (positions[triangle[2] as usize] - vertex0)
+        })
+        .collect::<Vec<_>>();
+
+    let unit_face_normals = face_normals
+        .iter()
+        .map(|normal| {
+            if normal.length_squared() > 0.0 {
+                normalize!(*normal)
+            } else {
+                Vector3::default()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut faces_by_vertex = vec![Vec::new(); positions.len()];
+
+    for (face, triangle) in indices.iter().enumerate() {
+        for vertex in triangle {
+            faces_by_vertex[*vertex as usize].push(face);
+        }
+    }
+
+    let mut normals = Vec::new();
+    let mut normals_by_vertex: Vec<Vec<(Vector3, u32)>> = vec![Vec::new(); positions.len()];
+
+    let normal_indices = indices
+        .iter()
+        .enumerate()
+        .map(|(face, triangle)| {
+            triangle.map(|vertex| {
+                let mut normal = faces_by_vertex[vertex as usize]
+                    .iter()
+                    .filter(|other_face| {
+                        **other_face == face
+                            || (unit_face_normals[face] ^ unit_face_normals[**other_face]) >= cos_threshold
+                    })
+                    .fold(Vector3::default(), |sum, other_face| sum + face_normals[*other_face]);
+
+                if normal.length_squared() > 0.0 {
+                    normal = normalize!(normal);
+                }
+
+                let existing_normals = &mut normals_by_vertex[vertex as usize];
+
+                match existing_normals.iter().find(|(existing, _)| *existing == normal) {
+                    Some((_, index)) => *index,
+                    None => {
+                        let index = normals.len() as u32;
+
+                        normals.push(normal);
+                        existing_normals.push((normal, index));
+
+                        index
+                    }
+                }
+            })
+        })
+        .collect();
+
+    (normals, normal_indices)
+}
@@ -0,0 +1,130 @@
+use super::indexed_mesh::IndexedMesh;
+use crate::{common::Real, geometries::mesh_buffers::MeshBuffers, math::vector3::Vector3};
+use std::{collections::HashMap, fs, path::Path};
+
+const BINARY_HEADER_SIZE: usize = 84;
+const BINARY_TRIANGLE_SIZE: usize = 50;
+
+/// Loads an STL file in either the ASCII or binary format. STL files store every triangle separately, so vertices at
+/// the same position are merged to let the mesh be smoothed. The facet normals are discarded since they're always
+/// flat. Returns `None` if the file can't be read or is malformed.
+pub fn load_stl(path: &Path) -> Option<IndexedMesh> {
+    let data = fs::read(path).ok()?;
+    parse_stl(&data)
+}
+
+/// Parses the contents of an STL file.
+///
+/// # Examples
+///
+/// ```
+/// # use yart::loaders::stl_loader::*;
+/// #
+/// let stl = "solid square
+///   facet normal 0 0 1
+///     outer loop
+///       vertex 0 0 0
+///       vertex 1 0 0
+///       vertex 1 1 0
+///     endloop
+///   endfacet
+///   facet normal 0 0 1
+///     outer loop
+///       vertex 0 0 0
+///       vertex 1 1 0
+///       vertex 0 1 0
+///     endloop
+///   endfacet
+/// endsolid square
+/// ";
+///
+/// let mesh = parse_stl(stl.as_bytes()).unwrap();
+///
+/// assert_eq!(4, mesh.buffers.positions.len());
+/// assert_eq!(vec![[0, 1, 2], [0, 2, 3]], mesh.indices);
+/// ```
+pub fn parse_stl(data: &[u8]) -> Option<IndexedMesh> {
+    // Some exporters write binary files whose header starts with "solid" too, so the size is the more reliable test.
+    let is_binary = data.len() >= BINARY_HEADER_SIZE
+        && u32::from_le_bytes(data[80..84].try_into().ok()?) as usize * BINARY_TRIANGLE_SIZE + BINARY_HEADER_SIZE
+            == data.len();
+
+    let vertices = if is_binary {
+        parse_binary_vertices(data)?
+    } else {
+        parse_ascii_vertices(std::str::from_utf8(data).ok()?)?
+    };
+
+    Some(weld_vertices(&vertices))
+}
+
+fn parse_binary_vertices(data: &[u8]) -> Option<Vec<Vector3>> {
+    let read_f32 = |offset: usize| Some(f32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?) as Real);
+
+    let triangle_count = (data.len() - BINARY_HEADER_SIZE) / BINARY_TRIANGLE_SIZE;
+    let mut vertices = Vec::with_capacity(triangle_count * 3);
+
+    for triangle in 0..triangle_count {
+        // Skip the facet normal at the start of each triangle.
+        let offset = BINARY_HEADER_SIZE + triangle * BINARY_TRIANGLE_SIZE + 12;
+
+        for vertex in 0..3 {
+            let vertex_offset = offset + vertex * 12;
+
+            vertices.push(Vector3::new(
+                read_f32(vertex_offset)?,
+                read_f32(vertex_offset + 4)?,
+                read_f32(vertex_offset + 8)?,
+            ));
+        }
+    }
+
+    Some(vertices)
+}
+
+fn parse_ascii_vertices(contents: &str) -> Option<Vec<Vector3>> {
+    let mut vertices = Vec::new();
+
+    for line in contents.lines() {
+        let mut tokens = line.split_whitespace();
+
+        if tokens.next() == Some("vertex") {
+            let x = tokens.next()?.parse::<Real>().ok()?;
+            let y = tokens.next()?.parse::<Real>().ok()?;
+            let z = tokens.next()?.parse::<Real>().ok()?;
+
+            vertices.push(Vector3::new(x, y, z));
+        }
+    }
+
+    if vertices.len() % 3 == 0 {
+        Some(vertices)
+    } else {
+        None
+    }
+}
+
+/// Merges vertices with identical positions and builds the triangle indices.
+fn weld_vertices(vertices: &[Vector3]) -> IndexedMesh {
+    let mut buffers = MeshBuffers::default();
+    let mut index_by_position = HashMap::new();
+
+    let vertex_indices = vertices
+        .iter()
+        .map(|vertex| {
+            *index_by_position
+                .entry([vertex.x.to_bits(), vertex.y.to_bits(), vertex.z.to_bits()])
+                .or_insert_with(|| {
+                    buffers.positions.push(*vertex);
+                    buffers.positions.len() as u32 - 1
+                })
+        })
+        .collect::<Vec<_>>();
+
+    let indices = vertex_indices
+        .chunks_exact(3)
+        .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+        .collect();
+
+    IndexedMesh::new(buffers, indices)
+}
@@ -1,18 +1,21 @@
-#![feature(trait_upcasting)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 
 pub mod cameras;
 pub mod common;
 pub mod errors;
 pub mod geometries;
 pub mod lights;
+pub mod loaders;
 pub mod materials;
 pub mod math;
 pub mod miss_shaders;
+pub mod output;
 pub mod rendering;
 pub mod scene;
+pub mod textures;
 pub mod yaml;
 
-use image::{ImageBuffer, Rgb, RgbImage};
+use output::write_render_output;
 use rendering::render;
 use std::{error::Error, path::Path};
 use yaml::parse::load_scene;
@@ -22,20 +25,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     //println!("{:?}", scene.area_lights);
 
     let pixels = render(&scene);
+    write_render_output(&scene, &pixels)?;
 
-    //write_image_file(&pixels)?;
-    Ok(())
-}
-
-fn write_image_file(pixels: &Vec<Rgb<u8>>) -> Result<(), Box<dyn Error>> {
-    let mut image: RgbImage = ImageBuffer::new(1920, 1080);
-
-    for y in 0..1080 {
-        for x in 0..1920 {
-            image.put_pixel(x, y, pixels[(y * 1920 + x) as usize]);
-        }
-    }
-
-    image.save("test.png")?;
     Ok(())
 }
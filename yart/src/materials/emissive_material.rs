@@ -1,21 +1,20 @@
 use super::material::Material;
 use crate::{
-    geometries::geometry::Geometry,
+    geometries::{geometry::Geometry, ray::Ray, surface_point::SurfacePoint},
     math::{color3::Color3, vector3::Vector3},
     scene::Scene,
+    textures::{texture::Texture, texture_coordinates::TextureCoordinates},
 };
 use rand::RngCore;
 
 #[derive(Debug)]
 pub struct EmissiveMaterial {
-    pub emissive_color: Color3,
+    pub emissive_color: Box<dyn Texture>,
 }
 
 impl EmissiveMaterial {
-    pub fn new(emissive_color: &Color3) -> Self {
-        Self {
-            emissive_color: *emissive_color,
-        }
+    pub fn new(emissive_color: Box<dyn Texture>) -> Self {
+        Self { emissive_color }
     }
 }
 
@@ -26,10 +25,12 @@ impl Material for EmissiveMaterial {
         _scene: &Scene,
         _current_depth: u16,
         _hit_geometry: &dyn Geometry,
-        _hit_position: &Vector3,
+        hit_position: &Vector3,
         _hit_normal: &Vector3,
-        _incoming_direction: &Vector3,
+        surface_point: &SurfacePoint,
+        incoming_ray: &Ray,
     ) -> Color3 {
         self.emissive_color
+            .evaluate(&TextureCoordinates::from_hit(hit_position, surface_point, incoming_ray))
     }
 }
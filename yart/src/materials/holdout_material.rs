@@ -0,0 +1,93 @@
+use super::material::Material;
+use crate::{
+    common::Real,
+    geometries::{geometry::Geometry, ray::Ray, surface_point::SurfacePoint},
+    math::{color3::Color3, vector3::Vector3},
+    scene::Scene,
+};
+use rand::RngCore;
+
+/// A material that cuts a hole in the rendered image. Geometry using a holdout still hides whatever is behind it but
+/// is rendered as black with zero alpha.
+///
+/// When used as a shadow catcher the alpha is instead the fraction of the lights that are blocked from the hit
+/// position so that shadows cast onto the surface can be composited over a plate.
+#[derive(Debug)]
+pub struct HoldoutMaterial {
+    pub shadow_catcher: bool,
+}
+
+impl HoldoutMaterial {
+    pub fn new(shadow_catcher: bool) -> Self {
+        Self { shadow_catcher }
+    }
+}
+
+impl Material for HoldoutMaterial {
+    fn calculate_rendering_equation(
+        &self,
+        _rng: &mut dyn RngCore,
+        _scene: &Scene,
+        _current_depth: u16,
+        _hit_geometry: &dyn Geometry,
+        _hit_position: &Vector3,
+        _hit_normal: &Vector3,
+        _surface_point: &SurfacePoint,
+        _incoming_ray: &Ray,
+    ) -> Color3 {
+        Color3::default()
+    }
+
+    fn calculate_coverage(
+        &self,
+        rng: &mut dyn RngCore,
+        scene: &Scene,
+        hit_position: &Vector3,
+        hit_normal: &Vector3,
+        incoming_ray: &Ray,
+    ) -> Real {
+        if !self.shadow_catcher {
+            return 0.0;
+        }
+
+        let light_count = scene.lights.len() + scene.area_lights.len();
+
+        if light_count == 0 {
+            return 0.0;
+        }
+
+        let mut shadowed_count = 0;
+
+        for light in &scene.lights {
+            let direction_to_light = light.get_direction_towards_light(hit_position, hit_normal);
+
+            if light.is_in_shadow(
+                rng,
+                scene,
+                hit_position,
+                hit_normal,
+                &direction_to_light,
+                incoming_ray.time(),
+            ) {
+                shadowed_count += 1;
+            }
+        }
+
+        for area_light in &scene.area_lights {
+            let point_on_light = area_light.get_point_on_light(rng, hit_position, hit_normal);
+
+            if area_light.is_in_shadow(
+                rng,
+                scene,
+                hit_position,
+                hit_normal,
+                &point_on_light,
+                incoming_ray.time(),
+            ) {
+                shadowed_count += 1;
+            }
+        }
+
+        shadowed_count as Real / light_count as Real
+    }
+}
@@ -1,22 +1,21 @@
 use crate::{
     common::{Real, ONE_OVER_PI},
-    geometries::{geometry::Geometry, ray::Ray},
+    geometries::{geometry::Geometry, ray::Ray, surface_point::SurfacePoint},
     materials::material::{generate_cosine_weighted_hemisphere_sample, Material},
     math::{color3::Color3, vector3::Vector3},
     scene::Scene,
+    textures::{texture::Texture, texture_coordinates::TextureCoordinates},
 };
 use rand::{seq::SliceRandom, Rng, RngCore};
 
 #[derive(Debug)]
 pub struct LambertianMaterial {
-    pub diffuse_color: Color3,
+    pub diffuse_color: Box<dyn Texture>,
 }
 
 impl LambertianMaterial {
-    pub fn new(diffuse_color: &Color3) -> Self {
-        Self {
-            diffuse_color: *diffuse_color,
-        }
+    pub fn new(diffuse_color: Box<dyn Texture>) -> Self {
+        Self { diffuse_color }
     }
 }
 
@@ -29,25 +28,31 @@ impl Material for LambertianMaterial {
         _hit_geometry: &dyn Geometry,
         hit_position: &Vector3,
         hit_normal: &Vector3,
-        incoming_direction: &Vector3,
+        surface_point: &SurfacePoint,
+        incoming_ray: &Ray,
     ) -> Color3 {
-        let probability_factor = if scene.area_lights.len() == 0 { 1.0 } else { 2.0 };
+        let diffuse_color =
+            self.diffuse_color
+                .evaluate(&TextureCoordinates::from_hit(hit_position, surface_point, incoming_ray));
+
+        let probability_factor = if scene.area_lights.is_empty() { 1.0 } else { 2.0 };
 
-        if scene.area_lights.len() == 0 || rng.gen::<bool>() {
+        if scene.area_lights.is_empty() || rng.gen::<bool>() {
             // Indirect light sample according to material.
             let outgoing_direction = generate_cosine_weighted_hemisphere_sample(rng, hit_normal);
-            let outgoing_ray = Ray::new(hit_position, &outgoing_direction);
+            let outgoing_ray =
+                Ray::new_from_surface(hit_position, hit_normal, &outgoing_direction, incoming_ray.time());
 
             let color_sample = scene.cast_ray_color(rng, &outgoing_ray, current_depth + 1);
-            let output_color = self.diffuse_color * color_sample * probability_factor;
 
-            output_color
+            diffuse_color * color_sample * probability_factor
         } else {
             let area_light = scene.area_lights.choose(rng).unwrap();
 
             // Direct light sample to a random light.
             let outgoing_direction = area_light.get_direction_towards_light(rng, hit_position, hit_normal);
-            let outgoing_ray = Ray::new(hit_position, &outgoing_direction);
+            let outgoing_ray =
+                Ray::new_from_surface(hit_position, hit_normal, &outgoing_direction, incoming_ray.time());
 
             let color_sample = scene.cast_ray_color(rng, &outgoing_ray, current_depth + 1);
 
@@ -56,16 +61,13 @@ impl Material for LambertianMaterial {
                 rng,
                 hit_position,
                 hit_normal,
-                incoming_direction,
+                incoming_ray.direction(),
                 &outgoing_direction,
             );
 
             let cosine_theta = Real::max(0.0, hit_normal ^ outgoing_direction);
 
-            let output_color =
-                brdf * self.diffuse_color * color_sample * inverse_pdf * cosine_theta * probability_factor;
-
-            output_color
+            brdf * diffuse_color * color_sample * inverse_pdf * cosine_theta * probability_factor
         }
     }
 }
@@ -1,15 +1,17 @@
 use crate::{
     common::{Real, TWO_PI},
-    geometries::geometry::Geometry,
+    geometries::{geometry::Geometry, ray::Ray, surface_point::SurfacePoint},
     math::{color3::Color3, vector3::Vector3},
     scene::Scene,
+    textures::normal_perturbation::NormalPerturbation,
 };
 use rand::{Rng, RngCore};
 use std::fmt::Debug;
 
 pub type MaterialIndex = usize;
 
-pub trait Material: Debug {
+pub trait Material: Debug + Send + Sync {
+    #[allow(clippy::too_many_arguments)]
     fn calculate_rendering_equation(
         &self,
         rng: &mut dyn RngCore,
@@ -18,8 +20,32 @@ pub trait Material: Debug {
         hit_geometry: &dyn Geometry,
         hit_position: &Vector3,
         hit_normal: &Vector3,
-        incoming_direction: &Vector3,
+        surface_point: &SurfacePoint,
+        incoming_ray: &Ray,
     ) -> Color3;
+
+    /// Calculates how much of the background a camera ray hitting this material covers. Most materials are opaque
+    /// but holdouts and shadow catchers let the background show through when the image is composited.
+    fn calculate_coverage(
+        &self,
+        _rng: &mut dyn RngCore,
+        _scene: &Scene,
+        _hit_position: &Vector3,
+        _hit_normal: &Vector3,
+        _incoming_ray: &Ray,
+    ) -> Real {
+        1.0
+    }
+
+    /// Whether geometry using this material blocks light from reaching the surfaces behind it.
+    fn casts_shadows(&self) -> bool {
+        true
+    }
+
+    /// The normal or bump map the scene applies to the shading normal before the rendering equation is calculated.
+    fn normal_perturbation(&self) -> Option<&dyn NormalPerturbation> {
+        None
+    }
 }
 
 pub fn generate_cosine_weighted_hemisphere_sample(rng: &mut dyn RngCore, hit_normal: &Vector3) -> Vector3 {
@@ -27,9 +53,7 @@ pub fn generate_cosine_weighted_hemisphere_sample(rng: &mut dyn RngCore, hit_nor
     let random2 = rng.gen::<Real>();
 
     let random_hemisphere_vector = cosine_weighted_sample_hemisphere(random1, random2);
-    let outgoing_direction = transform_from_tangent_space_to_world_space(hit_normal, &random_hemisphere_vector);
-
-    outgoing_direction
+    transform_from_tangent_space_to_world_space(hit_normal, &random_hemisphere_vector)
 }
 
 fn cosine_weighted_sample_hemisphere(random1: Real, random2: Real) -> Vector3 {
@@ -0,0 +1,183 @@
+use crate::{
+    common::{Real, ONE_OVER_PI},
+    geometries::{geometry::Geometry, ray::Ray, surface_point::SurfacePoint},
+    materials::material::{generate_cosine_weighted_hemisphere_sample, Material},
+    math::{color3::Color3, vector::Vector, vector3::Vector3},
+    normalize,
+    scene::Scene,
+    textures::{texture::Texture, texture_coordinates::TextureCoordinates},
+};
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+/// The reflectance of dielectrics at normal incidence used by the metallic-roughness model.
+const DIELECTRIC_REFLECTANCE: Real = 0.04;
+
+/// A physically based material following the metallic-roughness model used by glTF. Dielectrics are a mix of a
+/// diffuse base and a white specular coating, while metals only reflect, tinted by their base color. Rough surfaces
+/// scatter the specular reflection around the mirror direction. The metallic and roughness textures are evaluated as
+/// scalars and clamped between zero and one.
+///
+/// Lights are sampled directly for the diffuse base. The specular reflection only gathers light by following rays, so
+/// it doesn't reflect punctual lights, which rays can't hit.
+#[derive(Debug)]
+pub struct MetallicRoughnessMaterial {
+    pub base_color: Box<dyn Texture>,
+    pub metallic: Box<dyn Texture>,
+    pub roughness: Box<dyn Texture>,
+    pub emissive_color: Box<dyn Texture>,
+}
+
+impl MetallicRoughnessMaterial {
+    pub fn new(
+        base_color: Box<dyn Texture>,
+        metallic: Box<dyn Texture>,
+        roughness: Box<dyn Texture>,
+        emissive_color: Box<dyn Texture>,
+    ) -> Self {
+        Self {
+            base_color,
+            metallic,
+            roughness,
+            emissive_color,
+        }
+    }
+
+    fn sample_glossy_direction(rng: &mut dyn RngCore, reflected_direction: &Vector3, roughness: Real) -> Vector3 {
+        let alpha = roughness * roughness;
+
+        if alpha <= 0.0 {
+            return *reflected_direction;
+        }
+
+        let offset = loop {
+            let candidate = Vector3::new(
+                rng.gen::<Real>() * 2.0 - 1.0,
+                rng.gen::<Real>() * 2.0 - 1.0,
+                rng.gen::<Real>() * 2.0 - 1.0,
+            );
+
+            if candidate.length_squared() <= 1.0 {
+                break candidate;
+            }
+        };
+
+        normalize!(reflected_direction + offset * alpha)
+    }
+
+    /// Estimates the light reflected by a white diffuse base. Punctual lights are gathered directly, and area lights
+    /// are sampled half of the time the same way [`super::lambertian_material::LambertianMaterial`] does.
+    fn sample_diffuse_light(
+        rng: &mut dyn RngCore,
+        scene: &Scene,
+        current_depth: u16,
+        hit_position: &Vector3,
+        hit_normal: &Vector3,
+        incoming_ray: &Ray,
+    ) -> Color3 {
+        let mut color = Color3::default();
+
+        for light in &scene.lights {
+            let direction_to_light = light.get_direction_towards_light(hit_position, hit_normal);
+            let cosine_theta = direction_to_light ^ hit_normal;
+
+            if cosine_theta <= 0.0
+                || light.is_in_shadow(
+                    rng,
+                    scene,
+                    hit_position,
+                    hit_normal,
+                    &direction_to_light,
+                    incoming_ray.time(),
+                )
+            {
+                continue;
+            }
+
+            color += ONE_OVER_PI * light.calculate_color_at(hit_position) * cosine_theta;
+        }
+
+        let probability_factor = if scene.area_lights.is_empty() { 1.0 } else { 2.0 };
+
+        let color_sample = if scene.area_lights.is_empty() || rng.gen::<bool>() {
+            // Indirect light sample according to the diffuse base.
+            let outgoing_direction = generate_cosine_weighted_hemisphere_sample(rng, hit_normal);
+            let outgoing_ray =
+                Ray::new_from_surface(hit_position, hit_normal, &outgoing_direction, incoming_ray.time());
+
+            scene.cast_ray_color(rng, &outgoing_ray, current_depth + 1)
+        } else {
+            let area_light = scene.area_lights.choose(rng).unwrap();
+
+            // Direct light sample to a random light.
+            let outgoing_direction = area_light.get_direction_towards_light(rng, hit_position, hit_normal);
+            let outgoing_ray =
+                Ray::new_from_surface(hit_position, hit_normal, &outgoing_direction, incoming_ray.time());
+
+            let inverse_pdf = area_light.calculate_inverse_pdf(
+                rng,
+                hit_position,
+                hit_normal,
+                incoming_ray.direction(),
+                &outgoing_direction,
+            );
+
+            let cosine_theta = Real::max(0.0, hit_normal ^ outgoing_direction);
+
+            ONE_OVER_PI * scene.cast_ray_color(rng, &outgoing_ray, current_depth + 1) * inverse_pdf * cosine_theta
+        };
+
+        color + color_sample * probability_factor
+    }
+}
+
+impl Material for MetallicRoughnessMaterial {
+    fn calculate_rendering_equation(
+        &self,
+        rng: &mut dyn RngCore,
+        scene: &Scene,
+        current_depth: u16,
+        _hit_geometry: &dyn Geometry,
+        hit_position: &Vector3,
+        hit_normal: &Vector3,
+        surface_point: &SurfacePoint,
+        incoming_ray: &Ray,
+    ) -> Color3 {
+        let texture_coordinates = TextureCoordinates::from_hit(hit_position, surface_point, incoming_ray);
+
+        let base_color = self.base_color.evaluate(&texture_coordinates);
+        let emissive_color = self.emissive_color.evaluate(&texture_coordinates);
+        let metallic = Real::clamp(self.metallic.evaluate_scalar(&texture_coordinates), 0.0, 1.0);
+        let roughness = Real::clamp(self.roughness.evaluate_scalar(&texture_coordinates), 0.0, 1.0);
+
+        let specular_color = DIELECTRIC_REFLECTANCE * (1.0 - metallic) + base_color * metallic;
+
+        let cosine_theta = Real::clamp(-(incoming_ray.direction() ^ hit_normal), 0.0, 1.0);
+        let fresnel = specular_color + (1.0 - specular_color) * Real::powi(1.0 - cosine_theta, 5);
+
+        // Pick the lobe to follow in proportion to how much it contributes so that neither is undersampled.
+        let specular_probability = Real::clamp(fresnel.luminance(), 0.1, 0.9);
+
+        let color = if rng.gen::<Real>() < specular_probability {
+            let reflected_direction = normalize!(incoming_ray.direction().reflect(hit_normal));
+            let outgoing_direction = Self::sample_glossy_direction(rng, &reflected_direction, roughness);
+
+            // Rays scattered below the surface are absorbed.
+            if (outgoing_direction ^ hit_normal) <= 0.0 {
+                return emissive_color;
+            }
+
+            let outgoing_ray =
+                Ray::new_from_surface(hit_position, hit_normal, &outgoing_direction, incoming_ray.time());
+
+            fresnel * scene.cast_ray_color(rng, &outgoing_ray, current_depth + 1) / specular_probability
+        } else {
+            let diffuse_color = (1.0 - fresnel) * base_color * (1.0 - metallic);
+            let diffuse_light =
+                Self::sample_diffuse_light(rng, scene, current_depth, hit_position, hit_normal, incoming_ray);
+
+            diffuse_color * diffuse_light / (1.0 - specular_probability)
+        };
+
+        emissive_color + color
+    }
+}
@@ -1,6 +1,10 @@
 pub mod emissive_material;
+pub mod holdout_material;
 pub mod lambertian_material;
 pub mod material;
+pub mod metallic_roughness_material;
+pub mod normal_mapped_material;
 pub mod phong_material;
 pub mod reflective_material;
 pub mod refractive_material;
+pub mod shadowless_material;
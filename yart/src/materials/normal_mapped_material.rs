@@ -0,0 +1,71 @@
+use super::material::Material;
+use crate::{
+    common::Real,
+    geometries::{geometry::Geometry, ray::Ray, surface_point::SurfacePoint},
+    math::{color3::Color3, vector3::Vector3},
+    scene::Scene,
+    textures::normal_perturbation::NormalPerturbation,
+};
+use rand::RngCore;
+
+/// Renders like the material it wraps, but with a normal or bump map that the scene applies to the shading normal
+/// before the wrapped material sees it.
+#[derive(Debug)]
+pub struct NormalMappedMaterial {
+    material: Box<dyn Material>,
+    normal_perturbation: Box<dyn NormalPerturbation>,
+}
+
+impl NormalMappedMaterial {
+    pub fn new(material: Box<dyn Material>, normal_perturbation: Box<dyn NormalPerturbation>) -> Self {
+        Self {
+            material,
+            normal_perturbation,
+        }
+    }
+}
+
+impl Material for NormalMappedMaterial {
+    fn calculate_rendering_equation(
+        &self,
+        rng: &mut dyn RngCore,
+        scene: &Scene,
+        current_depth: u16,
+        hit_geometry: &dyn Geometry,
+        hit_position: &Vector3,
+        hit_normal: &Vector3,
+        surface_point: &SurfacePoint,
+        incoming_ray: &Ray,
+    ) -> Color3 {
+        self.material.calculate_rendering_equation(
+            rng,
+            scene,
+            current_depth,
+            hit_geometry,
+            hit_position,
+            hit_normal,
+            surface_point,
+            incoming_ray,
+        )
+    }
+
+    fn calculate_coverage(
+        &self,
+        rng: &mut dyn RngCore,
+        scene: &Scene,
+        hit_position: &Vector3,
+        hit_normal: &Vector3,
+        incoming_ray: &Ray,
+    ) -> Real {
+        self.material
+            .calculate_coverage(rng, scene, hit_position, hit_normal, incoming_ray)
+    }
+
+    fn casts_shadows(&self) -> bool {
+        self.material.casts_shadows()
+    }
+
+    fn normal_perturbation(&self) -> Option<&dyn NormalPerturbation> {
+        Some(self.normal_perturbation.as_ref())
+    }
+}
@@ -1,27 +1,34 @@
 use super::material::Material;
 use crate::{
     common::Real,
-    geometries::geometry::Geometry,
+    geometries::{geometry::Geometry, ray::Ray, surface_point::SurfacePoint},
     math::{color3::Color3, vector3::Vector3},
     scene::Scene,
+    textures::{texture::Texture, texture_coordinates::TextureCoordinates},
 };
 use rand::RngCore;
 
 #[derive(Debug)]
 pub struct PhongMaterial {
-    pub ambient_color: Color3,
-    pub diffuse_color: Color3,
-    pub specular_color: Color3,
+    pub ambient_color: Box<dyn Texture>,
+    pub diffuse_color: Box<dyn Texture>,
+    pub specular_color: Box<dyn Texture>,
 
-    pub shininess: Real,
+    /// Evaluated as a scalar.
+    pub shininess: Box<dyn Texture>,
 }
 
 impl PhongMaterial {
-    pub fn new(ambient_color: &Color3, diffuse_color: &Color3, specular_color: &Color3, shininess: Real) -> Self {
+    pub fn new(
+        ambient_color: Box<dyn Texture>,
+        diffuse_color: Box<dyn Texture>,
+        specular_color: Box<dyn Texture>,
+        shininess: Box<dyn Texture>,
+    ) -> Self {
         Self {
-            ambient_color: *ambient_color,
-            diffuse_color: *diffuse_color,
-            specular_color: *specular_color,
+            ambient_color,
+            diffuse_color,
+            specular_color,
             shininess,
         }
     }
@@ -36,29 +43,44 @@ impl Material for PhongMaterial {
         _hit_geometry: &dyn Geometry,
         hit_position: &Vector3,
         hit_normal: &Vector3,
-        incoming_direction: &Vector3,
+        surface_point: &SurfacePoint,
+        incoming_ray: &Ray,
     ) -> Color3 {
-        let ambient_component = self.ambient_color;
+        let texture_coordinates = TextureCoordinates::from_hit(hit_position, surface_point, incoming_ray);
+
+        let diffuse_color = self.diffuse_color.evaluate(&texture_coordinates);
+        let specular_color = self.specular_color.evaluate(&texture_coordinates);
+        let shininess = self.shininess.evaluate_scalar(&texture_coordinates);
+
+        let ambient_component = self.ambient_color.evaluate(&texture_coordinates);
         let mut diffuse_component = Color3::default();
         let mut specular_component = Color3::default();
 
         for light in &scene.lights {
             let direction_to_light = light.get_direction_towards_light(hit_position, hit_normal);
 
-            if light.is_in_shadow(rng, scene, hit_position, hit_normal, &direction_to_light) {
+            if light.is_in_shadow(
+                rng,
+                scene,
+                hit_position,
+                hit_normal,
+                &direction_to_light,
+                incoming_ray.time(),
+            ) {
                 continue;
             }
 
+            let light_color = light.calculate_color_at(hit_position);
+
             let light_dot_normal = direction_to_light ^ hit_normal;
             if light_dot_normal >= 0.0 {
-                diffuse_component += light_dot_normal * self.diffuse_color * light.color();
+                diffuse_component += light_dot_normal * diffuse_color * light_color;
 
                 let reflection_direction = direction_to_light.reflect(hit_normal);
-                let reflection_dot_view = reflection_direction ^ incoming_direction;
+                let reflection_dot_view = reflection_direction ^ incoming_ray.direction();
 
                 if reflection_dot_view >= 0.0 {
-                    specular_component +=
-                        Real::powf(reflection_dot_view, self.shininess) * self.specular_color * light.color();
+                    specular_component += Real::powf(reflection_dot_view, shininess) * specular_color * light_color;
                 }
             }
         }
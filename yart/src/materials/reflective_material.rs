@@ -1,13 +1,13 @@
 use super::material::Material;
 use crate::{
-    geometries::{geometry::Geometry, ray::Ray},
+    geometries::{geometry::Geometry, ray::Ray, surface_point::SurfacePoint},
     math::{color3::Color3, vector3::Vector3},
     normalize,
     scene::Scene,
 };
 use rand::RngCore;
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ReflectiveMaterial {}
 
 impl ReflectiveMaterial {
@@ -25,10 +25,11 @@ impl Material for ReflectiveMaterial {
         _hit_geometry: &dyn Geometry,
         hit_position: &Vector3,
         hit_normal: &Vector3,
-        incoming_direction: &Vector3,
+        _surface_point: &SurfacePoint,
+        incoming_ray: &Ray,
     ) -> Color3 {
-        let reflected_direction = normalize!(incoming_direction.reflect(hit_normal));
-        let outgoing_ray = Ray::new(hit_position, &reflected_direction);
+        let reflected_direction = normalize!(incoming_ray.direction().reflect(hit_normal));
+        let outgoing_ray = Ray::new_from_surface(hit_position, hit_normal, &reflected_direction, incoming_ray.time());
 
         scene.cast_ray_color(rng, &outgoing_ray, current_depth + 1)
     }
@@ -1,12 +1,13 @@
 use super::material::Material;
 use crate::{
-    common::{Real, EPSILON, NORMAL_BUMP},
-    geometries::{geometry::Geometry, ray::Ray},
+    common::{Real, EPSILON},
+    geometries::{geometry::Geometry, ray::Ray, surface_point::SurfacePoint},
     math::{color3::Color3, vector::Vector, vector3::Vector3},
     normalize,
     scene::Scene,
 };
 use rand::RngCore;
+use std::ptr;
 
 #[derive(Debug)]
 pub struct RefractiveMaterial {
@@ -28,9 +29,10 @@ impl Material for RefractiveMaterial {
         hit_geometry: &dyn Geometry,
         hit_position: &Vector3,
         hit_normal: &Vector3,
-        incoming_direction: &Vector3,
+        _surface_point: &SurfacePoint,
+        incoming_ray: &Ray,
     ) -> Color3 {
-        let refraction_direction = Vector3::refract(incoming_direction, hit_normal, 1.0, self.refraction_index);
+        let refraction_direction = Vector3::refract(incoming_ray.direction(), hit_normal, 1.0, self.refraction_index);
 
         if refraction_direction.length_squared() < EPSILON {
             return Color3::default();
@@ -38,27 +40,35 @@ impl Material for RefractiveMaterial {
 
         let refraction_direction_normalized = normalize!(refraction_direction);
 
-        let refraction_ray = Ray::new(hit_position, &refraction_direction_normalized);
-        let intersection_opt = hit_geometry.intersect(&refraction_ray);
+        let refraction_ray = Ray::new_from_surface(
+            hit_position,
+            hit_normal,
+            &refraction_direction_normalized,
+            incoming_ray.time(),
+        );
 
-        if intersection_opt.is_none() {
+        // Find where the ray leaves the geometry through the scene rather than the geometry itself, since the geometry
+        // may be placed by wrappers that move rays into its space, such as transformations and instances.
+        let Some(intersection) =
+            scene.intersect_filtered(&refraction_ray, &|x| ptr::addr_eq(x.hit_geometry, hit_geometry))
+        else {
             return Color3::default();
-        }
-
-        let intersection = intersection_opt.unwrap();
-        let mut exit_position = refraction_ray.position_along(intersection.exit_distance);
+        };
 
-        // Reverse the refraction direction so that the CalculateNormal method will see the ray
-        // as coming in towards the geometry rather than coming out of it.
-        let refraction_ray = Ray::new(refraction_ray.position(), &-refraction_ray.direction());
+        let exit_position = refraction_ray.position_along(intersection.entrance_distance);
+        let exit_surface_point = intersection
+            .surface_override
+            .unwrap_or_else(|| hit_geometry.calculate_surface_point(&refraction_ray, &exit_position, &intersection));
 
-        // Because we flipped the refraction direction, the normal should be pointing away
-        // from the geometry.
-        let exit_normal = hit_geometry.calculate_normal(&refraction_ray, hit_position);
-        exit_position += exit_normal * NORMAL_BUMP;
+        // Not every geometry faces its normals against the ray, so orient the normal the way the ray leaves the
+        // geometry.
+        let exit_normal = if (exit_surface_point.shading_normal ^ refraction_direction_normalized) < 0.0 {
+            -exit_surface_point.shading_normal
+        } else {
+            exit_surface_point.shading_normal
+        };
 
-        // Create the outgoing ray. Use the non reversed refraction direction and the reversed
-        // exit normal.
+        // Create the outgoing ray. Refract against the reversed exit normal, which points into the geometry.
         let outgoing_direction = Vector3::refract(&refraction_direction, &-exit_normal, self.refraction_index, 1.0);
 
         if outgoing_direction.length_squared() < EPSILON {
@@ -66,7 +76,8 @@ impl Material for RefractiveMaterial {
         }
 
         let outgoing_direction = normalize!(outgoing_direction);
-        let outgoing_ray = Ray::new(&exit_position, &outgoing_direction);
+        let outgoing_ray =
+            Ray::new_from_surface(&exit_position, &exit_normal, &outgoing_direction, incoming_ray.time());
 
         scene.cast_ray_color(rng, &outgoing_ray, current_depth + 1)
     }
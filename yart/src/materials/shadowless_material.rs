@@ -0,0 +1,67 @@
+use super::material::Material;
+use crate::{
+    common::Real,
+    geometries::{geometry::Geometry, ray::Ray, surface_point::SurfacePoint},
+    math::{color3::Color3, vector3::Vector3},
+    scene::Scene,
+    textures::normal_perturbation::NormalPerturbation,
+};
+use rand::RngCore;
+
+/// Renders exactly like the material it wraps, but doesn't cast shadows. Useful for fill geometry, such as light
+/// blockers seen only in reflections, and for speeding up scenes where some shadows don't matter.
+#[derive(Debug)]
+pub struct ShadowlessMaterial {
+    material: Box<dyn Material>,
+}
+
+impl ShadowlessMaterial {
+    pub fn new(material: Box<dyn Material>) -> Self {
+        Self { material }
+    }
+}
+
+impl Material for ShadowlessMaterial {
+    fn calculate_rendering_equation(
+        &self,
+        rng: &mut dyn RngCore,
+        scene: &Scene,
+        current_depth: u16,
+        hit_geometry: &dyn Geometry,
+        hit_position: &Vector3,
+        hit_normal: &Vector3,
+        surface_point: &SurfacePoint,
+        incoming_ray: &Ray,
+    ) -> Color3 {
+        self.material.calculate_rendering_equation(
+            rng,
+            scene,
+            current_depth,
+            hit_geometry,
+            hit_position,
+            hit_normal,
+            surface_point,
+            incoming_ray,
+        )
+    }
+
+    fn calculate_coverage(
+        &self,
+        rng: &mut dyn RngCore,
+        scene: &Scene,
+        hit_position: &Vector3,
+        hit_normal: &Vector3,
+        incoming_ray: &Ray,
+    ) -> Real {
+        self.material
+            .calculate_coverage(rng, scene, hit_position, hit_normal, incoming_ray)
+    }
+
+    fn casts_shadows(&self) -> bool {
+        false
+    }
+
+    fn normal_perturbation(&self) -> Option<&dyn NormalPerturbation> {
+        self.material.normal_perturbation()
+    }
+}
@@ -23,6 +23,20 @@ impl Color3 {
     pub fn from_vector3(vector3: &Vector3) -> Self {
         Self::new(vector3.x, vector3.y, vector3.z)
     }
+
+    /// Converts a color from the CIE XYZ color space to linear sRGB.
+    pub fn from_cie_xyz(x: Real, y: Real, z: Real) -> Self {
+        Self::new(
+            3.2406 * x - 1.5372 * y - 0.4986 * z,
+            -0.9689 * x + 1.8758 * y + 0.0415 * z,
+            0.0557 * x - 0.2040 * y + 1.0570 * z,
+        )
+    }
+
+    /// Calculates the relative luminance of a linear color using the Rec. 709 weights.
+    pub fn luminance(&self) -> Real {
+        0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+    }
 }
 
 impl Color for Color3 {
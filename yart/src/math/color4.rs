@@ -1,4 +1,4 @@
-use super::{color::Color, vector4::Vector4};
+use super::{color::Color, color3::Color3, vector4::Vector4};
 use crate::common::*;
 use approx::{AbsDiffEq, RelativeEq, UlpsEq};
 use impl_ops::*;
@@ -24,6 +24,15 @@ impl Color4 {
     pub fn from_vector4(vector4: &Vector4) -> Self {
         Self::new(vector4.x, vector4.y, vector4.z, vector4.w)
     }
+
+    pub fn from_color3(color3: &Color3, a: Real) -> Self {
+        Self::new(color3.r, color3.g, color3.b, a)
+    }
+
+    /// Returns the color channels with the alpha channel dropped.
+    pub fn rgb(&self) -> Color3 {
+        Color3::new(self.r, self.g, self.b)
+    }
 }
 
 impl Color for Color4 {
@@ -0,0 +1,155 @@
+use crate::common::Real;
+
+/// A piecewise-constant 1D distribution over the range `[0, 1)` built from a list of non-negative function values.
+/// Each function value covers an equally sized segment of the range.
+#[derive(Debug, Clone)]
+pub struct Distribution1D {
+    function: Vec<Real>,
+    cdf: Vec<Real>,
+    function_integral: Real,
+}
+
+impl Distribution1D {
+    /// Creates a new [`Distribution1D`] from a list of non-negative function values. If every value is zero the
+    /// distribution falls back to being uniform.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx::*;
+    /// # use yart::math::distribution::*;
+    /// #
+    /// let distribution = Distribution1D::new(&[1.0, 3.0]);
+    ///
+    /// assert_relative_eq!(2.0, distribution.function_integral());
+    /// assert_relative_eq!(1.5, distribution.pdf(0.75));
+    /// ```
+    pub fn new(function: &[Real]) -> Self {
+        let count = function.len();
+        let mut cdf = vec![0.0; count + 1];
+
+        for i in 1..=count {
+            cdf[i] = cdf[i - 1] + Real::abs(function[i - 1]) / count as Real;
+        }
+
+        let function_integral = cdf[count];
+
+        if function_integral == 0.0 {
+            for (i, value) in cdf.iter_mut().enumerate().skip(1) {
+                *value = i as Real / count as Real;
+            }
+        } else {
+            for value in cdf.iter_mut().skip(1) {
+                *value /= function_integral;
+            }
+        }
+
+        Self {
+            function: function.iter().map(|x| Real::abs(*x)).collect(),
+            cdf,
+            function_integral,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.function.len()
+    }
+
+    pub fn function_integral(&self) -> Real {
+        self.function_integral
+    }
+
+    /// Samples the distribution using a uniform random number in `[0, 1)`. Returns the sampled value in `[0, 1)`, the
+    /// value of the probability density function at that value, and the index of the segment that was sampled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx::*;
+    /// # use yart::math::distribution::*;
+    /// #
+    /// let distribution = Distribution1D::new(&[0.0, 1.0]);
+    /// let (value, pdf, index) = distribution.sample_continuous(0.5);
+    ///
+    /// assert_relative_eq!(0.75, value);
+    /// assert_relative_eq!(2.0, pdf);
+    /// assert_eq!(1, index);
+    /// ```
+    pub fn sample_continuous(&self, random: Real) -> (Real, Real, usize) {
+        let index = self.find_segment(random);
+
+        let mut offset = random - self.cdf[index];
+        let segment_width = self.cdf[index + 1] - self.cdf[index];
+
+        if segment_width > 0.0 {
+            offset /= segment_width;
+        }
+
+        let pdf = if self.function_integral > 0.0 {
+            self.function[index] / self.function_integral
+        } else {
+            1.0
+        };
+
+        let value = (index as Real + offset) / self.count() as Real;
+
+        (Real::min(value, 1.0 - Real::EPSILON), pdf, index)
+    }
+
+    /// Returns the value of the probability density function at a value in `[0, 1)`.
+    pub fn pdf(&self, value: Real) -> Real {
+        if self.function_integral == 0.0 {
+            return 1.0;
+        }
+
+        let index = usize::min((value * self.count() as Real) as usize, self.count() - 1);
+        self.function[index] / self.function_integral
+    }
+
+    fn find_segment(&self, random: Real) -> usize {
+        // Find the last cdf entry that is less than or equal to the random number.
+        let index = self.cdf.partition_point(|x| *x <= random);
+        usize::clamp(index, 1, self.count()) - 1
+    }
+}
+
+/// A piecewise-constant 2D distribution over `[0, 1)^2` built from a row major grid of non-negative function values.
+/// Sampling first picks a row from the marginal distribution and then a column from that row's conditional
+/// distribution.
+#[derive(Debug, Clone)]
+pub struct Distribution2D {
+    conditionals: Vec<Distribution1D>,
+    marginal: Distribution1D,
+}
+
+impl Distribution2D {
+    pub fn new(function: &[Real], width: usize, height: usize) -> Self {
+        let conditionals = function
+            .chunks(width)
+            .take(height)
+            .map(Distribution1D::new)
+            .collect::<Vec<_>>();
+
+        let marginal_function = conditionals.iter().map(|x| x.function_integral()).collect::<Vec<_>>();
+        let marginal = Distribution1D::new(&marginal_function);
+
+        Self { conditionals, marginal }
+    }
+
+    /// Samples the distribution using two uniform random numbers in `[0, 1)`. Returns the sampled `(u, v)` coordinate
+    /// and the value of the probability density function at that coordinate.
+    pub fn sample_continuous(&self, random1: Real, random2: Real) -> ((Real, Real), Real) {
+        let (v, marginal_pdf, row) = self.marginal.sample_continuous(random2);
+        let (u, conditional_pdf, _) = self.conditionals[row].sample_continuous(random1);
+
+        ((u, v), marginal_pdf * conditional_pdf)
+    }
+
+    /// Returns the value of the probability density function at the `(u, v)` coordinate.
+    pub fn pdf(&self, u: Real, v: Real) -> Real {
+        let rows = self.conditionals.len();
+        let row = usize::min((v * rows as Real) as usize, rows - 1);
+
+        self.marginal.pdf(v) * self.conditionals[row].pdf(u)
+    }
+}
@@ -0,0 +1,151 @@
+use super::vector3::Vector3;
+use crate::common::*;
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use impl_ops::*;
+use std::ops;
+
+/// A row major 3x3 matrix. Vectors are treated as columns and are multiplied on the right of the matrix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix3 {
+    pub m: [[Real; 3]; 3],
+}
+
+impl Default for Matrix3 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Matrix3 {
+    pub fn new(m: [[Real; 3]; 3]) -> Self {
+        Self { m }
+    }
+
+    pub fn identity() -> Self {
+        Self::from_diagonal(&Vector3::new(1.0, 1.0, 1.0))
+    }
+
+    pub fn from_diagonal(diagonal: &Vector3) -> Self {
+        Self::new([[diagonal.x, 0.0, 0.0], [0.0, diagonal.y, 0.0], [0.0, 0.0, diagonal.z]])
+    }
+
+    pub fn from_rows(row0: &Vector3, row1: &Vector3, row2: &Vector3) -> Self {
+        Self::new([
+            [row0.x, row0.y, row0.z],
+            [row1.x, row1.y, row1.z],
+            [row2.x, row2.y, row2.z],
+        ])
+    }
+
+    pub fn from_columns(column0: &Vector3, column1: &Vector3, column2: &Vector3) -> Self {
+        Self::from_rows(column0, column1, column2).transpose()
+    }
+
+    pub fn row(&self, index: usize) -> Vector3 {
+        Vector3::new(self.m[index][0], self.m[index][1], self.m[index][2])
+    }
+
+    pub fn column(&self, index: usize) -> Vector3 {
+        Vector3::new(self.m[0][index], self.m[1][index], self.m[2][index])
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_rows(&self.column(0), &self.column(1), &self.column(2))
+    }
+
+    pub fn determinant(&self) -> Real {
+        self.row(0) ^ (self.row(1) % self.row(2))
+    }
+
+    /// Calculates the inverse of the matrix. Returns `None` if the matrix is singular.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx::*;
+    /// # use yart::common::*;
+    /// # use yart::math::matrix3::*;
+    /// # use yart::math::vector3::*;
+    /// #
+    /// let matrix = Matrix3::new([[2.0, 0.0, 1.0], [0.0, 4.0, 0.0], [0.0, 0.0, 1.0]]);
+    ///
+    /// let result = matrix * matrix.inverse().unwrap();
+    ///
+    /// assert_relative_eq!(Matrix3::identity(), result, max_relative = EPSILON);
+    /// assert!(Matrix3::from_diagonal(&Vector3::new(1.0, 0.0, 1.0)).inverse().is_none());
+    /// ```
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+
+        if determinant == 0.0 {
+            return None;
+        }
+
+        // The columns of the inverse are the cross products of the rows divided by the determinant.
+        let row0 = self.row(0);
+        let row1 = self.row(1);
+        let row2 = self.row(2);
+
+        Some(Self::from_columns(&(row1 % row2), &(row2 % row0), &(row0 % row1)) * Real::recip(determinant))
+    }
+
+    /// Creates the matrix that transforms normals, which is the inverse transpose of this matrix. Returns `None` if
+    /// the matrix is singular.
+    pub fn normal_matrix(&self) -> Option<Self> {
+        Some(self.inverse()?.transpose())
+    }
+}
+
+// Matrix binary operators.
+impl_op_ex!(*|left: &Matrix3, right: &Matrix3| -> Matrix3 {
+    let mut m = [[0.0; 3]; 3];
+
+    for (row, values) in m.iter_mut().enumerate() {
+        for (column, value) in values.iter_mut().enumerate() {
+            *value = left.row(row) ^ right.column(column);
+        }
+    }
+
+    Matrix3::new(m)
+});
+
+impl_op_ex!(*|left: &Matrix3, right: &Vector3| -> Vector3 {
+    Vector3::new(left.row(0) ^ right, left.row(1) ^ right, left.row(2) ^ right)
+});
+
+// Scalar binary operators.
+impl_op_ex_commutative!(*|left: &Matrix3, right: &Real| -> Matrix3 {
+    Matrix3::new(left.m.map(|row| row.map(|value| value * right)))
+});
+
+impl AbsDiffEq for Matrix3 {
+    type Epsilon = Real;
+
+    fn default_epsilon() -> Real {
+        Real::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Real) -> bool {
+        (0..3).all(|i| Vector3::abs_diff_eq(&self.row(i), &other.row(i), epsilon))
+    }
+}
+
+impl RelativeEq for Matrix3 {
+    fn default_max_relative() -> Real {
+        Real::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: Real, max_relative: Real) -> bool {
+        (0..3).all(|i| Vector3::relative_eq(&self.row(i), &other.row(i), epsilon, max_relative))
+    }
+}
+
+impl UlpsEq for Matrix3 {
+    fn default_max_ulps() -> u32 {
+        Real::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Real, max_ulps: u32) -> bool {
+        (0..3).all(|i| Vector3::ulps_eq(&self.row(i), &other.row(i), epsilon, max_ulps))
+    }
+}
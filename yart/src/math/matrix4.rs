@@ -0,0 +1,229 @@
+use super::{matrix3::Matrix3, quaternion::Quaternion, vector3::Vector3, vector4::Vector4};
+use crate::common::*;
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use impl_ops::*;
+use std::ops;
+
+/// A row major 4x4 matrix used for affine transformations. Vectors are treated as columns and are multiplied on the
+/// right of the matrix, so `a * b` applies `b` first and then `a`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix4 {
+    pub m: [[Real; 4]; 4],
+}
+
+impl Default for Matrix4 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Matrix4 {
+    pub fn new(m: [[Real; 4]; 4]) -> Self {
+        Self { m }
+    }
+
+    pub fn identity() -> Self {
+        Self::from_matrix3(&Matrix3::identity())
+    }
+
+    /// Creates an affine transformation from a linear transformation without a translation.
+    pub fn from_matrix3(matrix: &Matrix3) -> Self {
+        let m = &matrix.m;
+
+        Self::new([
+            [m[0][0], m[0][1], m[0][2], 0.0],
+            [m[1][0], m[1][1], m[1][2], 0.0],
+            [m[2][0], m[2][1], m[2][2], 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn from_translation(translation: &Vector3) -> Self {
+        let mut matrix = Self::identity();
+
+        matrix.m[0][3] = translation.x;
+        matrix.m[1][3] = translation.y;
+        matrix.m[2][3] = translation.z;
+
+        matrix
+    }
+
+    pub fn from_scale(scale: &Vector3) -> Self {
+        Self::from_matrix3(&Matrix3::from_diagonal(scale))
+    }
+
+    pub fn from_rotation(rotation: &Quaternion) -> Self {
+        Self::from_matrix3(&rotation.to_matrix3())
+    }
+
+    /// Creates a transformation that scales, then rotates and then translates.
+    pub fn from_scale_rotation_translation(scale: &Vector3, rotation: &Quaternion, translation: &Vector3) -> Self {
+        Self::from_translation(translation) * Self::from_rotation(rotation) * Self::from_scale(scale)
+    }
+
+    pub fn row(&self, index: usize) -> Vector4 {
+        Vector4::new(self.m[index][0], self.m[index][1], self.m[index][2], self.m[index][3])
+    }
+
+    pub fn column(&self, index: usize) -> Vector4 {
+        Vector4::new(self.m[0][index], self.m[1][index], self.m[2][index], self.m[3][index])
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut m = [[0.0; 4]; 4];
+
+        for (row, values) in m.iter_mut().enumerate() {
+            for (column, value) in values.iter_mut().enumerate() {
+                *value = self.m[column][row];
+            }
+        }
+
+        Self::new(m)
+    }
+
+    /// The linear part of the transformation, without the translation.
+    pub fn upper_left(&self) -> Matrix3 {
+        Matrix3::new([
+            [self.m[0][0], self.m[0][1], self.m[0][2]],
+            [self.m[1][0], self.m[1][1], self.m[1][2]],
+            [self.m[2][0], self.m[2][1], self.m[2][2]],
+        ])
+    }
+
+    pub fn translation(&self) -> Vector3 {
+        Vector3::new(self.m[0][3], self.m[1][3], self.m[2][3])
+    }
+
+    pub fn determinant(&self) -> Real {
+        // Expand along the first row using the 3x3 minors.
+        (0..4)
+            .map(|column| {
+                let sign = if column % 2 == 0 { 1.0 } else { -1.0 };
+                sign * self.m[0][column] * self.minor(0, column).determinant()
+            })
+            .sum()
+    }
+
+    fn minor(&self, skipped_row: usize, skipped_column: usize) -> Matrix3 {
+        let mut m = [[0.0; 3]; 3];
+
+        for (row, values) in (0..4).filter(|x| *x != skipped_row).zip(m.iter_mut()) {
+            for (column, value) in (0..4).filter(|x| *x != skipped_column).zip(values.iter_mut()) {
+                *value = self.m[row][column];
+            }
+        }
+
+        Matrix3::new(m)
+    }
+
+    /// Calculates the inverse of the matrix. Returns `None` if the matrix is singular.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx::*;
+    /// # use yart::common::*;
+    /// # use yart::math::matrix4::*;
+    /// # use yart::math::quaternion::*;
+    /// # use yart::math::vector3::*;
+    /// #
+    /// let matrix = Matrix4::from_scale_rotation_translation(
+    ///     &Vector3::new(1.0, 2.0, 3.0),
+    ///     &Quaternion::from_euler_angles(&Vector3::new(0.3, 0.2, 0.1)),
+    ///     &Vector3::new(4.0, 5.0, 6.0),
+    /// );
+    ///
+    /// let result = matrix * matrix.inverse().unwrap();
+    ///
+    /// assert_relative_eq!(Matrix4::identity(), result, epsilon = EPSILON);
+    /// ```
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+
+        if determinant == 0.0 {
+            return None;
+        }
+
+        let recip_determinant = Real::recip(determinant);
+        let mut m = [[0.0; 4]; 4];
+
+        // The inverse is the transposed matrix of cofactors divided by the determinant.
+        for (row, values) in m.iter_mut().enumerate() {
+            for (column, value) in values.iter_mut().enumerate() {
+                let sign = if (row + column) % 2 == 0 { 1.0 } else { -1.0 };
+                *value = sign * self.minor(column, row).determinant() * recip_determinant;
+            }
+        }
+
+        Some(Self::new(m))
+    }
+
+    /// Creates the matrix that transforms normals, which is the inverse transpose of the linear part of the
+    /// transformation. Returns `None` if the matrix is singular.
+    pub fn normal_matrix(&self) -> Option<Matrix3> {
+        self.upper_left().normal_matrix()
+    }
+
+    pub fn transform_point(&self, point: &Vector3) -> Vector3 {
+        self.upper_left() * point + self.translation()
+    }
+
+    /// Transforms a direction, which unlike a point isn't affected by the translation.
+    pub fn transform_vector(&self, vector: &Vector3) -> Vector3 {
+        self.upper_left() * vector
+    }
+}
+
+// Matrix binary operators.
+impl_op_ex!(*|left: &Matrix4, right: &Matrix4| -> Matrix4 {
+    let mut m = [[0.0; 4]; 4];
+
+    for (row, values) in m.iter_mut().enumerate() {
+        for (column, value) in values.iter_mut().enumerate() {
+            *value = left.row(row) ^ right.column(column);
+        }
+    }
+
+    Matrix4::new(m)
+});
+
+impl_op_ex!(*|left: &Matrix4, right: &Vector4| -> Vector4 {
+    Vector4::new(
+        left.row(0) ^ right,
+        left.row(1) ^ right,
+        left.row(2) ^ right,
+        left.row(3) ^ right,
+    )
+});
+
+impl AbsDiffEq for Matrix4 {
+    type Epsilon = Real;
+
+    fn default_epsilon() -> Real {
+        Real::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Real) -> bool {
+        (0..4).all(|i| Vector4::abs_diff_eq(&self.row(i), &other.row(i), epsilon))
+    }
+}
+
+impl RelativeEq for Matrix4 {
+    fn default_max_relative() -> Real {
+        Real::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: Real, max_relative: Real) -> bool {
+        (0..4).all(|i| Vector4::relative_eq(&self.row(i), &other.row(i), epsilon, max_relative))
+    }
+}
+
+impl UlpsEq for Matrix4 {
+    fn default_max_ulps() -> u32 {
+        Real::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Real, max_ulps: u32) -> bool {
+        (0..4).all(|i| Vector4::ulps_eq(&self.row(i), &other.row(i), epsilon, max_ulps))
+    }
+}
@@ -1,6 +1,11 @@
 pub mod color;
 pub mod color3;
 pub mod color4;
+pub mod distribution;
+pub mod matrix3;
+pub mod matrix4;
+pub mod noise;
+pub mod quaternion;
 pub mod vector;
 pub mod vector2;
 pub mod vector3;
@@ -0,0 +1,198 @@
+use super::{vector::Vector, vector3::Vector3};
+use crate::common::Real;
+
+/// Scrambles the coordinates of a lattice point into a pseudo-random integer, so that no permutation table is needed.
+fn hash(x: i64, y: i64, z: i64) -> u32 {
+    let mut hash = (x as u32).wrapping_mul(0x8da6_b343)
+        ^ (y as u32).wrapping_mul(0xd816_3841)
+        ^ (z as u32).wrapping_mul(0xcb1a_b31f);
+
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x7feb_352d);
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(0x846c_a68b);
+    hash ^= hash >> 16;
+
+    hash
+}
+
+/// Turns a hash into a number in `[0, 1)`.
+fn hash_to_unit(hash: u32) -> Real {
+    (hash >> 8) as Real / (1u32 << 24) as Real
+}
+
+/// Dots the offset from a lattice point with one of the twelve gradients pointing at the edges of a cube, picked by
+/// the hash of the lattice point.
+fn gradient(hash: u32, x: Real, y: Real, z: Real) -> Real {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+fn fade(t: Real) -> Real {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: Real, a: Real, b: Real) -> Real {
+    a + t * (b - a)
+}
+
+/// Calculates Ken Perlin's improved gradient noise, which varies smoothly between roughly `-1` and `1` and is zero at
+/// every point with integer coordinates.
+///
+/// # Examples
+///
+/// ```
+/// # use yart::math::{noise::*, vector3::Vector3};
+/// #
+/// assert_eq!(0.0, perlin_noise(&Vector3::new(1.0, -2.0, 3.0)));
+/// assert!(perlin_noise(&Vector3::new(1.5, -2.5, 3.5)).abs() <= 1.0);
+/// ```
+pub fn perlin_noise(point: &Vector3) -> Real {
+    let cell = [point.x.floor(), point.y.floor(), point.z.floor()];
+    let (x, y, z) = (point.x - cell[0], point.y - cell[1], point.z - cell[2]);
+    let (i, j, k) = (cell[0] as i64, cell[1] as i64, cell[2] as i64);
+
+    let (u, v, w) = (fade(x), fade(y), fade(z));
+
+    lerp(
+        w,
+        lerp(
+            v,
+            lerp(
+                u,
+                gradient(hash(i, j, k), x, y, z),
+                gradient(hash(i + 1, j, k), x - 1.0, y, z),
+            ),
+            lerp(
+                u,
+                gradient(hash(i, j + 1, k), x, y - 1.0, z),
+                gradient(hash(i + 1, j + 1, k), x - 1.0, y - 1.0, z),
+            ),
+        ),
+        lerp(
+            v,
+            lerp(
+                u,
+                gradient(hash(i, j, k + 1), x, y, z - 1.0),
+                gradient(hash(i + 1, j, k + 1), x - 1.0, y, z - 1.0),
+            ),
+            lerp(
+                u,
+                gradient(hash(i, j + 1, k + 1), x, y - 1.0, z - 1.0),
+                gradient(hash(i + 1, j + 1, k + 1), x - 1.0, y - 1.0, z - 1.0),
+            ),
+        ),
+    )
+}
+
+/// How the octaves of [`fbm`] and [`turbulence`] are layered.
+#[derive(Debug, Copy, Clone)]
+pub struct NoiseOctaves {
+    /// The number of layers of noise that are added up.
+    pub count: u32,
+
+    /// How much the frequency grows from one octave to the next.
+    pub lacunarity: Real,
+
+    /// How much the amplitude shrinks from one octave to the next.
+    pub gain: Real,
+}
+
+impl Default for NoiseOctaves {
+    fn default() -> Self {
+        Self {
+            count: 6,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+}
+
+impl NoiseOctaves {
+    fn sum(&self, point: &Vector3, octave: impl Fn(Real) -> Real) -> Real {
+        let mut sum = 0.0;
+        let mut frequency = 1.0;
+        let mut amplitude = 1.0;
+
+        for _ in 0..self.count {
+            sum += amplitude * octave(perlin_noise(&(point * frequency)));
+
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+
+        sum
+    }
+}
+
+/// Adds up octaves of [`perlin_noise`] at growing frequencies and shrinking amplitudes, which gives the fractal
+/// detail of clouds and rock.
+pub fn fbm(point: &Vector3, octaves: &NoiseOctaves) -> Real {
+    octaves.sum(point, |x| x)
+}
+
+/// Like [`fbm`] but adds up the absolute value of every octave, which gives sharp creases where the noise crosses
+/// zero. The result is never negative.
+pub fn turbulence(point: &Vector3, octaves: &NoiseOctaves) -> Real {
+    octaves.sum(point, Real::abs)
+}
+
+/// The distances from a point to the closest feature points of [`worley_noise`].
+#[derive(Debug, Copy, Clone)]
+pub struct WorleyDistances {
+    /// The distance to the closest feature point.
+    pub closest: Real,
+
+    /// The distance to the second closest feature point.
+    pub second_closest: Real,
+
+    /// A random number in `[0, 1)` that is the same everywhere in the cell of the closest feature point.
+    pub cell_value: Real,
+}
+
+/// Calculates Steven Worley's cellular noise, which scatters one feature point in every unit cube and measures the
+/// distances to the closest of them.
+pub fn worley_noise(point: &Vector3) -> WorleyDistances {
+    let cell = [point.x.floor() as i64, point.y.floor() as i64, point.z.floor() as i64];
+
+    let mut distances = WorleyDistances {
+        closest: Real::INFINITY,
+        second_closest: Real::INFINITY,
+        cell_value: 0.0,
+    };
+
+    for i in cell[0] - 1..=cell[0] + 1 {
+        for j in cell[1] - 1..=cell[1] + 1 {
+            for k in cell[2] - 1..=cell[2] + 1 {
+                let cell_hash = hash(i, j, k);
+
+                let feature_point = Vector3::new(
+                    i as Real + hash_to_unit(cell_hash),
+                    j as Real + hash_to_unit(hash(k, i, cell_hash as i64)),
+                    k as Real + hash_to_unit(hash(cell_hash as i64, k, j)),
+                );
+
+                let distance = (feature_point - point).length();
+
+                if distance < distances.closest {
+                    distances.second_closest = distances.closest;
+                    distances.closest = distance;
+                    distances.cell_value = hash_to_unit(hash(cell_hash as i64, i, j ^ k));
+                } else if distance < distances.second_closest {
+                    distances.second_closest = distance;
+                }
+            }
+        }
+    }
+
+    distances
+}
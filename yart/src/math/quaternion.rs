@@ -0,0 +1,161 @@
+use super::{matrix3::Matrix3, vector3::Vector3};
+use crate::{common::*, normalize};
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use impl_ops::*;
+use std::ops;
+
+/// A rotation stored as a unit quaternion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion {
+    pub x: Real,
+    pub y: Real,
+    pub z: Real,
+    pub w: Real,
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Quaternion {
+    pub fn new(x: Real, y: Real, z: Real, w: Real) -> Self {
+        Self { x, y, z, w }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(0.0, 0.0, 0.0, 1.0)
+    }
+
+    /// Creates a rotation of `angle` radians around an axis.
+    pub fn from_axis_angle(axis: &Vector3, angle: Real) -> Self {
+        let axis = normalize!(*axis);
+        let (sin, cos) = Real::sin_cos(angle * 0.5);
+
+        Self::new(axis.x * sin, axis.y * sin, axis.z * sin, cos)
+    }
+
+    /// Creates a rotation from angles in radians around the x, y and z axes. The rotation around the x axis is applied
+    /// first and the rotation around the z axis last.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx::*;
+    /// # use yart::common::*;
+    /// # use yart::math::quaternion::*;
+    /// # use yart::math::vector3::*;
+    /// #
+    /// let rotation = Quaternion::from_euler_angles(&Vector3::new(0.0, Real::to_radians(90.0), 0.0));
+    ///
+    /// let result = rotation.rotate(&Vector3::new(1.0, 0.0, 0.0));
+    ///
+    /// assert_relative_eq!(Vector3::new(0.0, 0.0, -1.0), result, epsilon = EPSILON);
+    /// ```
+    pub fn from_euler_angles(angles: &Vector3) -> Self {
+        let x = Self::from_axis_angle(&Vector3::new(1.0, 0.0, 0.0), angles.x);
+        let y = Self::from_axis_angle(&Vector3::new(0.0, 1.0, 0.0), angles.y);
+        let z = Self::from_axis_angle(&Vector3::new(0.0, 0.0, 1.0), angles.z);
+
+        z * y * x
+    }
+
+    pub fn conjugate(&self) -> Self {
+        Self::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    pub fn normalize(&self) -> Self {
+        let recip_length = Real::recip(Real::sqrt(
+            self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w,
+        ));
+
+        Self::new(
+            self.x * recip_length,
+            self.y * recip_length,
+            self.z * recip_length,
+            self.w * recip_length,
+        )
+    }
+
+    /// Rotates a vector by the quaternion.
+    pub fn rotate(&self, vector: &Vector3) -> Vector3 {
+        let axis = Vector3::new(self.x, self.y, self.z);
+        let t = 2.0 * (axis % vector);
+
+        vector + self.w * t + axis % t
+    }
+
+    pub fn to_matrix3(&self) -> Matrix3 {
+        let Self { x, y, z, w } = *self;
+
+        Matrix3::new([
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - z * w),
+                2.0 * (x * z + y * w),
+            ],
+            [
+                2.0 * (x * y + z * w),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - x * w),
+            ],
+            [
+                2.0 * (x * z - y * w),
+                2.0 * (y * z + x * w),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        ])
+    }
+}
+
+// Quaternion binary operators.
+impl_op_ex!(*|left: &Quaternion, right: &Quaternion| -> Quaternion {
+    Quaternion::new(
+        left.w * right.x + left.x * right.w + left.y * right.z - left.z * right.y,
+        left.w * right.y - left.x * right.z + left.y * right.w + left.z * right.x,
+        left.w * right.z + left.x * right.y - left.y * right.x + left.z * right.w,
+        left.w * right.w - left.x * right.x - left.y * right.y - left.z * right.z,
+    )
+});
+
+impl AbsDiffEq for Quaternion {
+    type Epsilon = Real;
+
+    fn default_epsilon() -> Real {
+        Real::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Real) -> bool {
+        Real::abs_diff_eq(&self.x, &other.x, epsilon)
+            && Real::abs_diff_eq(&self.y, &other.y, epsilon)
+            && Real::abs_diff_eq(&self.z, &other.z, epsilon)
+            && Real::abs_diff_eq(&self.w, &other.w, epsilon)
+    }
+}
+
+impl RelativeEq for Quaternion {
+    fn default_max_relative() -> Real {
+        Real::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: Real, max_relative: Real) -> bool {
+        Real::relative_eq(&self.x, &other.x, epsilon, max_relative)
+            && Real::relative_eq(&self.y, &other.y, epsilon, max_relative)
+            && Real::relative_eq(&self.z, &other.z, epsilon, max_relative)
+            && Real::relative_eq(&self.w, &other.w, epsilon, max_relative)
+    }
+}
+
+impl UlpsEq for Quaternion {
+    fn default_max_ulps() -> u32 {
+        Real::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Real, max_ulps: u32) -> bool {
+        Real::ulps_eq(&self.x, &other.x, epsilon, max_ulps)
+            && Real::ulps_eq(&self.y, &other.y, epsilon, max_ulps)
+            && Real::ulps_eq(&self.z, &other.z, epsilon, max_ulps)
+            && Real::ulps_eq(&self.w, &other.w, epsilon, max_ulps)
+    }
+}
@@ -28,7 +28,7 @@ impl Vector3 {
     /// assert_eq!(3.0, result.y);
     /// assert_eq!(4.0, result.z);
     /// ```
-    pub fn new(x: Real, y: Real, z: Real) -> Self {
+    pub const fn new(x: Real, y: Real, z: Real) -> Self {
         Self { x, y, z }
     }
 
@@ -112,17 +112,18 @@ impl Vector3 {
         )
     }
 
+    #[allow(clippy::eq_op)]
     pub fn project_onto(&self, vector_to_project_onto: &Self) -> Self {
         vector_to_project_onto * ((self ^ vector_to_project_onto) / (vector_to_project_onto ^ vector_to_project_onto))
     }
 
-    /// Returns a new vector that is an outgoing reflection of itself around the normal.
+    /// Returns a new vector that is the reflection of an incoming direction around the normal.
     ///
     /// ```text
-    /// ^    ^    ^
+    /// \    ^    ^
     ///  \   |   /
     ///   \  |  /
-    ///    \ | /
+    ///    v | /
     ///    s n r
     /// s = self
     /// n = normal
@@ -137,7 +138,7 @@ impl Vector3 {
     /// # use yart::math::vector::*;
     /// # use yart::math::vector3::*;
     /// #
-    /// let target = Vector3::new(-0.707, 0.707, 0.0);
+    /// let target = Vector3::new(0.707, -0.707, 0.0);
     /// let normal = Vector3::new(0.0, 1.0, 0.0);
     ///
     /// let result = target.reflect(&normal);
@@ -0,0 +1,235 @@
+use super::miss_shader::MissShader;
+use crate::{
+    common::{Real, PI},
+    geometries::ray::Ray,
+    lights::sun_light::SunLight,
+    math::{color::Color, color3::Color3, vector::Vector, vector3::Vector3},
+    normalize,
+};
+
+/// The wavelengths, in meters, used for the red, green and blue channels when calculating Rayleigh scattering.
+const WAVELENGTHS: [Real; 3] = [680.0e-9, 550.0e-9, 440.0e-9];
+
+/// The scattering cross section, in square meters, of a single aerosol particle.
+const AEROSOL_SCATTERING_CROSS_SECTION: Real = 1.0e-5;
+
+/// The ratio of Mie extinction to Mie scattering. Aerosols absorb a small amount of the light that hits them.
+const MIE_EXTINCTION_RATIO: Real = 1.1;
+
+/// The number of samples taken along each ray towards the sun when calculating the optical depth of the light.
+const LIGHT_SAMPLE_COUNT: u32 = 8;
+
+/// A miss shader that calculates the color of the sky by integrating single Rayleigh and Mie scattering of sun light
+/// through a spherical planet's atmosphere. All distances are in meters.
+#[derive(Debug, Clone)]
+pub struct AtmosphereMissShader {
+    /// The offset added to the position of every ray to move it into the planet's coordinate system. The center of the
+    /// planet is at the origin.
+    pub offset: Vector3,
+
+    pub sun: SunLight,
+    pub sun_intensity: Color3,
+
+    /// The number of samples taken along each view ray.
+    pub sample_count: u32,
+
+    pub planet_radius: Real,
+    pub atmosphere_radius: Real,
+
+    pub rayleigh_scale_height: Real,
+    pub mie_scale_height: Real,
+
+    rayleigh_scattering: Color3,
+    mie_scattering: Real,
+    mie_asymmetry: Real,
+}
+
+impl AtmosphereMissShader {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        offset: &Vector3,
+        sun: &SunLight,
+        sun_intensity: &Color3,
+        sample_count: u32,
+        planet_radius: Real,
+        atmosphere_height: Real,
+        rayleigh_scale_height: Real,
+        mie_scale_height: Real,
+        index_of_refraction_at_sea_level: Real,
+        number_density_of_air_at_sea_level: Real,
+        number_density_of_aerosols_at_sea_level: Real,
+        mie_u: Real,
+    ) -> Self {
+        let n2_minus_one = index_of_refraction_at_sea_level * index_of_refraction_at_sea_level - 1.0;
+
+        let rayleigh_scattering_for_wavelength = |wavelength: Real| {
+            8.0 * PI * PI * PI * n2_minus_one * n2_minus_one
+                / (3.0 * number_density_of_air_at_sea_level * wavelength.powi(4))
+        };
+
+        Self {
+            offset: *offset,
+            sun: *sun,
+            sun_intensity: *sun_intensity,
+            sample_count: u32::max(1, sample_count),
+            planet_radius,
+            atmosphere_radius: planet_radius + atmosphere_height,
+            rayleigh_scale_height,
+            mie_scale_height,
+            rayleigh_scattering: Color3::new(
+                rayleigh_scattering_for_wavelength(WAVELENGTHS[0]),
+                rayleigh_scattering_for_wavelength(WAVELENGTHS[1]),
+                rayleigh_scattering_for_wavelength(WAVELENGTHS[2]),
+            ),
+            mie_scattering: number_density_of_aerosols_at_sea_level * AEROSOL_SCATTERING_CROSS_SECTION,
+            mie_asymmetry: Self::calculate_mie_asymmetry(mie_u),
+        }
+    }
+
+    /// Converts Nishita's Mie `u` parameter, which describes the atmospheric condition, into the asymmetry factor of
+    /// the Cornette-Shanks phase function.
+    fn calculate_mie_asymmetry(u: Real) -> Real {
+        let x = (5.0 / 9.0) * u
+            + (125.0 / 729.0) * u * u * u
+            + Real::sqrt((64.0 / 27.0) - (325.0 / 243.0) * u * u + (1250.0 / 2187.0) * u * u * u * u);
+
+        (5.0 / 9.0) * u - ((4.0 / 3.0) - (25.0 / 81.0) * u * u) * Real::cbrt(x).recip() + Real::cbrt(x)
+    }
+
+    fn rayleigh_phase(cos_theta: Real) -> Real {
+        (3.0 / (16.0 * PI)) * (1.0 + cos_theta * cos_theta)
+    }
+
+    fn mie_phase(&self, cos_theta: Real) -> Real {
+        let g = self.mie_asymmetry;
+        let g2 = g * g;
+
+        (3.0 / (8.0 * PI)) * ((1.0 - g2) * (1.0 + cos_theta * cos_theta))
+            / ((2.0 + g2) * Real::powf(1.0 + g2 - 2.0 * g * cos_theta, 1.5))
+    }
+
+    fn calculate_transmittance(&self, rayleigh_optical_depth: Real, mie_optical_depth: Real) -> Color3 {
+        let extinction = self.rayleigh_scattering * rayleigh_optical_depth
+            + Color3::from_value(self.mie_scattering * MIE_EXTINCTION_RATIO * mie_optical_depth);
+
+        Color3::exp(&-extinction)
+    }
+
+    /// Calculates the Rayleigh and Mie optical depths from a point in the atmosphere towards the sun. Returns `None`
+    /// if the planet blocks the sun.
+    fn calculate_sun_optical_depth(&self, position: &Vector3) -> Option<(Real, Real)> {
+        let direction_to_sun = self.sun.direction_to_sun();
+
+        if intersect_sphere(position, &direction_to_sun, self.planet_radius).is_some_and(|(_, exit)| exit > 0.0) {
+            return None;
+        }
+
+        let (_, exit_distance) = intersect_sphere(position, &direction_to_sun, self.atmosphere_radius)?;
+        let segment_length = exit_distance / LIGHT_SAMPLE_COUNT as Real;
+
+        let mut rayleigh_optical_depth = 0.0;
+        let mut mie_optical_depth = 0.0;
+
+        for i in 0..LIGHT_SAMPLE_COUNT {
+            let sample_position = position + direction_to_sun * ((i as Real + 0.5) * segment_length);
+            let height = Real::max(0.0, sample_position.length() - self.planet_radius);
+
+            rayleigh_optical_depth += Real::exp(-height / self.rayleigh_scale_height) * segment_length;
+            mie_optical_depth += Real::exp(-height / self.mie_scale_height) * segment_length;
+        }
+
+        Some((rayleigh_optical_depth, mie_optical_depth))
+    }
+}
+
+impl MissShader for AtmosphereMissShader {
+    fn calculate_color(&self, ray: &Ray) -> Color3 {
+        let position = ray.position() + self.offset;
+        let direction = normalize!(*ray.direction());
+
+        let Some((entrance_distance, exit_distance)) = intersect_sphere(&position, &direction, self.atmosphere_radius)
+        else {
+            return Color3::default();
+        };
+
+        if exit_distance < 0.0 {
+            return Color3::default();
+        }
+
+        let start_distance = Real::max(0.0, entrance_distance);
+        let mut end_distance = exit_distance;
+
+        // Stop integrating where the ray hits the ground.
+        let mut hit_ground = false;
+
+        if let Some((ground_distance, _)) = intersect_sphere(&position, &direction, self.planet_radius) {
+            if ground_distance > 0.0 {
+                end_distance = Real::min(end_distance, ground_distance);
+                hit_ground = true;
+            }
+        }
+
+        let segment_length = (end_distance - start_distance) / self.sample_count as Real;
+
+        let mut rayleigh_optical_depth = 0.0;
+        let mut mie_optical_depth = 0.0;
+
+        let mut rayleigh_sum = Color3::default();
+        let mut mie_sum = Color3::default();
+
+        for i in 0..self.sample_count {
+            let sample_position = position + direction * (start_distance + (i as Real + 0.5) * segment_length);
+            let height = Real::max(0.0, sample_position.length() - self.planet_radius);
+
+            let rayleigh_density = Real::exp(-height / self.rayleigh_scale_height) * segment_length;
+            let mie_density = Real::exp(-height / self.mie_scale_height) * segment_length;
+
+            rayleigh_optical_depth += rayleigh_density;
+            mie_optical_depth += mie_density;
+
+            let Some((sun_rayleigh_optical_depth, sun_mie_optical_depth)) =
+                self.calculate_sun_optical_depth(&sample_position)
+            else {
+                continue;
+            };
+
+            let transmittance = self.calculate_transmittance(
+                rayleigh_optical_depth + sun_rayleigh_optical_depth,
+                mie_optical_depth + sun_mie_optical_depth,
+            );
+
+            rayleigh_sum += transmittance * rayleigh_density;
+            mie_sum += transmittance * mie_density;
+        }
+
+        let cos_theta = direction ^ self.sun.direction_to_sun();
+
+        let mut color = (rayleigh_sum * self.rayleigh_scattering * Self::rayleigh_phase(cos_theta)
+            + mie_sum * self.mie_scattering * self.mie_phase(cos_theta))
+            * self.sun_intensity;
+
+        if !hit_ground && self.sun.contains_direction(&direction) {
+            color += self.sun.calculate_disk_radiance(&self.sun_intensity)
+                * self.calculate_transmittance(rayleigh_optical_depth, mie_optical_depth);
+        }
+
+        color
+    }
+}
+
+/// Intersects a ray with a sphere centered at the origin. Returns the entrance and exit distances which may be
+/// negative when the sphere is behind the ray.
+#[allow(clippy::eq_op)]
+fn intersect_sphere(position: &Vector3, direction: &Vector3, radius: Real) -> Option<(Real, Real)> {
+    let b = position ^ direction;
+    let c = (position ^ position) - radius * radius;
+
+    let discriminant = b * b - c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let discriminant_sqrt = Real::sqrt(discriminant);
+
+    Some((-b - discriminant_sqrt, -b + discriminant_sqrt))
+}
@@ -0,0 +1,51 @@
+use super::miss_shader::MissShader;
+use crate::{
+    common::{Real, ONE_OVER_PI, ONE_OVER_TWO_PI},
+    geometries::ray::Ray,
+    math::{color3::Color3, vector3::Vector3},
+    normalize,
+};
+
+/// Converts a ray direction into latitude-longitude coordinates in `[0, 1)`. The `u` coordinate wraps around the `y`
+/// axis and the `v` coordinate goes from the `+y` axis to the `-y` axis.
+fn direction_to_uv(direction: &Vector3) -> (Real, Real) {
+    let u = (Real::atan2(direction.z, direction.x) * ONE_OVER_TWO_PI).rem_euclid(1.0);
+    let v = Real::acos(Real::clamp(direction.y, -1.0, 1.0)) * ONE_OVER_PI;
+
+    (u, v)
+}
+
+/// A miss shader that wraps a checkerboard around the scene using latitude-longitude coordinates.
+#[derive(Debug)]
+pub struct CheckerMissShader {
+    pub color1: Color3,
+    pub color2: Color3,
+
+    /// The number of checks around the horizon and from the top to the bottom of the sphere.
+    pub divisions: (u32, u32),
+}
+
+impl CheckerMissShader {
+    pub fn new(color1: &Color3, color2: &Color3, divisions: (u32, u32)) -> Self {
+        Self {
+            color1: *color1,
+            color2: *color2,
+            divisions,
+        }
+    }
+}
+
+impl MissShader for CheckerMissShader {
+    fn calculate_color(&self, ray: &Ray) -> Color3 {
+        let (u, v) = direction_to_uv(&normalize!(*ray.direction()));
+
+        let check_x = (u * self.divisions.0 as Real) as u32;
+        let check_y = (v * self.divisions.1 as Real) as u32;
+
+        if (check_x + check_y).is_multiple_of(2) {
+            self.color1
+        } else {
+            self.color2
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use super::miss_shader::MissShader;
+use crate::{
+    common::{Real, ONE_OVER_PI, ONE_OVER_TWO_PI, PI, TWO_PI},
+    geometries::{area_light::AreaLight, ray::Ray},
+    math::{color3::Color3, distribution::Distribution2D, vector3::Vector3},
+    normalize,
+    scene::Scene,
+};
+use rand::{Rng, RngCore};
+use std::{path::Path, sync::Arc};
+
+/// The distance at which points on the environment are placed when the environment is treated as an area light.
+const ENVIRONMENT_DISTANCE: Real = 1.0e8;
+
+/// A miss shader that looks up the color of a missed ray in an equirectangular (latitude-longitude) image. The top row
+/// of the image maps to the `+y` axis and the bottom row to the `-y` axis.
+///
+/// The environment map can also be used as an [`AreaLight`] in which case directions are importance sampled
+/// according to the luminance of the image.
+#[derive(Debug, Clone)]
+pub struct EnvironmentMapMissShader {
+    width: usize,
+    height: usize,
+    pixels: Arc<Vec<Color3>>,
+    distribution: Arc<Distribution2D>,
+
+    /// The rotation of the environment around the `y` axis in radians.
+    pub rotation: Real,
+
+    /// A multiplier applied to every color looked up in the environment map.
+    pub intensity: Real,
+}
+
+impl EnvironmentMapMissShader {
+    pub fn new(width: usize, height: usize, pixels: Vec<Color3>, rotation: Real, intensity: Real) -> Self {
+        // Weight each pixel by the sine of its polar angle so that the stretched rows near the poles aren't sampled
+        // more than they contribute.
+        let mut function = Vec::with_capacity(width * height);
+
+        for y in 0..height {
+            let sin_theta = Real::sin(PI * (y as Real + 0.5) / height as Real);
+
+            for x in 0..width {
+                function.push(pixels[y * width + x].luminance() * sin_theta);
+            }
+        }
+
+        let distribution = Distribution2D::new(&function, width, height);
+
+        Self {
+            width,
+            height,
+            pixels: Arc::new(pixels),
+            distribution: Arc::new(distribution),
+            rotation,
+            intensity,
+        }
+    }
+
+    /// Loads an equirectangular image from disk. Any format supported by the `image` crate can be used although
+    /// high dynamic range formats such as `.hdr` and `.exr` are expected.
+    pub fn from_file(path: &Path, rotation: Real, intensity: Real) -> Option<Self> {
+        let image = image::open(path).ok()?.into_rgb32f();
+
+        let width = image.width() as usize;
+        let height = image.height() as usize;
+
+        let pixels = image
+            .pixels()
+            .map(|pixel| Color3::new(pixel[0] as Real, pixel[1] as Real, pixel[2] as Real))
+            .collect::<Vec<_>>();
+
+        Some(Self::new(width, height, pixels, rotation, intensity))
+    }
+
+    fn direction_to_uv(&self, direction: &Vector3) -> (Real, Real) {
+        let theta = Real::acos(Real::clamp(direction.y, -1.0, 1.0));
+        let phi = Real::atan2(direction.z, direction.x) + self.rotation;
+
+        let u = (phi * ONE_OVER_TWO_PI).rem_euclid(1.0);
+        let v = theta * ONE_OVER_PI;
+
+        (u, v)
+    }
+
+    fn uv_to_direction(&self, u: Real, v: Real) -> Vector3 {
+        let theta = v * PI;
+        let phi = u * TWO_PI - self.rotation;
+
+        let sin_theta = Real::sin(theta);
+
+        Vector3::new(sin_theta * Real::cos(phi), Real::cos(theta), sin_theta * Real::sin(phi))
+    }
+
+    fn lookup(&self, u: Real, v: Real) -> Color3 {
+        let x = usize::min((u * self.width as Real) as usize, self.width - 1);
+        let y = usize::min((v * self.height as Real) as usize, self.height - 1);
+
+        self.pixels[y * self.width + x] * self.intensity
+    }
+
+    fn sample_direction(&self, rng: &mut dyn RngCore) -> Vector3 {
+        let ((u, v), _) = self
+            .distribution
+            .sample_continuous(rng.gen::<Real>(), rng.gen::<Real>());
+        self.uv_to_direction(u, v)
+    }
+
+    /// Calculates the probability density, with respect to solid angle, of sampling the given direction.
+    fn calculate_pdf(&self, direction: &Vector3) -> Real {
+        let (u, v) = self.direction_to_uv(direction);
+        let sin_theta = Real::sin(v * PI);
+
+        if sin_theta <= 0.0 {
+            return 0.0;
+        }
+
+        self.distribution.pdf(u, v) / (2.0 * PI * PI * sin_theta)
+    }
+}
+
+impl MissShader for EnvironmentMapMissShader {
+    fn calculate_color(&self, ray: &Ray) -> Color3 {
+        let (u, v) = self.direction_to_uv(&normalize!(*ray.direction()));
+        self.lookup(u, v)
+    }
+}
+
+impl AreaLight for EnvironmentMapMissShader {
+    fn get_direction_towards_light(
+        &self,
+        rng: &mut dyn RngCore,
+        _hit_position: &Vector3,
+        _hit_normal: &Vector3,
+    ) -> Vector3 {
+        self.sample_direction(rng)
+    }
+
+    fn get_point_on_light(&self, rng: &mut dyn RngCore, hit_position: &Vector3, _hit_normal: &Vector3) -> Vector3 {
+        hit_position + self.sample_direction(rng) * ENVIRONMENT_DISTANCE
+    }
+
+    fn is_in_shadow(
+        &self,
+        _rng: &mut dyn RngCore,
+        scene: &Scene,
+        hit_position: &Vector3,
+        hit_normal: &Vector3,
+        point_on_light: &Vector3,
+        time: Real,
+    ) -> bool {
+        let direction_to_light = normalize!(point_on_light - hit_position);
+        let ray = Ray::new_from_surface(hit_position, hit_normal, &direction_to_light, time);
+
+        scene.occluded(&ray, Real::INFINITY)
+    }
+
+    fn calculate_inverse_pdf(
+        &self,
+        _rng: &mut dyn RngCore,
+        _hit_position: &Vector3,
+        _hit_normal: &Vector3,
+        _incoming_direction: &Vector3,
+        outgoing_direction: &Vector3,
+    ) -> Real {
+        let pdf = self.calculate_pdf(outgoing_direction);
+
+        if pdf > 0.0 {
+            pdf.recip()
+        } else {
+            0.0
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use super::miss_shader::MissShader;
+use crate::{common::Real, geometries::ray::Ray, math::color3::Color3, normalize};
+
+/// A miss shader that blends vertically from the horizon color to the zenith color above the horizon and from the
+/// horizon color to the ground color below it.
+#[derive(Debug)]
+pub struct GradientMissShader {
+    pub horizon_color: Color3,
+    pub zenith_color: Color3,
+    pub ground_color: Color3,
+
+    /// Shapes the blend. Values above `1` keep the horizon color over a larger part of the sky.
+    pub exponent: Real,
+}
+
+impl GradientMissShader {
+    pub fn new(horizon_color: &Color3, zenith_color: &Color3, ground_color: &Color3, exponent: Real) -> Self {
+        Self {
+            horizon_color: *horizon_color,
+            zenith_color: *zenith_color,
+            ground_color: *ground_color,
+            exponent,
+        }
+    }
+}
+
+impl MissShader for GradientMissShader {
+    fn calculate_color(&self, ray: &Ray) -> Color3 {
+        let direction = normalize!(*ray.direction());
+
+        let (amount, target_color) = if direction.y >= 0.0 {
+            (direction.y, self.zenith_color)
+        } else {
+            (-direction.y, self.ground_color)
+        };
+
+        let amount = Real::powf(Real::min(amount, 1.0), self.exponent);
+
+        self.horizon_color * (1.0 - amount) + target_color * amount
+    }
+}
@@ -0,0 +1,76 @@
+use super::miss_shader::MissShader;
+use crate::{common::Real, geometries::ray::Ray, math::color3::Color3, normalize};
+
+/// A miss shader that draws latitude and longitude lines around the scene. The horizon and the line through `+x` are
+/// drawn with the axis color to help with orientation.
+#[derive(Debug)]
+pub struct GridMissShader {
+    pub background_color: Color3,
+    pub line_color: Color3,
+    pub axis_color: Color3,
+
+    /// The angle between grid lines in radians.
+    pub spacing: Real,
+
+    /// The angular width of the grid lines in radians.
+    pub line_width: Real,
+}
+
+impl GridMissShader {
+    pub fn new(
+        background_color: &Color3,
+        line_color: &Color3,
+        axis_color: &Color3,
+        spacing: Real,
+        line_width: Real,
+    ) -> Self {
+        Self {
+            background_color: *background_color,
+            line_color: *line_color,
+            axis_color: *axis_color,
+            spacing,
+            line_width,
+        }
+    }
+
+    /// Returns the angular distance from an angle to the closest grid line along with the index of that line.
+    fn distance_to_line(&self, angle: Real) -> (Real, i64) {
+        let line_index = Real::round(angle / self.spacing);
+        let distance = Real::abs(angle - line_index * self.spacing);
+
+        (distance, line_index as i64)
+    }
+}
+
+impl MissShader for GridMissShader {
+    fn calculate_color(&self, ray: &Ray) -> Color3 {
+        let direction = normalize!(*ray.direction());
+
+        let latitude = Real::asin(Real::clamp(direction.y, -1.0, 1.0));
+        let longitude = Real::atan2(direction.z, direction.x);
+
+        let half_line_width = self.line_width * 0.5;
+
+        let (latitude_distance, latitude_line) = self.distance_to_line(latitude);
+
+        // Lines of longitude converge at the poles so scale the distance to keep their width constant.
+        let (longitude_distance, longitude_line) = self.distance_to_line(longitude);
+        let longitude_distance = longitude_distance * Real::cos(latitude);
+
+        if latitude_distance <= half_line_width {
+            if latitude_line == 0 {
+                self.axis_color
+            } else {
+                self.line_color
+            }
+        } else if longitude_distance <= half_line_width {
+            if longitude_line == 0 {
+                self.axis_color
+            } else {
+                self.line_color
+            }
+        } else {
+            self.background_color
+        }
+    }
+}
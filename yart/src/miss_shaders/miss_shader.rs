@@ -1,5 +1,11 @@
-use crate::{geometries::ray::Ray, math::color3::Color3};
+use crate::{common::Real, geometries::ray::Ray, math::color3::Color3};
 
-pub trait MissShader {
+pub trait MissShader: Send + Sync {
     fn calculate_color(&self, ray: &Ray) -> Color3;
+
+    /// Calculates how much of the background is covered by the miss shader. A value of `0` lets the background show
+    /// through when the image is composited.
+    fn calculate_alpha(&self, _ray: &Ray) -> Real {
+        1.0
+    }
 }
@@ -1,2 +1,9 @@
+pub mod atmosphere_miss_shader;
+pub mod checker_miss_shader;
 pub mod constant_miss_shader;
+pub mod environment_map_miss_shader;
+pub mod gradient_miss_shader;
+pub mod grid_miss_shader;
 pub mod miss_shader;
+pub mod preetham_sky_miss_shader;
+pub mod transparent_miss_shader;
@@ -0,0 +1,160 @@
+use super::miss_shader::MissShader;
+use crate::{
+    common::{Real, PI},
+    geometries::ray::Ray,
+    lights::sun_light::SunLight,
+    math::{color3::Color3, vector3::Vector3},
+    normalize,
+};
+
+/// The coefficients of the Perez sky luminance distribution function.
+#[derive(Debug, Copy, Clone)]
+struct PerezCoefficients {
+    a: Real,
+    b: Real,
+    c: Real,
+    d: Real,
+    e: Real,
+}
+
+impl PerezCoefficients {
+    /// Evaluates the Perez function for a view direction at angle `theta` from the zenith and angle `gamma` from the
+    /// sun.
+    fn evaluate(&self, cos_theta: Real, gamma: Real) -> Real {
+        let cos_gamma = Real::cos(gamma);
+
+        (1.0 + self.a * Real::exp(self.b / cos_theta))
+            * (1.0 + self.c * Real::exp(self.d * gamma) + self.e * cos_gamma * cos_gamma)
+    }
+}
+
+/// A miss shader implementing the analytic daylight model from "A Practical Analytic Model for Daylight" by Preetham,
+/// Shirley and Smits. Directions below the horizon are black.
+#[derive(Debug, Clone)]
+pub struct PreethamSkyMissShader {
+    pub sun: SunLight,
+    pub sun_intensity: Color3,
+    pub turbidity: Real,
+
+    /// A multiplier applied to the sky luminance which is otherwise in kilocandelas per square meter.
+    pub intensity: Real,
+
+    luminance_coefficients: PerezCoefficients,
+    x_coefficients: PerezCoefficients,
+    y_coefficients: PerezCoefficients,
+
+    zenith_luminance: Real,
+    zenith_x: Real,
+    zenith_y: Real,
+
+    sun_theta: Real,
+}
+
+impl PreethamSkyMissShader {
+    pub fn new(sun: &SunLight, sun_intensity: &Color3, turbidity: Real, intensity: Real) -> Self {
+        let t = turbidity;
+
+        let luminance_coefficients = PerezCoefficients {
+            a: 0.1787 * t - 1.4630,
+            b: -0.3554 * t + 0.4275,
+            c: -0.0227 * t + 5.3251,
+            d: 0.1206 * t - 2.5771,
+            e: -0.0670 * t + 0.3703,
+        };
+
+        let x_coefficients = PerezCoefficients {
+            a: -0.0193 * t - 0.2592,
+            b: -0.0665 * t + 0.0008,
+            c: -0.0004 * t + 0.2125,
+            d: -0.0641 * t - 0.8989,
+            e: -0.0033 * t + 0.0452,
+        };
+
+        let y_coefficients = PerezCoefficients {
+            a: -0.0167 * t - 0.2608,
+            b: -0.0950 * t + 0.0092,
+            c: -0.0079 * t + 0.2102,
+            d: -0.0441 * t - 1.6537,
+            e: -0.0109 * t + 0.0529,
+        };
+
+        // Keep the sun slightly above the horizon so that the zenith values stay well defined.
+        let sun_theta = Real::min(
+            Real::acos(Real::clamp(sun.direction_to_sun().y, -1.0, 1.0)),
+            PI * 0.5 - 0.001,
+        );
+
+        let sun_theta2 = sun_theta * sun_theta;
+        let sun_theta3 = sun_theta2 * sun_theta;
+        let t2 = t * t;
+
+        let chi = (4.0 / 9.0 - t / 120.0) * (PI - 2.0 * sun_theta);
+        let zenith_luminance = Real::max(0.0, (4.0453 * t - 4.9710) * Real::tan(chi) - 0.2155 * t + 2.4192);
+
+        let zenith_x = t2 * (0.00166 * sun_theta3 - 0.00375 * sun_theta2 + 0.00209 * sun_theta)
+            + t * (-0.02903 * sun_theta3 + 0.06377 * sun_theta2 - 0.03202 * sun_theta + 0.00394)
+            + (0.11693 * sun_theta3 - 0.21196 * sun_theta2 + 0.06052 * sun_theta + 0.25886);
+
+        let zenith_y = t2 * (0.00275 * sun_theta3 - 0.00610 * sun_theta2 + 0.00317 * sun_theta)
+            + t * (-0.04214 * sun_theta3 + 0.08970 * sun_theta2 - 0.04153 * sun_theta + 0.00516)
+            + (0.15346 * sun_theta3 - 0.26756 * sun_theta2 + 0.06670 * sun_theta + 0.26688);
+
+        Self {
+            sun: *sun,
+            sun_intensity: *sun_intensity,
+            turbidity,
+            intensity,
+            luminance_coefficients,
+            x_coefficients,
+            y_coefficients,
+            zenith_luminance,
+            zenith_x,
+            zenith_y,
+            sun_theta,
+        }
+    }
+
+    fn evaluate(&self, coefficients: &PerezCoefficients, zenith_value: Real, cos_theta: Real, gamma: Real) -> Real {
+        zenith_value * coefficients.evaluate(cos_theta, gamma) / coefficients.evaluate(1.0, self.sun_theta)
+    }
+
+    fn calculate_sky_color(&self, direction: &Vector3) -> Color3 {
+        // Clamp the view direction slightly above the horizon to avoid the singularity of the Perez function.
+        let cos_theta = Real::max(direction.y, 0.001);
+        let gamma = Real::acos(Real::clamp(direction ^ self.sun.direction_to_sun(), -1.0, 1.0));
+
+        let luminance = self.evaluate(&self.luminance_coefficients, self.zenith_luminance, cos_theta, gamma);
+        let x = self.evaluate(&self.x_coefficients, self.zenith_x, cos_theta, gamma);
+        let y = self.evaluate(&self.y_coefficients, self.zenith_y, cos_theta, gamma);
+
+        if y <= 0.0 {
+            return Color3::default();
+        }
+
+        let color = Color3::from_cie_xyz(x / y * luminance, luminance, (1.0 - x - y) / y * luminance) * self.intensity;
+
+        Color3::new(
+            Real::max(0.0, color.r),
+            Real::max(0.0, color.g),
+            Real::max(0.0, color.b),
+        )
+    }
+}
+
+impl MissShader for PreethamSkyMissShader {
+    fn calculate_color(&self, ray: &Ray) -> Color3 {
+        let direction = normalize!(*ray.direction());
+
+        if direction.y < 0.0 {
+            return Color3::default();
+        }
+
+        let mut color = self.calculate_sky_color(&direction);
+
+        if self.sun.contains_direction(&direction) {
+            color += self.sun.calculate_disk_radiance(&self.sun_intensity);
+        }
+
+        color
+    }
+}
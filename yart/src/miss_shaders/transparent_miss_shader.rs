@@ -0,0 +1,25 @@
+use super::miss_shader::MissShader;
+use crate::{common::Real, geometries::ray::Ray, math::color3::Color3};
+
+/// A miss shader that leaves the background fully transparent so that the image can be composited over a plate. The
+/// color is still returned for rays that bounce off of geometry and miss, for example in reflections.
+#[derive(Debug)]
+pub struct TransparentMissShader {
+    pub color: Color3,
+}
+
+impl TransparentMissShader {
+    pub fn new(color: &Color3) -> Self {
+        Self { color: *color }
+    }
+}
+
+impl MissShader for TransparentMissShader {
+    fn calculate_color(&self, _ray: &Ray) -> Color3 {
+        self.color
+    }
+
+    fn calculate_alpha(&self, _ray: &Ray) -> Real {
+        0.0
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{
+    common::Real,
+    math::{color4::Color4, vector2::Vector2},
+    scene::Scene,
+};
+use image::{ImageBuffer, ImageResult, Rgba, Rgba32FImage, RgbaImage};
+use std::path::Path;
+
+/// Writes the pixels of a scene's render region, as returned by [`crate::rendering::render`], to the scene's output
+/// file. The image is either cropped to the render region or is the size of the whole image in which case the pixels
+/// outside of the region are transparent or, when resuming, taken from the existing output file.
+pub fn write_render_output(scene: &Scene, region_pixels: &[Color4]) -> ImageResult<()> {
+    let screen_size = scene.camera.screen_size();
+    let region = scene.config.render_region(screen_size);
+
+    let path = Path::new(scene.config.output_file());
+    let color_clamp = scene.config.color_clamp();
+
+    if scene.config.crop_to_render_region() {
+        return write_image_file(path, region.width, region.height, region_pixels, &color_clamp);
+    }
+
+    let previous_pixels = if scene.config.resume() {
+        read_image_file(path)
+            .ok()
+            .filter(|(width, height, _)| (*width, *height) == screen_size)
+            .map(|(_, _, pixels)| pixels)
+    } else {
+        None
+    };
+
+    let mut pixels =
+        previous_pixels.unwrap_or_else(|| vec![Color4::default(); (screen_size.0 * screen_size.1) as usize]);
+
+    region.copy_into_image(region_pixels, &mut pixels, screen_size.0);
+
+    write_image_file(path, screen_size.0, screen_size.1, &pixels, &color_clamp)
+}
+
+/// Reads an image file written by [`write_image_file`] and returns its width, height and premultiplied pixels.
+pub fn read_image_file(path: &Path) -> ImageResult<(u32, u32, Vec<Color4>)> {
+    let image = image::open(path)?.into_rgba32f();
+    let is_exr = is_exr_path(path);
+
+    let pixels = image
+        .pixels()
+        .map(|pixel| {
+            let color = Color4::new(pixel[0] as Real, pixel[1] as Real, pixel[2] as Real, pixel[3] as Real);
+
+            // Everything but OpenEXR files is stored with straight alpha.
+            if is_exr {
+                color
+            } else {
+                Color4::from_color3(&(color.rgb() * color.a), color.a)
+            }
+        })
+        .collect();
+
+    Ok((image.width(), image.height(), pixels))
+}
+
+/// Writes premultiplied pixels to an image file. The format is chosen from the file extension. OpenEXR files store
+/// the premultiplied linear values as is while every other format is written with 8 bits per channel and straight
+/// alpha after clamping the colors to `color_clamp`.
+pub fn write_image_file(
+    path: &Path,
+    width: u32,
+    height: u32,
+    pixels: &[Color4],
+    color_clamp: &Vector2,
+) -> ImageResult<()> {
+    if is_exr_path(path) {
+        write_exr_file(path, width, height, pixels)
+    } else {
+        write_ldr_file(path, width, height, pixels, color_clamp)
+    }
+}
+
+// The casts are needed unless the `f32` feature is enabled.
+#[allow(clippy::unnecessary_cast)]
+fn write_exr_file(path: &Path, width: u32, height: u32, pixels: &[Color4]) -> ImageResult<()> {
+    let image: Rgba32FImage = ImageBuffer::from_fn(width, height, |x, y| {
+        let pixel = pixels[(y * width + x) as usize];
+        Rgba([pixel.r as f32, pixel.g as f32, pixel.b as f32, pixel.a as f32])
+    });
+
+    image.save(path)
+}
+
+fn write_ldr_file(path: &Path, width: u32, height: u32, pixels: &[Color4], color_clamp: &Vector2) -> ImageResult<()> {
+    let to_u8 = |value: Real| (Real::clamp(value, color_clamp.x, color_clamp.y) * 255.0).round() as u8;
+
+    let image: RgbaImage = ImageBuffer::from_fn(width, height, |x, y| {
+        let pixel = pixels[(y * width + x) as usize];
+
+        // Convert from premultiplied to straight alpha.
+        let color = if pixel.a > 0.0 {
+            pixel.rgb() / pixel.a
+        } else {
+            pixel.rgb()
+        };
+
+        Rgba([
+            to_u8(color.r),
+            to_u8(color.g),
+            to_u8(color.b),
+            (Real::clamp(pixel.a, 0.0, 1.0) * 255.0).round() as u8,
+        ])
+    });
+
+    image.save(path)
+}
+
+fn is_exr_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|x| x.to_str())
+        .is_some_and(|x| x.eq_ignore_ascii_case("exr"))
+}
@@ -1,62 +1,115 @@
-use crate::{common::Real, math::color3::Color3, scene::Scene};
+use crate::{
+    common::Real,
+    math::{color4::Color4, vector2::Vector2},
+    scene::Scene,
+};
 use rand::RngCore;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 
-pub fn render(scene: &Scene) -> Vec<Color3> {
-    let width = scene.camera.screen_size().0;
-    let height = scene.camera.screen_size().1;
+/// A rectangle of pixels within the image. Only the pixels inside the render region of a scene are rendered which
+/// makes it possible to re-render a small problem area at full quality.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RenderRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl RenderRegion {
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self { x, y, width, height }
+    }
+
+    /// Creates a region covering the whole image.
+    pub fn full_frame(screen_size: (u32, u32)) -> Self {
+        Self::new(0, 0, screen_size.0, screen_size.1)
+    }
 
-    let patches = create_patches(width, height, 8);
+    /// Creates a region from a border given in normalized image coordinates where `(0, 0)` is the upper left corner of
+    /// the image and `(1, 1)` is the lower right corner.
+    pub fn from_border(minimum: &Vector2, maximum: &Vector2, screen_size: (u32, u32)) -> Self {
+        let to_pixel = |value: Real, size: u32| (Real::clamp(value, 0.0, 1.0) * size as Real).round() as u32;
+
+        let start_x = to_pixel(minimum.x, screen_size.0);
+        let start_y = to_pixel(minimum.y, screen_size.1);
+        let end_x = to_pixel(maximum.x, screen_size.0);
+        let end_y = to_pixel(maximum.y, screen_size.1);
+
+        Self::new(
+            start_x,
+            start_y,
+            end_x.saturating_sub(start_x),
+            end_y.saturating_sub(start_y),
+        )
+    }
+
+    /// Shrinks the region so that it lies completely within an image of the given size.
+    pub fn clamp_to_screen(&self, screen_size: (u32, u32)) -> Self {
+        let x = u32::min(self.x, screen_size.0);
+        let y = u32::min(self.y, screen_size.1);
+
+        Self::new(
+            x,
+            y,
+            u32::min(self.width, screen_size.0 - x),
+            u32::min(self.height, screen_size.1 - y),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Copies the row major pixels of the region into the pixels of the whole image.
+    pub fn copy_into_image(&self, region_pixels: &[Color4], image_pixels: &mut [Color4], image_width: u32) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                image_pixels[((self.y + y) * image_width + self.x + x) as usize] =
+                    region_pixels[(y * self.width + x) as usize];
+            }
+        }
+    }
+}
+
+/// Renders the scene's render region and returns its pixels in row major order. The colors are premultiplied by their
+/// alpha. Without a render region the whole image is rendered.
+pub fn render(scene: &Scene) -> Vec<Color4> {
+    let region = scene.config.render_region(scene.camera.screen_size());
+
+    if region.is_empty() {
+        return Vec::new();
+    }
+
+    let width = region.width;
+    let height = region.height;
+
+    let patches = create_patches(&region, 8);
 
     let patch_results = patches
         .par_iter()
         .map(|patch| {
-            let scene2 = scene;
-
             let mut rng = rand::thread_rng();
-            render_patch(&mut rng, scene2, patch)
+            render_patch(&mut rng, scene, patch)
         })
         .collect::<Vec<_>>();
 
-    let mut pixels = vec![Color3::default(); (width * height) as usize];
+    let mut pixels = vec![Color4::default(); (width * height) as usize];
 
-    for patch_result in &patch_results {}
+    for patch_result in &patch_results {
+        let patch = &patch_result.patch;
+        let patch_width = patch.end_x - patch.start_x + 1;
 
-    pixels
+        for y in patch.start_y..=patch.end_y {
+            for x in patch.start_x..=patch.end_x {
+                let patch_pixel_index = ((y - patch.start_y) * patch_width + (x - patch.start_x)) as usize;
+                pixels[((y - region.y) * width + (x - region.x)) as usize] =
+                    patch_result.patch_pixels[patch_pixel_index];
+            }
+        }
+    }
 
-    // let total_subpixels = scene.camera.subpixel_count() * scene.camera.subpixel_count();
-
-    // let mut pixels = vec![Color3::default(); (width * height) as usize];
-
-    // for _ in 0..scene.config.iterations() {
-    //     for y in 0..height {
-    //         for x in 0..width {
-    //             let pixel_index = (y * width + x) as usize;
-
-    //             for subpixel_y in 0..scene.camera.subpixel_count() {
-    //                 for subpixel_x in 0..scene.camera.subpixel_count() {
-    //                     let ray = scene.camera.create_ray(&mut rng, (x, y), (subpixel_x, subpixel_y));
-    //                     let color = scene.cast_ray_color(&mut rng, &ray, 1);
-
-    //                     pixels[pixel_index] += color;
-    //                 }
-    //             }
-
-    //             pixels[pixel_index] /= total_subpixels as Real;
-    //         }
-    //     }
-    // }
-
-    // pixels
-    //     .iter()
-    //     .map(|pixel| {
-    //         Rgb::from([
-    //             (pixel.r / (scene.config.iterations() as Real) * 255.0) as u8,
-    //             (pixel.g / (scene.config.iterations() as Real) * 255.0) as u8,
-    //             (pixel.b / (scene.config.iterations() as Real) * 255.0) as u8,
-    //         ])
-    //     })
-    //     .collect()
+    pixels
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -81,39 +134,43 @@ impl Patch {
 #[derive(Debug)]
 struct PatchResults {
     patch: Patch,
-    patch_pixels: Vec<Color3>,
+    patch_pixels: Vec<Color4>,
 }
 
 impl PatchResults {
-    fn new(patch: Patch, patch_pixels: Vec<Color3>) -> Self {
+    fn new(patch: Patch, patch_pixels: Vec<Color4>) -> Self {
         Self { patch, patch_pixels }
     }
 }
 
-fn create_patches(width: u32, height: u32, patch_size: u32) -> Vec<Patch> {
+fn create_patches(region: &RenderRegion, patch_size: u32) -> Vec<Patch> {
     let mut patches = Vec::new();
 
-    let mut start_y = 0u32;
-    let mut start_x = 0u32;
+    let region_end_x = region.x + region.width;
+    let region_end_y = region.y + region.height;
+
+    let mut start_y = region.y;
 
     loop {
-        if start_y >= height {
+        if start_y >= region_end_y {
             break;
         }
 
-        let end_y = if start_y + patch_size >= height {
-            height - 1
+        let end_y = if start_y + patch_size >= region_end_y {
+            region_end_y - 1
         } else {
             start_y + patch_size - 1
         };
 
+        let mut start_x = region.x;
+
         loop {
-            if start_x >= width {
+            if start_x >= region_end_x {
                 break;
             }
 
-            let end_x = if start_x + patch_size >= width {
-                width - 1
+            let end_x = if start_x + patch_size >= region_end_x {
+                region_end_x - 1
             } else {
                 start_x + patch_size - 1
             };
@@ -129,31 +186,35 @@ fn create_patches(width: u32, height: u32, patch_size: u32) -> Vec<Patch> {
 }
 
 fn render_patch(rng: &mut dyn RngCore, scene: &Scene, patch: &Patch) -> PatchResults {
-    let width = scene.camera.screen_size().0;
-    let height = scene.camera.screen_size().1;
+    let patch_width = patch.end_x - patch.start_x + 1;
+    let patch_height = patch.end_y - patch.start_y + 1;
 
-    let total_subpixels = scene.camera.subpixel_count() * scene.camera.subpixel_count();
+    let total_samples = scene.config.iterations() * scene.camera.subpixel_count() * scene.camera.subpixel_count();
 
-    let mut pixels = vec![Color3::default(); (width * height) as usize];
+    let mut pixels = vec![Color4::default(); (patch_width * patch_height) as usize];
 
     for _ in 0..scene.config.iterations() {
         for y in patch.start_y..=patch.end_y {
             for x in patch.start_x..=patch.end_x {
-                let pixel_index = (y * width + x) as usize;
+                let pixel_index = ((y - patch.start_y) * patch_width + (x - patch.start_x)) as usize;
 
                 for subpixel_y in 0..scene.camera.subpixel_count() {
                     for subpixel_x in 0..scene.camera.subpixel_count() {
-                        let ray = scene.camera.create_ray(rng, (x, y), (subpixel_x, subpixel_y));
-                        let color = scene.cast_ray_color(rng, &ray, 1);
+                        if let Some(ray) = scene.camera.create_ray(rng, (x, y), (subpixel_x, subpixel_y)) {
+                            let color = scene.cast_camera_ray(rng, &ray);
+                            let exposure = scene.camera.calculate_exposure(&ray);
 
-                        pixels[pixel_index] += color;
+                            pixels[pixel_index] += Color4::from_color3(&(color.rgb() * exposure), color.a);
+                        }
                     }
                 }
-
-                pixels[pixel_index] /= total_subpixels as Real;
             }
         }
     }
 
+    for pixel in &mut pixels {
+        *pixel /= total_samples as Real;
+    }
+
     PatchResults::new(*patch, pixels)
 }
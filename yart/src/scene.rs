@@ -1,11 +1,15 @@
 use crate::{
-    cameras::camera::Camera,
-    common::{Real, NORMAL_BUMP},
-    geometries::{area_light::AreaLight, intersectable::Intersectable, ray::Ray},
+    cameras::camera::{estimate_ray_cone, Camera},
+    common::Real,
+    geometries::{
+        area_light::AreaLight, intersectable::Intersectable, intersection::Intersection, ray::Ray,
+        surface_point::SurfacePoint,
+    },
     lights::light::Light,
     materials::material::Material,
-    math::color3::Color3,
+    math::{color3::Color3, color4::Color4, vector3::Vector3},
     miss_shaders::miss_shader::MissShader,
+    textures::{normal_perturbation::keep_facing_viewer, texture_coordinates::TextureCoordinates},
     yaml::parse_config::Config,
 };
 use rand::RngCore;
@@ -18,6 +22,9 @@ pub struct Scene {
     pub area_lights: Vec<Box<dyn AreaLight>>,
     pub miss_shader: Box<dyn MissShader>,
     pub root_geometry: Box<dyn Intersectable>,
+
+    /// The width and spread of the cone each camera ray stands for. See [`Ray::with_cone`].
+    camera_ray_cone: (Real, Real),
 }
 
 impl Scene {
@@ -30,7 +37,7 @@ impl Scene {
         miss_shader: Box<dyn MissShader>,
         root_geometry: Box<dyn Intersectable>,
     ) -> Self {
-        Self {
+        let mut scene = Self {
             config,
             camera,
             materials,
@@ -38,6 +45,61 @@ impl Scene {
             area_lights,
             miss_shader,
             root_geometry,
+            camera_ray_cone: (0.0, 0.0),
+        };
+
+        scene.autofocus_camera();
+        scene.camera_ray_cone = estimate_ray_cone(scene.camera.as_ref());
+
+        scene
+    }
+
+    /// Focuses the camera on the first surface along its autofocus ray. The camera keeps its configured focus distance
+    /// if the ray doesn't hit anything.
+    fn autofocus_camera(&mut self) {
+        let Some(ray) = self.camera.autofocus_ray() else {
+            return;
+        };
+
+        if let Some(distance) = self.cast_ray_distance(&ray) {
+            self.camera.set_focus_distance(distance);
+        }
+    }
+
+    /// Casts a ray from the camera and returns the premultiplied color along with how much of the background the
+    /// sample covers.
+    pub fn cast_camera_ray(&self, rng: &mut dyn RngCore, ray: &Ray) -> Color4 {
+        let ray = &ray.with_cone(self.camera_ray_cone.0, self.camera_ray_cone.1);
+        let maybe_intersection = self.root_geometry.intersect(ray);
+
+        match maybe_intersection {
+            Some(intersection) => {
+                let (material, hit_position, surface_point) = self.resolve_intersection(ray, &intersection);
+                let hit_normal = surface_point.shading_normal;
+
+                match material {
+                    Some(material_some) => {
+                        let coverage = material_some.calculate_coverage(rng, self, &hit_position, &hit_normal, ray);
+                        let color = material_some.calculate_rendering_equation(
+                            rng,
+                            self,
+                            1,
+                            intersection.hit_geometry,
+                            &hit_position,
+                            &hit_normal,
+                            &surface_point,
+                            ray,
+                        );
+
+                        Color4::from_color3(&(color * coverage), coverage)
+                    }
+                    None => Color4::new(0.0, 0.0, 0.0, 1.0),
+                }
+            }
+            None => {
+                let alpha = self.miss_shader.calculate_alpha(ray);
+                Color4::from_color3(&(self.miss_shader.calculate_color(ray) * alpha), alpha)
+            }
         }
     }
 
@@ -50,16 +112,8 @@ impl Scene {
 
         match maybe_intersection {
             Some(intersection) => {
-                let material = if intersection.material_index_override > 0 {
-                    self.materials.get(intersection.material_index_override)
-                } else {
-                    self.materials.get(intersection.hit_geometry.material_index())
-                };
-
-                let mut hit_position = ray.position_along(intersection.entrance_distance);
-                let hit_normal = intersection.hit_geometry.calculate_normal(ray, &hit_position);
-
-                hit_position += hit_normal * NORMAL_BUMP;
+                let (material, hit_position, surface_point) = self.resolve_intersection(ray, &intersection);
+                let hit_normal = surface_point.shading_normal;
 
                 match material {
                     Some(material_some) => material_some.calculate_rendering_equation(
@@ -69,7 +123,8 @@ impl Scene {
                         intersection.hit_geometry,
                         &hit_position,
                         &hit_normal,
-                        ray.direction(),
+                        &surface_point,
+                        ray,
                     ),
                     None => Color3::default(),
                 }
@@ -78,6 +133,71 @@ impl Scene {
         }
     }
 
+    /// Finds the material that was hit along with the hit position and the surface at the hit position. The shading
+    /// normal of the surface is perturbed by the normal or bump map of the material, if it has one.
+    fn resolve_intersection(
+        &self,
+        ray: &Ray,
+        intersection: &Intersection,
+    ) -> (Option<&dyn Material>, Vector3, SurfacePoint) {
+        let material_index = intersection
+            .material_index_override
+            .unwrap_or_else(|| intersection.hit_geometry.material_index());
+
+        let material = self.materials.get(material_index).map(|x| x.as_ref());
+
+        let hit_position = ray.position_along(intersection.entrance_distance);
+        let mut surface_point = intersection.surface_override.unwrap_or_else(|| {
+            intersection
+                .hit_geometry
+                .calculate_surface_point(ray, &hit_position, intersection)
+        });
+
+        if let Some(normal_perturbation) = material.and_then(|x| x.normal_perturbation()) {
+            let texture_coordinates = TextureCoordinates::from_hit(&hit_position, &surface_point, ray);
+            let perturbed_normal = normal_perturbation.perturb_normal(&surface_point, &texture_coordinates);
+
+            surface_point.shading_normal =
+                keep_facing_viewer(&perturbed_normal, &surface_point.shading_normal, ray.direction());
+        }
+
+        (material, hit_position, surface_point)
+    }
+
+    /// Determines if anything that casts shadows is hit by the ray before `maximum_distance`. Used for shadow rays,
+    /// which only need to know whether there is a hit rather than where the closest one is.
+    pub fn occluded(&self, ray: &Ray, maximum_distance: Real) -> bool {
+        let ray = ray.with_maximum_distance(Real::min(ray.maximum_distance(), maximum_distance));
+
+        self.root_geometry
+            .occluded(&ray, &|intersection| self.casts_shadows(intersection))
+    }
+
+    fn casts_shadows(&self, intersection: &Intersection) -> bool {
+        let material_index = intersection
+            .material_index_override
+            .unwrap_or_else(|| intersection.hit_geometry.material_index());
+
+        self.materials.get(material_index).is_none_or(|x| x.casts_shadows())
+    }
+
+    /// Finds the closest hit along the ray that `filter` accepts. Hits that aren't accepted are stepped over by
+    /// intersecting the scene again beyond them, so this suits rays that are expected to pass few of them, such as a
+    /// ray inside a geometry looking for where it leaves that geometry.
+    pub fn intersect_filtered(&self, ray: &Ray, filter: &dyn Fn(&Intersection) -> bool) -> Option<Intersection<'_>> {
+        let mut ray = ray.clone();
+
+        loop {
+            let intersection = self.root_geometry.intersect(&ray)?;
+
+            if filter(&intersection) {
+                return Some(intersection);
+            }
+
+            ray = ray.with_extent(intersection.entrance_distance.next_up(), ray.maximum_distance());
+        }
+    }
+
     pub fn cast_ray_distance(&self, ray: &Ray) -> Option<Real> {
         let intersection = self.root_geometry.intersect(ray)?;
         Some(Real::max(0.0, intersection.entrance_distance))
@@ -0,0 +1,69 @@
+use super::{normal_perturbation::NormalPerturbation, texture::Texture, texture_coordinates::TextureCoordinates};
+use crate::{
+    common::Real,
+    geometries::surface_point::SurfacePoint,
+    math::{vector::Vector, vector2::Vector2, vector3::Vector3},
+    normalize,
+};
+
+/// The step in texture coordinates the height is differentiated over when the ray doesn't track its cone.
+const MINIMUM_STEP: Real = 0.0005;
+
+/// Tilts the shading normal as if the surface were displaced along it by a height texture, which is evaluated as a
+/// scalar. The height is differentiated along `u` and `v` by evaluating it a small step away, over the footprint of
+/// the ray when it's known so that distant bumps don't alias.
+#[derive(Debug)]
+pub struct BumpMap {
+    height: Box<dyn Texture>,
+
+    /// The distance the surface is displaced by for a height of one.
+    pub strength: Real,
+}
+
+impl BumpMap {
+    pub fn new(height: Box<dyn Texture>) -> Self {
+        Self { height, strength: 1.0 }
+    }
+
+    /// Evaluates the height a step along a tangent. Solid textures are moved along the tangent too, which is exact
+    /// for world space and for geometry that isn't transformed.
+    fn evaluate_step(&self, coordinates: &TextureCoordinates, uv_step: &Vector2, position_step: &Vector3) -> Real {
+        let stepped_coordinates = TextureCoordinates {
+            position: coordinates.position + position_step,
+            object_position: coordinates.object_position + position_step,
+            uv: coordinates.uv + uv_step,
+            ..*coordinates
+        };
+
+        self.height.evaluate_scalar(&stepped_coordinates)
+    }
+}
+
+impl NormalPerturbation for BumpMap {
+    fn perturb_normal(&self, surface_point: &SurfacePoint, coordinates: &TextureCoordinates) -> Vector3 {
+        let normal = surface_point.shading_normal;
+        let step = Real::max(coordinates.footprint * 0.5, MINIMUM_STEP);
+
+        let height = self.height.evaluate_scalar(coordinates);
+        let height_u = self.evaluate_step(coordinates, &Vector2::new(step, 0.0), &(surface_point.dpdu * step));
+        let height_v = self.evaluate_step(coordinates, &Vector2::new(0.0, step), &(surface_point.dpdv * step));
+
+        // The tangents of the displaced surface, ignoring how the normal itself changes along it.
+        let dpdu = surface_point.dpdu + normal * ((height_u - height) / step * self.strength);
+        let dpdv = surface_point.dpdv + normal * ((height_v - height) / step * self.strength);
+
+        let perturbed = dpdu % dpdv;
+
+        if perturbed.length_squared() == 0.0 {
+            return normal;
+        }
+
+        let perturbed = normalize!(perturbed);
+
+        if (perturbed ^ normal) < 0.0 {
+            -perturbed
+        } else {
+            perturbed
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use super::{texture::Texture, texture_coordinates::TextureCoordinates};
+use crate::{common::Real, math::color3::Color3};
+
+/// A grey texture made of a single channel of another texture. Images such as the metallic-roughness textures of glTF
+/// files pack several scalar parameters into their channels, which are taken apart with a texture per channel.
+#[derive(Debug)]
+pub struct ChannelTexture {
+    texture: Box<dyn Texture>,
+
+    /// The index of the channel, with zero being red.
+    channel: usize,
+
+    /// A factor the channel is multiplied by.
+    pub factor: Real,
+}
+
+impl ChannelTexture {
+    pub fn new(texture: Box<dyn Texture>, channel: usize) -> Self {
+        assert!(channel < 3);
+
+        Self {
+            texture,
+            channel,
+            factor: 1.0,
+        }
+    }
+}
+
+impl Texture for ChannelTexture {
+    fn evaluate(&self, coordinates: &TextureCoordinates) -> Color3 {
+        Color3::from_value(self.evaluate_scalar(coordinates))
+    }
+
+    /// The channel is used as is rather than the luminance of the grey color, which would only differ by rounding.
+    fn evaluate_scalar(&self, coordinates: &TextureCoordinates) -> Real {
+        self.texture.evaluate(coordinates)[self.channel] * self.factor
+    }
+}
@@ -0,0 +1,56 @@
+use super::{
+    texture::{mix_textures, Texture},
+    texture_coordinates::{TextureCoordinates, TextureSpace},
+};
+use crate::{common::Real, math::color3::Color3};
+
+/// Integrates a square wave that is zero on even and one on odd intervals, from zero to `x`.
+fn integrate_square_wave(x: Real) -> Real {
+    let half = x * 0.5;
+    half.floor() + 2.0 * Real::max(half - half.floor() - 0.5, 0.0)
+}
+
+/// Alternates between two textures in cubes that are one unit wide before scaling, or squares in texture space.
+#[derive(Debug)]
+pub struct CheckerTexture {
+    color1: Box<dyn Texture>,
+    color2: Box<dyn Texture>,
+
+    pub space: TextureSpace,
+
+    /// The point is multiplied by `scale` before it's evaluated, so a scale of two halves the size of the cells.
+    pub scale: Real,
+}
+
+impl CheckerTexture {
+    pub fn new(color1: Box<dyn Texture>, color2: Box<dyn Texture>, space: TextureSpace) -> Self {
+        Self {
+            color1,
+            color2,
+            space,
+            scale: 1.0,
+        }
+    }
+}
+
+impl Texture for CheckerTexture {
+    /// In texture space the cells are averaged over the footprint of the ray, so distant checkers fade to grey rather
+    /// than alias.
+    fn evaluate(&self, coordinates: &TextureCoordinates) -> Color3 {
+        let point = coordinates.point(self.space) * self.scale;
+        let width = coordinates.footprint * self.scale.abs() * 0.5;
+
+        let amount = if self.space == TextureSpace::Uv && width > 0.0 {
+            let fraction =
+                |x: Real| (integrate_square_wave(x + width) - integrate_square_wave(x - width)) / (2.0 * width);
+
+            let (u, v) = (fraction(point.x), fraction(point.y));
+            u + v - 2.0 * u * v
+        } else {
+            let parity = point.x.floor() + point.y.floor() + point.z.floor();
+            parity.rem_euclid(2.0)
+        };
+
+        mix_textures(self.color1.as_ref(), self.color2.as_ref(), amount, coordinates)
+    }
+}
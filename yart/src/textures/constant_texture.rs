@@ -0,0 +1,25 @@
+use super::{texture::Texture, texture_coordinates::TextureCoordinates};
+use crate::{common::Real, math::color3::Color3};
+
+/// A texture that is the same color everywhere.
+#[derive(Debug)]
+pub struct ConstantTexture {
+    pub color: Color3,
+}
+
+impl ConstantTexture {
+    pub fn new(color: &Color3) -> Self {
+        Self { color: *color }
+    }
+
+    /// Creates a grey texture that evaluates to `value` as a scalar.
+    pub fn from_value(value: Real) -> Self {
+        Self::new(&Color3::from_value(value))
+    }
+}
+
+impl Texture for ConstantTexture {
+    fn evaluate(&self, _coordinates: &TextureCoordinates) -> Color3 {
+        self.color
+    }
+}
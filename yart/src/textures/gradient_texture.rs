@@ -0,0 +1,50 @@
+use super::{
+    texture::{mix_textures, Texture},
+    texture_coordinates::{TextureCoordinates, TextureSpace},
+};
+use crate::math::{color3::Color3, vector::Vector, vector3::Vector3};
+
+/// Blends linearly between two textures from a start point to an end point. Points before the start or beyond the end
+/// get the texture of the closer one.
+#[derive(Debug)]
+pub struct GradientTexture {
+    color1: Box<dyn Texture>,
+    color2: Box<dyn Texture>,
+
+    pub space: TextureSpace,
+    pub start: Vector3,
+    pub end: Vector3,
+}
+
+impl GradientTexture {
+    pub fn new(
+        color1: Box<dyn Texture>,
+        color2: Box<dyn Texture>,
+        space: TextureSpace,
+        start: &Vector3,
+        end: &Vector3,
+    ) -> Self {
+        Self {
+            color1,
+            color2,
+            space,
+            start: *start,
+            end: *end,
+        }
+    }
+}
+
+impl Texture for GradientTexture {
+    fn evaluate(&self, coordinates: &TextureCoordinates) -> Color3 {
+        let direction = self.end - self.start;
+        let length_squared = direction.length_squared();
+
+        let amount = if length_squared > 0.0 {
+            ((coordinates.point(self.space) - self.start) ^ direction) / length_squared
+        } else {
+            0.0
+        };
+
+        mix_textures(self.color1.as_ref(), self.color2.as_ref(), amount, coordinates)
+    }
+}
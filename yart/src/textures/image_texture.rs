@@ -0,0 +1,71 @@
+use super::{
+    mip_map::{MipMap, WrapMode},
+    texture::Texture,
+    texture_coordinates::TextureCoordinates,
+};
+use crate::{
+    common::Real,
+    math::{color3::Color3, vector2::Vector2},
+};
+use std::sync::Arc;
+
+/// How an [`ImageTexture`] blends the pixels around the texture coordinates it's looked up at.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TextureFilter {
+    /// Interpolates between the four closest pixels of the full size image.
+    Bilinear,
+
+    /// Also interpolates between the two mip map levels whose pixels are closest in size to the area the ray covers.
+    /// Rays that don't track their cone use the full size image.
+    Trilinear,
+}
+
+/// A texture that looks up its colors in an image. The image can be shared with other textures since it's usually
+/// loaded through a [`super::texture_cache::TextureCache`].
+#[derive(Debug)]
+pub struct ImageTexture {
+    mip_map: Arc<MipMap>,
+
+    pub filter: TextureFilter,
+    pub wrap_mode: WrapMode,
+
+    /// Texture coordinates are multiplied by `scale` and then moved by `offset` before the image is looked up, so a
+    /// scale of two repeats the image twice.
+    pub scale: Vector2,
+    pub offset: Vector2,
+
+    /// A color every looked up color is multiplied by.
+    pub tint: Color3,
+}
+
+impl ImageTexture {
+    pub fn new(mip_map: &Arc<MipMap>, filter: TextureFilter, wrap_mode: WrapMode) -> Self {
+        Self {
+            mip_map: mip_map.clone(),
+            filter,
+            wrap_mode,
+            scale: Vector2::new(1.0, 1.0),
+            offset: Vector2::default(),
+            tint: Color3::from_value(1.0),
+        }
+    }
+}
+
+impl Texture for ImageTexture {
+    fn evaluate(&self, coordinates: &TextureCoordinates) -> Color3 {
+        let uv = Vector2::new(
+            coordinates.uv.x * self.scale.x + self.offset.x,
+            coordinates.uv.y * self.scale.y + self.offset.y,
+        );
+
+        let color = match self.filter {
+            TextureFilter::Bilinear => self.mip_map.lookup_bilinear(&uv, self.wrap_mode),
+            TextureFilter::Trilinear => {
+                let footprint = coordinates.footprint * Real::max(self.scale.x.abs(), self.scale.y.abs());
+                self.mip_map.lookup_trilinear(&uv, footprint, self.wrap_mode)
+            }
+        };
+
+        color * self.tint
+    }
+}
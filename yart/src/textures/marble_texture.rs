@@ -0,0 +1,52 @@
+use super::{
+    texture::{mix_textures, Texture},
+    texture_coordinates::{TextureCoordinates, TextureSpace},
+};
+use crate::{
+    common::Real,
+    math::{
+        color3::Color3,
+        noise::{turbulence, NoiseOctaves},
+    },
+};
+
+/// Veins of `color2` running through `color1`, made by bending stripes along the `x` axis with turbulence.
+#[derive(Debug)]
+pub struct MarbleTexture {
+    color1: Box<dyn Texture>,
+    color2: Box<dyn Texture>,
+
+    pub space: TextureSpace,
+
+    /// The point is multiplied by `scale` before it's evaluated, so a larger scale gives narrower veins.
+    pub scale: Real,
+    pub octaves: NoiseOctaves,
+
+    /// How far the stripes are bent, where zero gives straight stripes.
+    pub turbulence: Real,
+}
+
+impl MarbleTexture {
+    pub fn new(color1: Box<dyn Texture>, color2: Box<dyn Texture>, space: TextureSpace) -> Self {
+        Self {
+            color1,
+            color2,
+            space,
+            scale: 1.0,
+            octaves: NoiseOctaves::default(),
+            turbulence: 2.0,
+        }
+    }
+}
+
+impl Texture for MarbleTexture {
+    fn evaluate(&self, coordinates: &TextureCoordinates) -> Color3 {
+        let point = coordinates.point(self.space) * self.scale;
+        let stripes = Real::sin(point.x + self.turbulence * turbulence(&point, &self.octaves));
+
+        // Sharpen the stripes into thin veins.
+        let amount = Real::powi(0.5 - 0.5 * stripes, 3);
+
+        mix_textures(self.color1.as_ref(), self.color2.as_ref(), amount, coordinates)
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{
+    common::Real,
+    math::{color3::Color3, vector2::Vector2},
+};
+use image::DynamicImage;
+use std::path::Path;
+
+/// How the colors stored in an image relate to linear light.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ColorSpace {
+    /// Colors are encoded with the sRGB transfer function, as is usual for 8 bit images such as PNG and JPEG files.
+    Srgb,
+
+    /// Colors are stored as linear light, as is usual for floating point images such as HDR and EXR files and for
+    /// images that hold data rather than colors.
+    Linear,
+}
+
+impl ColorSpace {
+    /// Picks the color space an image is most likely stored in from its pixel format.
+    pub fn guess(image: &DynamicImage) -> Self {
+        match image {
+            DynamicImage::ImageRgb32F(_) | DynamicImage::ImageRgba32F(_) => Self::Linear,
+            _ => Self::Srgb,
+        }
+    }
+
+    fn decode(&self, value: Real) -> Real {
+        match self {
+            Self::Srgb if value <= 0.04045 => value / 12.92,
+            Self::Srgb => Real::powf((value + 0.055) / 1.055, 2.4),
+            Self::Linear => value,
+        }
+    }
+}
+
+/// What happens to texture coordinates outside of the zero to one range.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WrapMode {
+    /// The image is tiled.
+    Repeat,
+
+    /// The pixels at the edges of the image are stretched outward.
+    Clamp,
+
+    /// The image is tiled with every other tile flipped, so there are no seams between tiles.
+    Mirror,
+}
+
+impl WrapMode {
+    fn wrap(&self, coordinate: i64, size: usize) -> usize {
+        let size = size as i64;
+
+        let wrapped = match self {
+            Self::Repeat => coordinate.rem_euclid(size),
+            Self::Clamp => coordinate.clamp(0, size - 1),
+            Self::Mirror => {
+                let mirrored = coordinate.rem_euclid(2 * size);
+
+                if mirrored < size {
+                    mirrored
+                } else {
+                    2 * size - 1 - mirrored
+                }
+            }
+        };
+
+        wrapped as usize
+    }
+}
+
+#[derive(Debug)]
+struct MipLevel {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color3>,
+}
+
+impl MipLevel {
+    /// Averages every two by two block of pixels into one.
+    fn downsample(&self) -> Self {
+        let width = usize::max(self.width / 2, 1);
+        let height = usize::max(self.height / 2, 1);
+
+        let mut pixels = Vec::with_capacity(width * height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let x0 = usize::min(2 * x, self.width - 1);
+                let x1 = usize::min(2 * x + 1, self.width - 1);
+                let y0 = usize::min(2 * y, self.height - 1);
+                let y1 = usize::min(2 * y + 1, self.height - 1);
+
+                pixels.push(
+                    (self.pixels[y0 * self.width + x0]
+                        + self.pixels[y0 * self.width + x1]
+                        + self.pixels[y1 * self.width + x0]
+                        + self.pixels[y1 * self.width + x1])
+                        * 0.25,
+                );
+            }
+        }
+
+        Self { width, height, pixels }
+    }
+
+    fn texel(&self, x: i64, y: i64, wrap_mode: WrapMode) -> Color3 {
+        self.pixels[wrap_mode.wrap(y, self.height) * self.width + wrap_mode.wrap(x, self.width)]
+    }
+
+    fn lookup_bilinear(&self, uv: &Vector2, wrap_mode: WrapMode) -> Color3 {
+        // Texel centers are at half coordinates.
+        let x = uv.x * self.width as Real - 0.5;
+        let y = uv.y * self.height as Real - 0.5;
+
+        let x0 = x.floor();
+        let y0 = y.floor();
+
+        let fraction_x = x - x0;
+        let fraction_y = y - y0;
+
+        // Huge or infinite coordinates, such as those of planes seen at grazing angles, saturate when converted, so
+        // the neighboring texels must saturate as well rather than overflow.
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        let (x1, y1) = (x0.saturating_add(1), y0.saturating_add(1));
+
+        let top = self.texel(x0, y0, wrap_mode) * (1.0 - fraction_x) + self.texel(x1, y0, wrap_mode) * fraction_x;
+        let bottom = self.texel(x0, y1, wrap_mode) * (1.0 - fraction_x) + self.texel(x1, y1, wrap_mode) * fraction_x;
+
+        top * (1.0 - fraction_y) + bottom * fraction_y
+    }
+}
+
+/// An image along with successively halved versions of it, down to a single pixel. Looking up the version whose
+/// pixels are about as large as the area a ray covers avoids the aliasing of sampling a detailed image sparsely.
+///
+/// Texture coordinates go from zero to one across the image, with `(0, 0)` at the upper left corner.
+#[derive(Debug)]
+pub struct MipMap {
+    levels: Vec<MipLevel>,
+}
+
+impl MipMap {
+    /// Creates a mip map from linear colors.
+    pub fn new(width: usize, height: usize, pixels: Vec<Color3>) -> Self {
+        let mut levels = vec![MipLevel { width, height, pixels }];
+
+        while let Some(level) = levels.last().filter(|x| x.width > 1 || x.height > 1) {
+            levels.push(level.downsample());
+        }
+
+        Self { levels }
+    }
+
+    /// Creates a mip map from an image, converting its colors to linear light. The color space is guessed from the
+    /// pixel format if it isn't given.
+    pub fn from_image(image: DynamicImage, color_space: Option<ColorSpace>) -> Self {
+        let color_space = color_space.unwrap_or_else(|| ColorSpace::guess(&image));
+        let image = image.into_rgb32f();
+
+        let pixels = image
+            .pixels()
+            .map(|pixel| {
+                Color3::new(
+                    color_space.decode(pixel[0] as Real),
+                    color_space.decode(pixel[1] as Real),
+                    color_space.decode(pixel[2] as Real),
+                )
+            })
+            .collect();
+
+        Self::new(image.width() as usize, image.height() as usize, pixels)
+    }
+
+    /// Loads an image in any format supported by the `image` crate, such as PNG, JPEG, HDR or EXR.
+    pub fn from_file(path: &Path, color_space: Option<ColorSpace>) -> Option<Self> {
+        Some(Self::from_image(image::open(path).ok()?, color_space))
+    }
+
+    /// Decodes an image held in memory, such as one embedded in a glTF file.
+    pub fn from_memory(data: &[u8], color_space: Option<ColorSpace>) -> Option<Self> {
+        Some(Self::from_image(image::load_from_memory(data).ok()?, color_space))
+    }
+
+    pub fn width(&self) -> usize {
+        self.levels[0].width
+    }
+
+    pub fn height(&self) -> usize {
+        self.levels[0].height
+    }
+
+    pub fn level_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Interpolates between the four pixels of the full size image closest to `uv`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use yart::math::{color3::Color3, vector2::Vector2};
+    /// # use yart::textures::mip_map::*;
+    /// #
+    /// let black = Color3::new(0.0, 0.0, 0.0);
+    /// let white = Color3::new(1.0, 1.0, 1.0);
+    /// let mip_map = MipMap::new(2, 2, vec![black, white, white, black]);
+    ///
+    /// assert_eq!(white, mip_map.lookup_bilinear(&Vector2::new(1e30, 0.25), WrapMode::Clamp));
+    /// ```
+    pub fn lookup_bilinear(&self, uv: &Vector2, wrap_mode: WrapMode) -> Color3 {
+        self.levels[0].lookup_bilinear(uv, wrap_mode)
+    }
+
+    /// Interpolates between the two levels whose pixels are closest in size to `footprint`, which is a width in
+    /// texture coordinates, and between the four pixels closest to `uv` within each.
+    pub fn lookup_trilinear(&self, uv: &Vector2, footprint: Real, wrap_mode: WrapMode) -> Color3 {
+        let size = usize::max(self.width(), self.height()) as Real;
+        let level = Real::clamp(Real::log2(footprint * size), 0.0, (self.levels.len() - 1) as Real);
+
+        let lower_level = level.floor() as usize;
+        let upper_level = usize::min(lower_level + 1, self.levels.len() - 1);
+        let fraction = level - lower_level as Real;
+
+        let lower_color = self.levels[lower_level].lookup_bilinear(uv, wrap_mode);
+
+        if fraction > 0.0 {
+            lower_color * (1.0 - fraction) + self.levels[upper_level].lookup_bilinear(uv, wrap_mode) * fraction
+        } else {
+            lower_color
+        }
+    }
+}
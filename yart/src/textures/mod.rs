@@ -0,0 +1,17 @@
+pub mod bump_map;
+pub mod channel_texture;
+pub mod checker_texture;
+pub mod constant_texture;
+pub mod gradient_texture;
+pub mod image_texture;
+pub mod marble_texture;
+pub mod mip_map;
+pub mod noise_texture;
+pub mod normal_map;
+pub mod normal_perturbation;
+pub mod texture;
+pub mod texture_cache;
+pub mod texture_coordinates;
+pub mod vertex_color_texture;
+pub mod voronoi_texture;
+pub mod wood_texture;
@@ -0,0 +1,61 @@
+use super::{
+    texture::{mix_textures, Texture},
+    texture_coordinates::{TextureCoordinates, TextureSpace},
+};
+use crate::{
+    common::Real,
+    math::{
+        color3::Color3,
+        noise::{fbm, turbulence, NoiseOctaves},
+    },
+};
+
+/// Which kind of fractal noise a [`NoiseTexture`] is.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NoiseKind {
+    /// Smooth cloudy noise, see [`fbm`].
+    Fbm,
+
+    /// Billowy noise with sharp creases, see [`turbulence`].
+    Turbulence,
+}
+
+/// Blends between two textures by fractal Perlin noise.
+#[derive(Debug)]
+pub struct NoiseTexture {
+    color1: Box<dyn Texture>,
+    color2: Box<dyn Texture>,
+
+    pub kind: NoiseKind,
+    pub space: TextureSpace,
+
+    /// The point is multiplied by `scale` before it's evaluated, so a larger scale gives finer noise.
+    pub scale: Real,
+    pub octaves: NoiseOctaves,
+}
+
+impl NoiseTexture {
+    pub fn new(color1: Box<dyn Texture>, color2: Box<dyn Texture>, kind: NoiseKind, space: TextureSpace) -> Self {
+        Self {
+            color1,
+            color2,
+            kind,
+            space,
+            scale: 1.0,
+            octaves: NoiseOctaves::default(),
+        }
+    }
+}
+
+impl Texture for NoiseTexture {
+    fn evaluate(&self, coordinates: &TextureCoordinates) -> Color3 {
+        let point = coordinates.point(self.space) * self.scale;
+
+        let amount = match self.kind {
+            NoiseKind::Fbm => 0.5 + 0.5 * fbm(&point, &self.octaves),
+            NoiseKind::Turbulence => turbulence(&point, &self.octaves),
+        };
+
+        mix_textures(self.color1.as_ref(), self.color2.as_ref(), amount, coordinates)
+    }
+}
@@ -0,0 +1,48 @@
+use super::{
+    normal_perturbation::{build_tangent_frame, NormalPerturbation},
+    texture::Texture,
+    texture_coordinates::TextureCoordinates,
+};
+use crate::{
+    common::Real,
+    geometries::surface_point::SurfacePoint,
+    math::{vector::Vector, vector3::Vector3},
+    normalize,
+};
+
+/// Replaces the shading normal with one stored in a texture, in the space of the surface's tangents. Red goes from
+/// `-1` to `1` along `u`, green from `-1` to `1` up the image, against `v`, and blue from `0` to `1` along the normal,
+/// as in glTF. The texture should be stored in linear rather than sRGB colors.
+#[derive(Debug)]
+pub struct NormalMap {
+    texture: Box<dyn Texture>,
+
+    /// Scales how far the normals are tilted, where zero leaves the surface flat and one tilts them as stored.
+    pub strength: Real,
+}
+
+impl NormalMap {
+    pub fn new(texture: Box<dyn Texture>) -> Self {
+        Self { texture, strength: 1.0 }
+    }
+}
+
+impl NormalPerturbation for NormalMap {
+    fn perturb_normal(&self, surface_point: &SurfacePoint, coordinates: &TextureCoordinates) -> Vector3 {
+        let normal = surface_point.shading_normal;
+        let (tangent, bitangent) = build_tangent_frame(&normal, &surface_point.dpdu, &surface_point.dpdv);
+
+        let color = self.texture.evaluate(coordinates);
+        let x = (color.r * 2.0 - 1.0) * self.strength;
+        let y = (color.g * 2.0 - 1.0) * self.strength;
+        let z = Real::max(color.b * 2.0 - 1.0, 0.0);
+
+        let perturbed = tangent * x + bitangent * y + normal * z;
+
+        if perturbed.length_squared() > 0.0 {
+            normalize!(perturbed)
+        } else {
+            normal
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::texture_coordinates::TextureCoordinates;
+use crate::{
+    common::EPSILON,
+    geometries::surface_point::SurfacePoint,
+    math::{vector::Vector, vector3::Vector3},
+    normalize,
+};
+use std::fmt::Debug;
+
+/// Changes the shading normal of a surface to add detail the geometry doesn't have, such as the grain of leather or
+/// the mortar between bricks. The geometric normal isn't changed.
+pub trait NormalPerturbation: Debug + Send + Sync {
+    /// Returns the perturbed shading normal, on the same side of the surface as `surface_point.shading_normal`.
+    fn perturb_normal(&self, surface_point: &SurfacePoint, coordinates: &TextureCoordinates) -> Vector3;
+}
+
+/// Bends a perturbed normal just far enough towards the viewer that it doesn't face away from them, when the normal it
+/// was perturbed from didn't. Normals facing away would reflect rays into the surface and shade the visible side
+/// as if it were lit from behind, which shows up as black spots along silhouettes.
+pub fn keep_facing_viewer(normal: &Vector3, unperturbed_normal: &Vector3, incoming_direction: &Vector3) -> Vector3 {
+    // Flip everything to the side of the unperturbed normal so that normals pointing into a geometry, as the sphere's
+    // do when hit from inside, are handled the same way.
+    let towards_viewer = if (incoming_direction ^ unperturbed_normal) <= 0.0 {
+        -incoming_direction
+    } else {
+        *incoming_direction
+    };
+
+    let cosine = normal ^ towards_viewer;
+
+    if cosine >= EPSILON {
+        return *normal;
+    }
+
+    normalize!(normal + towards_viewer * (EPSILON - cosine))
+}
+
+/// Builds unit tangents along `u` and against `v` that are perpendicular to `normal`, falling back to arbitrary ones
+/// when the surface doesn't define its tangents. Going against `v` follows the convention of normal maps, where `v`
+/// goes down the image and green points up it.
+pub fn build_tangent_frame(normal: &Vector3, dpdu: &Vector3, dpdv: &Vector3) -> (Vector3, Vector3) {
+    let projected_dpdu = dpdu - normal * (normal ^ dpdu);
+
+    let tangent = if projected_dpdu.length_squared() > 0.0 {
+        normalize!(projected_dpdu)
+    } else {
+        normalize!(Vector3::build_perpendicular_vector(normal))
+    };
+
+    let bitangent = normal % tangent;
+
+    if (bitangent ^ dpdv) > 0.0 {
+        (tangent, -bitangent)
+    } else {
+        (tangent, bitangent)
+    }
+}
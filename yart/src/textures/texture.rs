@@ -0,0 +1,32 @@
+use super::texture_coordinates::TextureCoordinates;
+use crate::{common::Real, math::color3::Color3};
+use std::fmt::Debug;
+
+/// A color that varies over a surface. Material parameters are textures so that they can be constant, looked up in
+/// an image or calculated.
+pub trait Texture: Debug + Send + Sync {
+    fn evaluate(&self, coordinates: &TextureCoordinates) -> Color3;
+
+    /// Evaluates the texture for a scalar parameter such as roughness, using the luminance of its color. Grey colors
+    /// are therefore their own value.
+    fn evaluate_scalar(&self, coordinates: &TextureCoordinates) -> Real {
+        self.evaluate(coordinates).luminance()
+    }
+}
+
+/// Blends from `first` at an amount of zero to `second` at an amount of one. A texture isn't evaluated when it doesn't
+/// contribute.
+pub fn mix_textures(
+    first: &dyn Texture,
+    second: &dyn Texture,
+    amount: Real,
+    coordinates: &TextureCoordinates,
+) -> Color3 {
+    if amount <= 0.0 {
+        first.evaluate(coordinates)
+    } else if amount >= 1.0 {
+        second.evaluate(coordinates)
+    } else {
+        first.evaluate(coordinates) * (1.0 - amount) + second.evaluate(coordinates) * amount
+    }
+}
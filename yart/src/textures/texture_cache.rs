@@ -0,0 +1,33 @@
+use super::mip_map::{ColorSpace, MipMap};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Keeps the images loaded while a scene is parsed, so that materials referring to the same file share one copy.
+#[derive(Debug, Default)]
+pub struct TextureCache {
+    mip_maps: HashMap<(PathBuf, Option<ColorSpace>), Arc<MipMap>>,
+}
+
+impl TextureCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads an image, or returns the copy loaded earlier with the same color space. Returns `None` if the image can't
+    /// be loaded.
+    pub fn load(&mut self, path: &Path, color_space: Option<ColorSpace>) -> Option<Arc<MipMap>> {
+        let key = (path.to_path_buf(), color_space);
+
+        if let Some(mip_map) = self.mip_maps.get(&key) {
+            return Some(mip_map.clone());
+        }
+
+        let mip_map = Arc::new(MipMap::from_file(path, color_space)?);
+        self.mip_maps.insert(key, mip_map.clone());
+
+        Some(mip_map)
+    }
+}
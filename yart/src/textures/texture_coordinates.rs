@@ -0,0 +1,77 @@
+use crate::{
+    common::Real,
+    geometries::{ray::Ray, surface_point::SurfacePoint},
+    math::{color3::Color3, vector::Vector, vector2::Vector2, vector3::Vector3},
+};
+
+/// Which coordinates a procedural texture is evaluated on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TextureSpace {
+    /// The texture coordinates of the surface, as the point `(u, v, 0)`.
+    Uv,
+
+    /// The hit position in the scene.
+    World,
+
+    /// The hit position in the space of the geometry, before it's transformed or moved.
+    Object,
+}
+
+/// Where a texture is looked up.
+#[derive(Debug, Copy, Clone)]
+pub struct TextureCoordinates {
+    pub position: Vector3,
+    pub object_position: Vector3,
+    pub uv: Vector2,
+
+    /// The width of the area around `uv` the texture should be averaged over, in texture coordinates. Zero when the
+    /// ray that hit the surface doesn't track its cone.
+    pub footprint: Real,
+
+    /// The vertex color of the surface, for meshes that have vertex colors.
+    pub vertex_color: Option<Color3>,
+}
+
+impl TextureCoordinates {
+    pub fn new(position: &Vector3, object_position: &Vector3, uv: &Vector2, footprint: Real) -> Self {
+        Self {
+            position: *position,
+            object_position: *object_position,
+            uv: *uv,
+            footprint,
+            vertex_color: None,
+        }
+    }
+
+    /// Creates the coordinates for a hit by `incoming_ray`. The footprint is the width of the ray's cone at the hit,
+    /// converted to texture coordinates with the tangents of the surface.
+    pub fn from_hit(hit_position: &Vector3, surface_point: &SurfacePoint, incoming_ray: &Ray) -> Self {
+        let cone_width = incoming_ray.cone_width_at((hit_position - incoming_ray.position()).length());
+        let tangent_length = Real::sqrt(surface_point.dpdu.length() * surface_point.dpdv.length());
+
+        let footprint = if tangent_length > 0.0 {
+            cone_width / tangent_length
+        } else {
+            0.0
+        };
+
+        Self {
+            vertex_color: surface_point.vertex_color,
+            ..Self::new(
+                hit_position,
+                &surface_point.object_position,
+                &surface_point.uv,
+                footprint,
+            )
+        }
+    }
+
+    /// Returns the point a procedural texture evaluated in `space` is evaluated at.
+    pub fn point(&self, space: TextureSpace) -> Vector3 {
+        match space {
+            TextureSpace::Uv => Vector3::new(self.uv.x, self.uv.y, 0.0),
+            TextureSpace::World => self.position,
+            TextureSpace::Object => self.object_position,
+        }
+    }
+}
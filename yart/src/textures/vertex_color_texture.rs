@@ -0,0 +1,23 @@
+use super::{texture::Texture, texture_coordinates::TextureCoordinates};
+use crate::math::color3::Color3;
+
+/// A texture that is the interpolated vertex color of the surface, such as the colors loaded from a PLY file. Surfaces
+/// without vertex colors use the fallback texture instead.
+#[derive(Debug)]
+pub struct VertexColorTexture {
+    fallback: Box<dyn Texture>,
+}
+
+impl VertexColorTexture {
+    pub fn new(fallback: Box<dyn Texture>) -> Self {
+        Self { fallback }
+    }
+}
+
+impl Texture for VertexColorTexture {
+    fn evaluate(&self, coordinates: &TextureCoordinates) -> Color3 {
+        coordinates
+            .vertex_color
+            .unwrap_or_else(|| self.fallback.evaluate(coordinates))
+    }
+}
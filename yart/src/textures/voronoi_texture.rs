@@ -0,0 +1,74 @@
+use super::{
+    texture::{mix_textures, Texture},
+    texture_coordinates::{TextureCoordinates, TextureSpace},
+};
+use crate::{
+    common::Real,
+    math::{color3::Color3, noise::worley_noise},
+};
+
+/// What a [`VoronoiTexture`] shows of the cells around its feature points.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VoronoiPattern {
+    /// Every cell is a random blend of the two textures.
+    Cells,
+
+    /// Blends from `color1` at the feature points to `color2` one unit away from them.
+    Distance,
+
+    /// The cells are `color1` with borders of `color2`.
+    Edges,
+}
+
+/// Cells around feature points scattered through space, like cobblestones, scales or foam. See
+/// [`crate::math::noise::worley_noise`].
+#[derive(Debug)]
+pub struct VoronoiTexture {
+    color1: Box<dyn Texture>,
+    color2: Box<dyn Texture>,
+
+    pub pattern: VoronoiPattern,
+    pub space: TextureSpace,
+
+    /// The point is multiplied by `scale` before it's evaluated, so a larger scale gives smaller cells.
+    pub scale: Real,
+
+    /// The width of the borders of [`VoronoiPattern::Edges`], relative to the size of the cells.
+    pub edge_width: Real,
+}
+
+impl VoronoiTexture {
+    pub fn new(
+        color1: Box<dyn Texture>,
+        color2: Box<dyn Texture>,
+        pattern: VoronoiPattern,
+        space: TextureSpace,
+    ) -> Self {
+        Self {
+            color1,
+            color2,
+            pattern,
+            space,
+            scale: 1.0,
+            edge_width: 0.05,
+        }
+    }
+}
+
+impl Texture for VoronoiTexture {
+    fn evaluate(&self, coordinates: &TextureCoordinates) -> Color3 {
+        let distances = worley_noise(&(coordinates.point(self.space) * self.scale));
+
+        let amount = match self.pattern {
+            VoronoiPattern::Cells => distances.cell_value,
+            VoronoiPattern::Distance => distances.closest,
+            VoronoiPattern::Edges => {
+                // Half way between two feature points the difference of the distances to them is zero.
+                let border_distance = distances.second_closest - distances.closest;
+                1.0 - Real::clamp(border_distance / self.edge_width, 0.0, 1.0)
+            }
+        };
+
+        mix_textures(self.color1.as_ref(), self.color2.as_ref(), amount, coordinates)
+    }
+}
@@ -0,0 +1,52 @@
+use super::{
+    texture::{mix_textures, Texture},
+    texture_coordinates::{TextureCoordinates, TextureSpace},
+};
+use crate::{
+    common::Real,
+    math::{
+        color3::Color3,
+        noise::{fbm, NoiseOctaves},
+    },
+};
+
+/// Growth rings around the `y` axis that fade from `color1` at their inside to `color2` at their outside, with noise
+/// so they aren't perfect circles.
+#[derive(Debug)]
+pub struct WoodTexture {
+    color1: Box<dyn Texture>,
+    color2: Box<dyn Texture>,
+
+    pub space: TextureSpace,
+
+    /// The point is multiplied by `scale` before it's evaluated, so the scale is the number of rings per unit.
+    pub scale: Real,
+    pub octaves: NoiseOctaves,
+
+    /// How far the rings are distorted, in rings.
+    pub turbulence: Real,
+}
+
+impl WoodTexture {
+    pub fn new(color1: Box<dyn Texture>, color2: Box<dyn Texture>, space: TextureSpace) -> Self {
+        Self {
+            color1,
+            color2,
+            space,
+            scale: 1.0,
+            octaves: NoiseOctaves::default(),
+            turbulence: 0.2,
+        }
+    }
+}
+
+impl Texture for WoodTexture {
+    fn evaluate(&self, coordinates: &TextureCoordinates) -> Color3 {
+        let point = coordinates.point(self.space) * self.scale;
+
+        let rings = Real::sqrt(point.x * point.x + point.z * point.z) + self.turbulence * fbm(&point, &self.octaves);
+        let amount = rings.rem_euclid(1.0);
+
+        mix_textures(self.color1.as_ref(), self.color2.as_ref(), amount, coordinates)
+    }
+}
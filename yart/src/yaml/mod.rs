@@ -1,8 +1,10 @@
 pub mod parse;
 pub mod parse_cameras;
 pub mod parse_config;
+pub mod parse_context;
 pub mod parse_geometries;
 pub mod parse_lights;
 pub mod parse_materials;
 pub mod parse_math;
 pub mod parse_miss_shaders;
+pub mod parse_textures;
@@ -1,8 +1,12 @@
 use super::{
-    parse_cameras::parse_camera, parse_geometries::parse_intersectable, parse_lights::parse_lights,
-    parse_materials::parse_materials, parse_miss_shaders::parse_miss_shader,
+    parse_cameras::parse_camera,
+    parse_context::ParseContext,
+    parse_geometries::{parse_definitions, parse_intersectable},
+    parse_lights::parse_lights,
+    parse_materials::parse_materials,
+    parse_miss_shaders::parse_miss_shader,
 };
-use crate::{errors::Result, scene::Scene, yaml::parse_config::parse_config};
+use crate::{errors::Result, scene::Scene, textures::texture_cache::TextureCache, yaml::parse_config::parse_config};
 use std::{
     fs::{self},
     path::Path,
@@ -13,27 +17,29 @@ pub fn load_scene(path: &Path) -> Result<Scene> {
     let yaml_data = fs::read_to_string(path).unwrap();
     let doc = YamlLoader::load_from_str(yaml_data.as_str()).unwrap();
 
-    let scene = parse_scene(&doc[0]);
-    scene
+    parse_scene(&doc[0])
 }
 
 fn parse_scene(node: &Yaml) -> Result<Scene> {
     let config = parse_config(&node["config"])?;
 
     let camera = parse_camera(&node["camera"]).unwrap();
-    let miss_shader = parse_miss_shader(&node["missShader"]).unwrap();
     let lights = parse_lights(&node["lights"]).unwrap();
-    let (materials, material_name_to_index_map) = parse_materials(&node["materials"]).unwrap();
+    let mut texture_cache = TextureCache::new();
+    let (materials, material_name_to_index_map) = parse_materials(&node["materials"], &mut texture_cache).unwrap();
 
-    let mut area_lights = Vec::new();
-    let root_geometry = parse_intersectable(&node["geometry"], &material_name_to_index_map, &mut area_lights).unwrap();
+    let mut context = ParseContext::new(materials, material_name_to_index_map, lights, texture_cache);
+
+    let miss_shader = parse_miss_shader(&node["missShader"], &mut context.area_lights).unwrap();
+    parse_definitions(&node["definitions"], &mut context).unwrap();
+    let root_geometry = parse_intersectable(&node["geometry"], &mut context).unwrap();
 
     Ok(Scene::new(
         config,
         camera,
-        materials,
-        lights,
-        area_lights,
+        context.materials,
+        context.lights,
+        context.area_lights,
         miss_shader,
         root_geometry,
     ))
@@ -1,14 +1,37 @@
-use super::parse_math::{parse_vector2u32, parse_vector3};
+use super::parse_math::{parse_optional_transformation, parse_vector2, parse_vector2u32, parse_vector3};
 use crate::{
-    cameras::{camera::Camera, perspective_camera::PerspectiveCamera},
-    yaml::parse_math::parse_real,
+    cameras::{
+        aperture::Aperture,
+        camera::Camera,
+        cubemap_camera::CubemapCamera,
+        equirectangular_camera::EquirectangularCamera,
+        exposure::Exposure,
+        fisheye_camera::{FisheyeCamera, FisheyeProjection},
+        omni_directional_stereo_camera::OmniDirectionalStereoCamera,
+        orthographic_camera::OrthographicCamera,
+        perspective_camera::PerspectiveCamera,
+        shutter::Shutter,
+    },
+    common::Real,
+    loaders::gltf_loader::{load_gltf, GltfProjection},
+    math::{vector::Vector, vector2::Vector2, vector3::Vector3},
+    normalize,
+    yaml::parse_math::{parse_real, parse_u32},
 };
+use std::path::Path;
 use yaml_rust::Yaml;
 
+#[allow(clippy::type_complexity, clippy::vec_init_then_push)]
 fn create_function_map() -> Vec<(&'static str, fn(&Yaml) -> Option<Box<dyn Camera>>)> {
     let mut map: Vec<(&'static str, fn(&Yaml) -> Option<Box<dyn Camera>>)> = Vec::new();
 
     map.push(("perspective", parse_perspective_camera));
+    map.push(("orthographic", parse_orthographic_camera));
+    map.push(("equirectangular", parse_equirectangular_camera));
+    map.push(("omniDirectionalStereo", parse_omni_directional_stereo_camera));
+    map.push(("cubemap", parse_cubemap_camera));
+    map.push(("fisheye", parse_fisheye_camera));
+    map.push(("gltf", parse_gltf_camera));
 
     map
 }
@@ -30,10 +53,26 @@ fn parse_perspective_camera(node: &Yaml) -> Option<Box<dyn Camera>> {
     let look_at = parse_vector3(&node["lookAt"])?;
     let up = parse_vector3(&node["up"])?;
 
-    let field_of_view = parse_real(&node["fov"])?;
     let screen_size = parse_vector2u32(&node["screenSize"])?;
     let subpixel_count = node["subpixelCount"].as_i64()? as u32;
 
+    // The sensor size is in millimeters and defaults to a full frame sensor.
+    let sensor_size = parse_vector2(&node["sensorSize"]).unwrap_or(Vector2::new(36.0, 24.0));
+
+    let field_of_view = parse_field_of_view(node, screen_size, &sensor_size)?;
+    let focal_length = sensor_size.y / (2.0 * Real::tan(field_of_view * 0.5));
+
+    let aperture = parse_aperture(node, focal_length);
+    let exposure = parse_exposure(node);
+    let vignetting = parse_real(&node["vignetting"]).unwrap_or(0.0);
+
+    // Focus on the look at point unless a distance or another point to focus on is given.
+    let focus_distance = parse_real(&node["focusDistance"])
+        .or_else(|| parse_focus_point_distance(&node["focusPoint"], &position, &look_at))
+        .unwrap_or_else(|| Vector3::distance(&position, &look_at));
+
+    let autofocus = node["autofocus"].as_bool().unwrap_or(false);
+
     Some(Box::new(PerspectiveCamera::new(
         &position,
         &look_at,
@@ -41,5 +80,216 @@ fn parse_perspective_camera(node: &Yaml) -> Option<Box<dyn Camera>> {
         subpixel_count,
         screen_size,
         field_of_view,
+        &aperture,
+        focus_distance,
+        autofocus,
+        &parse_shutter(&node["shutter"]),
+        exposure.as_ref(),
+        vignetting,
     )))
 }
+
+/// Parses the vertical field of view of a camera in radians. It's given either as an angle in degrees, measured
+/// vertically with `verticalFov`, horizontally with `horizontalFov` or diagonally with `diagonalFov`, or as a
+/// `focalLength` in millimeters in which case the vertical field of view covers the height of the sensor. The older
+/// `fov` key is still read as a vertical angle in radians.
+fn parse_field_of_view(node: &Yaml, screen_size: (u32, u32), sensor_size: &Vector2) -> Option<Real> {
+    let width = screen_size.0 as Real;
+    let height = screen_size.1 as Real;
+
+    let tangent_of_half_angle = |degrees: Real| Real::tan(degrees.to_radians() * 0.5);
+
+    if let Some(focal_length) = parse_real(&node["focalLength"]) {
+        Some(2.0 * Real::atan(sensor_size.y / (2.0 * focal_length)))
+    } else if let Some(degrees) = parse_real(&node["verticalFov"]) {
+        Some(degrees.to_radians())
+    } else if let Some(degrees) = parse_real(&node["horizontalFov"]) {
+        Some(2.0 * Real::atan(tangent_of_half_angle(degrees) * height / width))
+    } else if let Some(degrees) = parse_real(&node["diagonalFov"]) {
+        Some(2.0 * Real::atan(tangent_of_half_angle(degrees) * height / Real::sqrt(width * width + height * height)))
+    } else {
+        parse_real(&node["fov"])
+    }
+}
+
+/// Parses the physical exposure settings of a camera. They are only used when an `iso` is given in which case the
+/// `shutterSpeed`, in seconds, and `fStop` default to one.
+fn parse_exposure(node: &Yaml) -> Option<Exposure> {
+    let iso = parse_real(&node["iso"])?;
+    let shutter_speed = parse_real(&node["shutterSpeed"]).unwrap_or(1.0);
+    let f_number = parse_real(&node["fStop"]).unwrap_or(1.0);
+
+    Some(Exposure::new(iso, shutter_speed, f_number))
+}
+
+/// Parses the lens aperture of a camera. The size is given either directly with `apertureRadius`, in scene units, or
+/// with `fStop` relative to the focal length, in millimeters, assuming the scene is modeled in meters. Since `fStop` is
+/// also an exposure setting, it only sizes the aperture when `depthOfField` is enabled, and the camera is a pinhole
+/// camera otherwise.
+fn parse_aperture(node: &Yaml, focal_length: Real) -> Aperture {
+    let depth_of_field = node["depthOfField"].as_bool().unwrap_or(false);
+
+    let radius = parse_real(&node["apertureRadius"]).or_else(|| {
+        let f_stop = parse_real(&node["fStop"]).filter(|_| depth_of_field)?;
+        Some(focal_length * 0.001 / (2.0 * f_stop))
+    });
+
+    let Some(radius) = radius else {
+        return Aperture::pinhole();
+    };
+
+    let blade_count = parse_u32(&node["apertureBlades"]).unwrap_or(0);
+    let rotation = parse_real(&node["apertureRotation"]).unwrap_or(0.0).to_radians();
+
+    Aperture::new(radius, blade_count, rotation)
+}
+
+/// Calculates the distance along the view direction from the camera to the plane containing the focus point.
+fn parse_focus_point_distance(node: &Yaml, position: &Vector3, look_at: &Vector3) -> Option<Real> {
+    let focus_point = parse_vector3(node)?;
+    let view_direction = normalize!(look_at - position);
+
+    Some((focus_point - position) ^ view_direction)
+}
+
+fn parse_orthographic_camera(node: &Yaml) -> Option<Box<dyn Camera>> {
+    let position = parse_vector3(&node["position"])?;
+    let look_at = parse_vector3(&node["lookAt"])?;
+    let up = parse_vector3(&node["up"])?;
+
+    let ortho_size = parse_vector2(&node["orthoSize"])?;
+    let screen_size = parse_vector2u32(&node["screenSize"])?;
+    let subpixel_count = node["subpixelCount"].as_i64()? as u32;
+
+    Some(Box::new(OrthographicCamera::new(
+        &position,
+        &look_at,
+        &up,
+        &ortho_size,
+        subpixel_count,
+        screen_size,
+        &parse_shutter(&node["shutter"]),
+    )))
+}
+
+fn parse_gltf_camera(node: &Yaml) -> Option<Box<dyn Camera>> {
+    let transformation = parse_optional_transformation(&node["transformation"])?;
+    let scene = load_gltf(Path::new(node["file"].as_str()?), &transformation)?;
+    let camera = scene.cameras.get(parse_u32(&node["camera"]).unwrap_or(0) as usize)?;
+
+    let screen_size = parse_vector2u32(&node["screenSize"])?;
+    let subpixel_count = node["subpixelCount"].as_i64()? as u32;
+    let shutter = parse_shutter(&node["shutter"]);
+
+    match camera.projection {
+        GltfProjection::Perspective { y_fov, .. } => Some(Box::new(PerspectiveCamera::new(
+            &camera.position,
+            &camera.look_at,
+            &camera.up,
+            subpixel_count,
+            screen_size,
+            y_fov,
+            &Aperture::pinhole(),
+            1.0,
+            false,
+            &shutter,
+            parse_exposure(node).as_ref(),
+            parse_real(&node["vignetting"]).unwrap_or(0.0),
+        ))),
+        GltfProjection::Orthographic { x_mag, y_mag } => Some(Box::new(OrthographicCamera::new(
+            &camera.position,
+            &camera.look_at,
+            &camera.up,
+            &Vector2::new(x_mag * 2.0, y_mag * 2.0),
+            subpixel_count,
+            screen_size,
+            &shutter,
+        ))),
+    }
+}
+
+fn parse_equirectangular_camera(node: &Yaml) -> Option<Box<dyn Camera>> {
+    let position = parse_vector3(&node["position"])?;
+    let look_at = parse_vector3(&node["lookAt"])?;
+    let up = parse_vector3(&node["up"])?;
+
+    let screen_size = parse_vector2u32(&node["screenSize"])?;
+    let subpixel_count = node["subpixelCount"].as_i64()? as u32;
+
+    Some(Box::new(EquirectangularCamera::new(
+        &position,
+        &look_at,
+        &up,
+        subpixel_count,
+        screen_size,
+        &parse_shutter(&node["shutter"]),
+    )))
+}
+
+fn parse_omni_directional_stereo_camera(node: &Yaml) -> Option<Box<dyn Camera>> {
+    let position = parse_vector3(&node["position"])?;
+    let look_at = parse_vector3(&node["lookAt"])?;
+    let up = parse_vector3(&node["up"])?;
+
+    let screen_size = parse_vector2u32(&node["screenSize"])?;
+    let subpixel_count = node["subpixelCount"].as_i64()? as u32;
+
+    // Default to the average adult interpupillary distance assuming the scene is modeled in meters.
+    let interpupillary_distance = parse_real(&node["interpupillaryDistance"]).unwrap_or(0.064);
+
+    Some(Box::new(OmniDirectionalStereoCamera::new(
+        &position,
+        &look_at,
+        &up,
+        subpixel_count,
+        screen_size,
+        &parse_shutter(&node["shutter"]),
+        interpupillary_distance,
+    )))
+}
+
+fn parse_cubemap_camera(node: &Yaml) -> Option<Box<dyn Camera>> {
+    let position = parse_vector3(&node["position"])?;
+
+    let screen_size = parse_vector2u32(&node["screenSize"])?;
+    let subpixel_count = node["subpixelCount"].as_i64()? as u32;
+
+    Some(Box::new(CubemapCamera::new(
+        &position,
+        subpixel_count,
+        screen_size,
+        &parse_shutter(&node["shutter"]),
+    )))
+}
+
+fn parse_fisheye_camera(node: &Yaml) -> Option<Box<dyn Camera>> {
+    let position = parse_vector3(&node["position"])?;
+    let look_at = parse_vector3(&node["lookAt"])?;
+    let up = parse_vector3(&node["up"])?;
+
+    let field_of_view = parse_real(&node["fov"]).unwrap_or(180.0).to_radians();
+    let screen_size = parse_vector2u32(&node["screenSize"])?;
+    let subpixel_count = node["subpixelCount"].as_i64()? as u32;
+
+    let projection = match node["projection"].as_str() {
+        Some("equisolid") => FisheyeProjection::Equisolid,
+        Some("equidistant") | None => FisheyeProjection::Equidistant,
+        Some(_) => return None,
+    };
+
+    Some(Box::new(FisheyeCamera::new(
+        &position,
+        &look_at,
+        &up,
+        subpixel_count,
+        screen_size,
+        field_of_view,
+        projection,
+        &parse_shutter(&node["shutter"]),
+    )))
+}
+
+/// Parses the `[open, close]` interval of a camera's shutter. Without one the shutter is only open at time zero.
+fn parse_shutter(node: &Yaml) -> Shutter {
+    parse_vector2(node).map(|x| Shutter::new(x.x, x.y)).unwrap_or_default()
+}
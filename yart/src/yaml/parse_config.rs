@@ -1,7 +1,8 @@
 use crate::{
     errors::Error,
-    math::vector2::Vector2,
-    yaml::parse_math::{parse_u32, parse_vector2},
+    math::{vector2::Vector2, vector4::Vector4},
+    rendering::RenderRegion,
+    yaml::parse_math::{parse_u32, parse_vector2, parse_vector4},
 };
 use yaml_rust::Yaml;
 
@@ -9,13 +10,36 @@ use yaml_rust::Yaml;
 pub struct Config {
     iterations: u32,
     color_clamp: Vector2,
+    output_file: String,
+
+    /// The region to render as `[x, y, width, height]` in pixels.
+    render_region: Option<Vector4>,
+
+    /// The region to render as `[minimum x, minimum y, maximum x, maximum y]` in normalized image coordinates.
+    render_border: Option<Vector4>,
+
+    crop_to_render_region: bool,
+    resume: bool,
 }
 
 impl Config {
-    pub fn new(iterations: u32, color_clamp: &Vector2) -> Self {
+    pub fn new(
+        iterations: u32,
+        color_clamp: &Vector2,
+        output_file: &str,
+        render_region: Option<&Vector4>,
+        render_border: Option<&Vector4>,
+        crop_to_render_region: bool,
+        resume: bool,
+    ) -> Self {
         Self {
             iterations,
             color_clamp: *color_clamp,
+            output_file: output_file.to_string(),
+            render_region: render_region.copied(),
+            render_border: render_border.copied(),
+            crop_to_render_region,
+            resume,
         }
     }
 
@@ -26,6 +50,45 @@ impl Config {
     pub fn color_clamp(&self) -> Vector2 {
         self.color_clamp
     }
+
+    pub fn output_file(&self) -> &str {
+        &self.output_file
+    }
+
+    /// Returns the region of an image of the given size that should be rendered. This is the whole image unless a
+    /// render region or render border is configured.
+    pub fn render_region(&self, screen_size: (u32, u32)) -> RenderRegion {
+        let region = if let Some(region) = self.render_region {
+            RenderRegion::new(
+                region.x.max(0.0) as u32,
+                region.y.max(0.0) as u32,
+                region.z.max(0.0) as u32,
+                region.w.max(0.0) as u32,
+            )
+        } else if let Some(border) = self.render_border {
+            RenderRegion::from_border(
+                &Vector2::new(border.x, border.y),
+                &Vector2::new(border.z, border.w),
+                screen_size,
+            )
+        } else {
+            RenderRegion::full_frame(screen_size)
+        };
+
+        region.clamp_to_screen(screen_size)
+    }
+
+    /// When set only the render region is written to the output file. Otherwise the output is the size of the whole
+    /// image with the pixels outside of the render region left transparent.
+    pub fn crop_to_render_region(&self) -> bool {
+        self.crop_to_render_region
+    }
+
+    /// When set the pixels outside of the render region are taken from the existing output file so that the region is
+    /// updated in place.
+    pub fn resume(&self) -> bool {
+        self.resume
+    }
 }
 
 pub fn parse_config(node: &Yaml) -> Result<Config, Error> {
@@ -35,5 +98,21 @@ pub fn parse_config(node: &Yaml) -> Result<Config, Error> {
     let color_clamp = parse_vector2(&node["colorClamp"])
         .ok_or_else(|| Error::from_yaml_parser("colorClamp", Some(node.clone()), None))?;
 
-    Ok(Config::new(iterations, &color_clamp))
+    let output_file = node["outputFile"].as_str().unwrap_or("test.png");
+
+    let render_region = parse_vector4(&node["renderRegion"]);
+    let render_border = parse_vector4(&node["renderBorder"]);
+
+    let crop_to_render_region = node["cropToRenderRegion"].as_bool().unwrap_or(false);
+    let resume = node["resume"].as_bool().unwrap_or(false);
+
+    Ok(Config::new(
+        iterations,
+        &color_clamp,
+        output_file,
+        render_region.as_ref(),
+        render_border.as_ref(),
+        crop_to_render_region,
+        resume,
+    ))
 }
@@ -0,0 +1,59 @@
+use crate::{
+    geometries::{area_light::AreaLight, intersectable::Intersectable},
+    lights::light::Light,
+    materials::material::{Material, MaterialIndex},
+    textures::texture_cache::TextureCache,
+};
+use std::{collections::HashMap, sync::Arc};
+
+/// The state shared by the parsers while a scene is being built. Geometry parsers can look up materials by name and
+/// add materials and lights of their own, such as those defined by the MTL files of loaded meshes.
+pub struct ParseContext {
+    pub materials: Vec<Box<dyn Material>>,
+    pub material_name_to_index_map: HashMap<String, MaterialIndex>,
+    pub lights: Vec<Box<dyn Light>>,
+    pub area_lights: Vec<Box<dyn AreaLight>>,
+
+    /// Named geometry that can be placed in the scene any number of times with instances.
+    pub definitions: HashMap<String, Arc<dyn Intersectable>>,
+
+    /// The images loaded for the textures of materials so far.
+    pub texture_cache: TextureCache,
+}
+
+impl ParseContext {
+    pub fn new(
+        materials: Vec<Box<dyn Material>>,
+        material_name_to_index_map: HashMap<String, MaterialIndex>,
+        lights: Vec<Box<dyn Light>>,
+        texture_cache: TextureCache,
+    ) -> Self {
+        Self {
+            materials,
+            material_name_to_index_map,
+            lights,
+            area_lights: Vec::new(),
+            definitions: HashMap::new(),
+            texture_cache,
+        }
+    }
+
+    /// Looks up a material by name, falling back to the default material if there isn't one with that name.
+    pub fn material_index(&self, name: &str) -> MaterialIndex {
+        self.find_material_index(name).unwrap_or(0 as MaterialIndex)
+    }
+
+    pub fn find_material_index(&self, name: &str) -> Option<MaterialIndex> {
+        self.material_name_to_index_map.get(name).copied()
+    }
+
+    /// Adds a material that is only referred to by its index, such as one defined by a file a mesh is loaded from, and
+    /// returns the index. Only materials defined in the scene can be looked up by name, so that the materials of one
+    /// file can't be picked up by another file that happens to use the same names.
+    pub fn add_material(&mut self, material: Box<dyn Material>) -> MaterialIndex {
+        let index = self.materials.len() as MaterialIndex;
+        self.materials.push(material);
+
+        index
+    }
+}
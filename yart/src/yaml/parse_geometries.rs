@@ -1,16 +1,58 @@
-use super::parse_math::parse_vector3;
+use super::{
+    parse_context::ParseContext,
+    parse_math::{parse_optional_transformation, parse_transformation, parse_vector3},
+};
 use crate::{
+    common::Real,
     geometries::{
-        area_light::AreaLight, bounding_box::BoundingBox,
-        bounding_box_hierarchy::build_bounding_box_hierarchy_split_by_long_axis, bounding_geometry::BoundingGeometry,
-        bounding_volume::BoundingVolume, geometry::Geometry, intersectable::Intersectable,
-        intersectable_collection::IntersectableCollection, parallelogram::Parallelogram, plane::Plane, sphere::Sphere,
+        bounding_box::BoundingBox,
+        bounding_box_hierarchy::{build_bounding_box_hierarchy, BoundingBoxHierarchyOptions},
+        bounding_geometry::BoundingGeometry,
+        bounding_volume::BoundingVolume,
+        geometry::Geometry,
+        instance::Instance,
+        intersectable::Intersectable,
+        intersectable_collection::IntersectableCollection,
+        mesh_triangle::MeshTriangle,
+        moving_intersectable::MovingIntersectable,
+        parallelogram::Parallelogram,
+        plane::Plane,
+        sphere::Sphere,
+        transformed::Transformed,
         triangle::Triangle,
+        triangle_mesh::TriangleMesh,
+    },
+    lights::{directional_light::DirectionalLight, light::Light, point_light::PointLight, spot_light::SpotLight},
+    loaders::{
+        gltf_loader::{load_gltf, GltfImage, GltfLight, GltfLightType, GltfMaterial},
+        indexed_mesh::IndexedMesh,
+        mtl_loader::{load_mtl, MtlMaterial},
+        obj_loader::load_obj,
+        ply_loader::load_ply,
+        smooth_normals::generate_smooth_normals,
+        stl_loader::load_stl,
+    },
+    materials::{
+        emissive_material::EmissiveMaterial,
+        lambertian_material::LambertianMaterial,
+        material::{Material, MaterialIndex},
+        metallic_roughness_material::MetallicRoughnessMaterial,
+        normal_mapped_material::NormalMappedMaterial,
+        refractive_material::RefractiveMaterial,
+    },
+    math::{color3::Color3, matrix4::Matrix4},
+    textures::{
+        channel_texture::ChannelTexture,
+        constant_texture::ConstantTexture,
+        image_texture::{ImageTexture, TextureFilter},
+        mip_map::{ColorSpace, MipMap, WrapMode},
+        normal_map::NormalMap,
+        texture::Texture,
+        texture_cache::TextureCache,
     },
-    materials::material::MaterialIndex,
-    yaml::parse_math::parse_real,
+    yaml::parse_math::{parse_real, parse_u16, parse_u32},
 };
-use std::collections::HashMap;
+use std::{path::Path, sync::Arc};
 use yaml_rust::Yaml;
 
 enum GeometryEnum {
@@ -18,14 +60,9 @@ enum GeometryEnum {
     Geometry(Box<dyn Geometry>),
 }
 
-fn create_intersectable_function_map() -> Vec<(
-    &'static str,
-    fn(&Yaml, &HashMap<String, MaterialIndex>, &mut Vec<Box<dyn AreaLight>>) -> Option<GeometryEnum>,
-)> {
-    let mut map: Vec<(
-        &'static str,
-        fn(&Yaml, &HashMap<String, MaterialIndex>, &mut Vec<Box<dyn AreaLight>>) -> Option<GeometryEnum>,
-    )> = Vec::new();
+#[allow(clippy::type_complexity, clippy::vec_init_then_push)]
+fn create_intersectable_function_map() -> Vec<(&'static str, fn(&Yaml, &mut ParseContext) -> Option<GeometryEnum>)> {
+    let mut map: Vec<(&'static str, fn(&Yaml, &mut ParseContext) -> Option<GeometryEnum>)> = Vec::new();
 
     map.push(("sphere", parse_sphere));
     map.push(("plane", parse_plane));
@@ -34,122 +71,81 @@ fn create_intersectable_function_map() -> Vec<(
     map.push(("boundingGeometry", parse_bounding_geometry));
     map.push(("collection", parse_intersectable_collection));
     map.push(("boundingBoxHierarchy", parse_bounding_box_hierarchy));
+    map.push(("moving", parse_moving_intersectable));
+    map.push(("transformed", parse_transformed));
+    map.push(("instance", parse_instance));
+    map.push(("triangleMeshObj", parse_triangle_mesh_obj));
+    map.push(("triangleMeshPly", parse_triangle_mesh_ply));
+    map.push(("triangleMeshStl", parse_triangle_mesh_stl));
+    map.push(("gltf", parse_gltf));
 
     map
 }
 
-pub fn parse_intersectable(
-    node: &Yaml,
-    material_name_to_index_map: &HashMap<String, MaterialIndex>,
-    area_lights: &mut Vec<Box<dyn AreaLight>>,
-) -> Option<Box<dyn Intersectable>> {
+pub fn parse_intersectable(node: &Yaml, context: &mut ParseContext) -> Option<Box<dyn Intersectable>> {
     let mut found_geometry_enum: Option<GeometryEnum> = None;
 
     for (name, function) in create_intersectable_function_map() {
         let child_node = &node[name];
 
         if !child_node.is_badvalue() {
-            found_geometry_enum = function(child_node, material_name_to_index_map, area_lights);
+            found_geometry_enum = function(child_node, context);
         }
     }
 
-    match found_geometry_enum {
-        Some(geometry_enum) => match geometry_enum {
-            GeometryEnum::Intersectable(intersectable) => Some(intersectable),
-            GeometryEnum::Geometry(geometry) => Some(geometry as Box<dyn Intersectable>),
-        },
-        None => None,
-    }
+    found_geometry_enum.map(|geometry_enum| match geometry_enum {
+        GeometryEnum::Intersectable(intersectable) => intersectable,
+        GeometryEnum::Geometry(geometry) => geometry as Box<dyn Intersectable>,
+    })
 }
 
-fn parse_intersectables(
-    node: &Yaml,
-    material_name_to_index_map: &HashMap<String, MaterialIndex>,
-    area_lights: &mut Vec<Box<dyn AreaLight>>,
-) -> Option<Vec<Box<dyn Intersectable>>> {
-    let mut intersectables = Vec::new();
-
+/// Parses the named geometry that instances refer to. Definitions are parsed in order so that they can instance the
+/// definitions before them.
+pub fn parse_definitions(node: &Yaml, context: &mut ParseContext) -> Option<()> {
     if !node.is_badvalue() && node.is_array() {
         for child_node in node.as_vec()? {
-            let maybe_intersectable = parse_intersectable(child_node, material_name_to_index_map, area_lights);
+            let name = child_node["name"].as_str()?;
+            let geometry = parse_intersectable(&child_node["geometry"], context)?;
 
-            if maybe_intersectable.is_some() {
-                intersectables.push(maybe_intersectable.unwrap());
-            }
+            context.definitions.insert(name.to_string(), Arc::from(geometry));
         }
     }
 
-    Some(intersectables)
+    Some(())
 }
 
-fn create_geometry_function_map() -> Vec<(
-    &'static str,
-    fn(&Yaml, &HashMap<String, MaterialIndex>, &mut Vec<Box<dyn AreaLight>>) -> Option<GeometryEnum>,
-)> {
-    let mut map: Vec<(
-        &'static str,
-        fn(&Yaml, &HashMap<String, MaterialIndex>, &mut Vec<Box<dyn AreaLight>>) -> Option<GeometryEnum>,
-    )> = Vec::new();
-
-    map.push(("sphere", parse_sphere));
-    map.push(("plane", parse_plane));
-    map.push(("triangle", parse_triangle));
-    map.push(("parallelogram", parse_parallelogram));
-
-    map
-}
-
-fn parse_geometry(
-    node: &Yaml,
-    material_name_to_index_map: &HashMap<String, MaterialIndex>,
-    area_lights: &mut Vec<Box<dyn AreaLight>>,
-) -> Option<Box<dyn Geometry>> {
-    let mut found_geometry_enum: Option<GeometryEnum> = None;
-
-    for (name, function) in create_geometry_function_map() {
-        let child_node = &node[name];
+fn parse_intersectables(node: &Yaml, context: &mut ParseContext) -> Option<Vec<Box<dyn Intersectable>>> {
+    let mut intersectables = Vec::new();
 
-        if !child_node.is_badvalue() {
-            found_geometry_enum = function(child_node, material_name_to_index_map, area_lights);
+    if !node.is_badvalue() && node.is_array() {
+        for child_node in node.as_vec()? {
+            if let Some(intersectable) = parse_intersectable(child_node, context) {
+                intersectables.push(intersectable);
+            }
         }
     }
 
-    match found_geometry_enum {
-        Some(geometry_enum) => match geometry_enum {
-            GeometryEnum::Intersectable(_) => None,
-            GeometryEnum::Geometry(geometry) => Some(geometry),
-        },
-        None => None,
-    }
+    Some(intersectables)
 }
 
-fn parse_sphere(
-    node: &Yaml,
-    material_name_to_index_map: &HashMap<String, MaterialIndex>,
-    _area_lights: &mut Vec<Box<dyn AreaLight>>,
-) -> Option<GeometryEnum> {
+fn parse_sphere(node: &Yaml, context: &mut ParseContext) -> Option<GeometryEnum> {
     let material_name = node["material"].as_str()?;
 
     let position = parse_vector3(&node["position"])?;
     let radius = parse_real(&node["radius"])?;
+    let motion = parse_vector3(&node["motion"]).unwrap_or_default();
 
-    let material_index = match material_name_to_index_map.get(material_name) {
-        Some(x) => *x,
-        None => 0 as MaterialIndex,
-    };
+    let material_index = context.material_index(material_name);
 
-    Some(GeometryEnum::Geometry(Box::new(Sphere::new(
+    Some(GeometryEnum::Geometry(Box::new(Sphere::new_moving(
         &position,
+        &motion,
         radius,
         material_index,
     ))))
 }
 
-fn parse_plane(
-    node: &Yaml,
-    material_name_to_index_map: &HashMap<String, MaterialIndex>,
-    _area_lights: &mut Vec<Box<dyn AreaLight>>,
-) -> Option<GeometryEnum> {
+fn parse_plane(node: &Yaml, context: &mut ParseContext) -> Option<GeometryEnum> {
     let material_name = node["material"].as_str()?;
 
     let normal = parse_vector3(&node["normal"])?;
@@ -157,10 +153,7 @@ fn parse_plane(
     let maybe_distance = parse_real(&node["distance"]);
     let maybe_point = parse_vector3(&node["point"]);
 
-    let material_index = match material_name_to_index_map.get(material_name) {
-        Some(x) => *x,
-        None => 0 as MaterialIndex,
-    };
+    let material_index = context.material_index(material_name);
 
     if let Some(distance) = maybe_distance {
         Some(GeometryEnum::Geometry(Box::new(Plane::new(
@@ -168,22 +161,12 @@ fn parse_plane(
             distance,
             material_index,
         ))))
-    } else if let Some(point) = maybe_point {
-        Some(GeometryEnum::Geometry(Box::new(Plane::from_point(
-            &normal,
-            &point,
-            material_index,
-        ))))
     } else {
-        None
+        maybe_point.map(|point| GeometryEnum::Geometry(Box::new(Plane::from_point(&normal, &point, material_index))))
     }
 }
 
-fn parse_triangle(
-    node: &Yaml,
-    material_name_to_index_map: &HashMap<String, MaterialIndex>,
-    _area_lights: &mut Vec<Box<dyn AreaLight>>,
-) -> Option<GeometryEnum> {
+fn parse_triangle(node: &Yaml, context: &mut ParseContext) -> Option<GeometryEnum> {
     let material_name = node["material"].as_str()?;
 
     let vertex0 = parse_vector3(&node["vertex0"])?;
@@ -194,41 +177,38 @@ fn parse_triangle(
     let maybe_normal1 = parse_vector3(&node["normal1"]);
     let maybe_normal2 = parse_vector3(&node["normal2"]);
 
-    let material_index = match material_name_to_index_map.get(material_name) {
-        Some(x) => *x,
-        None => 0 as MaterialIndex,
-    };
+    let motion = parse_vector3(&node["motion"]).unwrap_or_default();
+
+    let material_index = context.material_index(material_name);
 
-    if maybe_normal0.is_some() && maybe_normal1.is_some() && maybe_normal2.is_some() {
-        Some(GeometryEnum::Geometry(Box::new(Triangle::new(
+    if let (Some(normal0), Some(normal1), Some(normal2)) = (maybe_normal0, maybe_normal1, maybe_normal2) {
+        Some(GeometryEnum::Geometry(Box::new(Triangle::new_moving(
             &vertex0,
             &vertex1,
             &vertex2,
-            &maybe_normal0.unwrap(),
-            &maybe_normal1.unwrap(),
-            &maybe_normal2.unwrap(),
+            &normal0,
+            &normal1,
+            &normal2,
+            &motion,
             material_index,
         ))))
     } else {
         let normal = Triangle::calculate_face_normal(&vertex0, &vertex1);
 
-        Some(GeometryEnum::Geometry(Box::new(Triangle::new(
+        Some(GeometryEnum::Geometry(Box::new(Triangle::new_moving(
             &vertex0,
             &vertex1,
             &vertex2,
             &normal,
             &normal,
             &normal,
+            &motion,
             material_index,
         ))))
     }
 }
 
-fn parse_parallelogram(
-    node: &Yaml,
-    material_name_to_index_map: &HashMap<String, MaterialIndex>,
-    area_lights: &mut Vec<Box<dyn AreaLight>>,
-) -> Option<GeometryEnum> {
+fn parse_parallelogram(node: &Yaml, context: &mut ParseContext) -> Option<GeometryEnum> {
     let material_name = node["material"].as_str()?;
     let area_light = node["areaLight"].as_bool().unwrap_or(false);
 
@@ -236,27 +216,20 @@ fn parse_parallelogram(
     let edge1 = parse_vector3(&node["edge1"])?;
     let edge2 = parse_vector3(&node["edge2"])?;
 
-    let material_index = match material_name_to_index_map.get(material_name) {
-        Some(x) => *x,
-        None => 0 as MaterialIndex,
-    };
+    let material_index = context.material_index(material_name);
 
     let parallelogram = Box::new(Parallelogram::new(&position, &edge1, &edge2, material_index));
 
     if area_light {
-        area_lights.push(parallelogram.clone());
+        context.area_lights.push(parallelogram.clone());
     }
 
     Some(GeometryEnum::Geometry(parallelogram))
 }
 
-fn parse_bounding_geometry(
-    node: &Yaml,
-    material_name_to_index_map: &HashMap<String, MaterialIndex>,
-    area_lights: &mut Vec<Box<dyn AreaLight>>,
-) -> Option<GeometryEnum> {
+fn parse_bounding_geometry(node: &Yaml, context: &mut ParseContext) -> Option<GeometryEnum> {
     let bounding_volume = parse_bounding_volume(&node["boundingVolume"])?;
-    let child = parse_intersectable(&node["child"], material_name_to_index_map, area_lights)?;
+    let child = parse_intersectable(&node["child"], context)?;
 
     // TODO: Enable auto calculation of bounding box if a bounding volume is not provided.
 
@@ -266,30 +239,383 @@ fn parse_bounding_geometry(
     ))))
 }
 
-fn parse_intersectable_collection(
-    node: &Yaml,
-    material_name_to_index_map: &HashMap<String, MaterialIndex>,
-    area_lights: &mut Vec<Box<dyn AreaLight>>,
-) -> Option<GeometryEnum> {
-    let children = parse_intersectables(&node["children"], material_name_to_index_map, area_lights)?;
+fn parse_intersectable_collection(node: &Yaml, context: &mut ParseContext) -> Option<GeometryEnum> {
+    let children = parse_intersectables(&node["children"], context)?;
 
     Some(GeometryEnum::Intersectable(Box::new(IntersectableCollection::new(
         children,
     ))))
 }
 
-fn parse_bounding_box_hierarchy(
-    node: &Yaml,
-    material_name_to_index_map: &HashMap<String, MaterialIndex>,
-    area_lights: &mut Vec<Box<dyn AreaLight>>,
-) -> Option<GeometryEnum> {
-    let children = parse_intersectables(&node["children"], material_name_to_index_map, area_lights)?;
+fn parse_bounding_box_hierarchy(node: &Yaml, context: &mut ParseContext) -> Option<GeometryEnum> {
+    let children = parse_intersectables(&node["children"], context)?;
+
+    let default_options = BoundingBoxHierarchyOptions::default();
+    let options = BoundingBoxHierarchyOptions::new(
+        parse_u32(&node["maximumLeafSize"]).map_or(default_options.maximum_leaf_size, |x| x as usize),
+        parse_u16(&node["maximumDepth"]).unwrap_or(default_options.maximum_depth),
+        parse_u32(&node["binCount"]).map_or(default_options.bin_count, |x| x as usize),
+    );
+
+    Some(GeometryEnum::Intersectable(build_bounding_box_hierarchy(
+        children, &options,
+    )))
+}
+
+fn parse_moving_intersectable(node: &Yaml, context: &mut ParseContext) -> Option<GeometryEnum> {
+    let motion = parse_vector3(&node["motion"])?;
+    let child = parse_intersectable(&node["child"], context)?;
+
+    Some(GeometryEnum::Intersectable(Box::new(MovingIntersectable::new(
+        &motion, child,
+    ))))
+}
+
+fn parse_transformed(node: &Yaml, context: &mut ParseContext) -> Option<GeometryEnum> {
+    let transformation = parse_transformation(&node["transformation"])?;
+    let child = parse_intersectable(&node["child"], context)?;
+
+    Some(GeometryEnum::Intersectable(Box::new(Transformed::new(
+        &transformation,
+        child,
+    )?)))
+}
+
+fn parse_instance(node: &Yaml, context: &mut ParseContext) -> Option<GeometryEnum> {
+    let definition = context.definitions.get(node["definition"].as_str()?)?;
+    let transformation = parse_optional_transformation(&node["transformation"])?;
+
+    // Instances keep the materials of their definition unless they're given one, which has to exist.
+    let material_index_override = match node["material"].as_str() {
+        Some(name) => Some(context.find_material_index(name)?),
+        None => None,
+    };
+
+    Some(GeometryEnum::Intersectable(Box::new(Instance::new(
+        definition,
+        &transformation,
+        material_index_override,
+    )?)))
+}
+
+/// Parses the optional `transformation` of a mesh. Meshes have their transformation applied to their vertices when
+/// loaded so that their bounding volume hierarchy is built in world space.
+fn parse_mesh_transformation(node: &Yaml) -> Option<Matrix4> {
+    parse_optional_transformation(&node["transformation"])
+}
+
+fn parse_triangle_mesh_obj(node: &Yaml, context: &mut ParseContext) -> Option<GeometryEnum> {
+    let material_name = node["material"].as_str()?;
+    let obj_file = Path::new(node["objFile"].as_str()?);
+
+    let mut model = load_obj(obj_file)?;
+    model.buffers.transform(&parse_mesh_transformation(node)?)?;
+
+    let default_material_index = context.material_index(material_name);
+
+    // MTL files are referenced relative to the OBJ file.
+    let obj_directory = obj_file.parent().unwrap_or(Path::new(""));
+    let mtl_materials = model
+        .material_libraries
+        .iter()
+        .filter_map(|library| load_mtl(&obj_directory.join(library)))
+        .flatten()
+        .collect::<Vec<_>>();
+
+    // Materials defined in the scene take precedence over the MTL definitions so that meshes can be restyled without
+    // editing their files. The MTL materials are only known to this mesh.
+    let material_indices = model
+        .material_names
+        .iter()
+        .map(|name| {
+            context
+                .find_material_index(name)
+                .unwrap_or_else(|| match mtl_materials.iter().find(|x| x.name == *name) {
+                    Some(mtl_material) => context.add_material(create_material_from_mtl(mtl_material)),
+                    None => default_material_index,
+                })
+        })
+        .collect::<Vec<_>>();
+
+    let buffers = Arc::new(model.buffers);
+
+    // A mesh has a single material, so the triangles are split into a mesh per material sharing the same buffers.
+    let mut triangles_by_material: Vec<(MaterialIndex, Vec<MeshTriangle>)> = Vec::new();
+
+    for triangle in &model.triangles {
+        let material_index = triangle
+            .material
+            .map_or(default_material_index, |x| material_indices[x]);
+        let mesh_triangle = MeshTriangle::new(triangle.position_indices, triangle.normal_indices, triangle.uv_indices);
+
+        match triangles_by_material.iter_mut().find(|(x, _)| *x == material_index) {
+            Some((_, triangles)) => triangles.push(mesh_triangle),
+            None => triangles_by_material.push((material_index, vec![mesh_triangle])),
+        }
+    }
+
+    let mut meshes = triangles_by_material
+        .into_iter()
+        .map(|(material_index, triangles)| {
+            Box::new(TriangleMesh::new(&buffers, triangles, material_index)) as Box<dyn Intersectable>
+        })
+        .collect::<Vec<_>>();
+
+    if meshes.len() == 1 {
+        return meshes.pop().map(GeometryEnum::Intersectable);
+    }
+
+    Some(GeometryEnum::Intersectable(build_bounding_box_hierarchy(
+        meshes,
+        &BoundingBoxHierarchyOptions::default(),
+    )))
+}
+
+fn create_material_from_mtl(mtl_material: &MtlMaterial) -> Box<dyn Material> {
+    if mtl_material.emissive_color != Color3::default() {
+        Box::new(EmissiveMaterial::new(Box::new(ConstantTexture::new(
+            &mtl_material.emissive_color,
+        ))))
+    } else if mtl_material.dissolve < 1.0 {
+        Box::new(RefractiveMaterial::new(mtl_material.index_of_refraction))
+    } else {
+        Box::new(LambertianMaterial::new(Box::new(ConstantTexture::new(
+            &mtl_material.diffuse_color,
+        ))))
+    }
+}
+
+fn parse_triangle_mesh_ply(node: &Yaml, context: &mut ParseContext) -> Option<GeometryEnum> {
+    let material_name = node["material"].as_str()?;
+    let mut mesh = load_ply(Path::new(node["plyFile"].as_str()?))?;
+    mesh.buffers.transform(&parse_mesh_transformation(node)?)?;
+
+    let smoothing_angle = parse_real(&node["smoothingAngle"]).unwrap_or(30.0).to_radians();
+
+    Some(GeometryEnum::Intersectable(Box::new(create_triangle_mesh(
+        mesh,
+        Some(smoothing_angle),
+        context.material_index(material_name),
+    ))))
+}
+
+fn parse_triangle_mesh_stl(node: &Yaml, context: &mut ParseContext) -> Option<GeometryEnum> {
+    let material_name = node["material"].as_str()?;
+    let mut mesh = load_stl(Path::new(node["stlFile"].as_str()?))?;
+    mesh.buffers.transform(&parse_mesh_transformation(node)?)?;
+
+    let smoothing_angle = parse_real(&node["smoothingAngle"]).unwrap_or(30.0).to_radians();
+
+    Some(GeometryEnum::Intersectable(Box::new(create_triangle_mesh(
+        mesh,
+        Some(smoothing_angle),
+        context.material_index(material_name),
+    ))))
+}
+
+fn parse_gltf(node: &Yaml, context: &mut ParseContext) -> Option<GeometryEnum> {
+    let material_name = node["material"].as_str()?;
+    let scene = load_gltf(Path::new(node["file"].as_str()?), &parse_mesh_transformation(node)?)?;
+    let import_lights = node["importLights"].as_bool().unwrap_or(true);
+
+    let default_material_index = context.material_index(material_name);
+
+    // Like OBJ materials, materials defined in the scene take precedence over the ones in the file, which are only
+    // known to this file. Unnamed materials can only be the file's own.
+    let material_indices = scene
+        .materials
+        .iter()
+        .map(|material| {
+            material
+                .name
+                .as_deref()
+                .and_then(|name| context.find_material_index(name))
+                .unwrap_or_else(|| {
+                    let created_material = create_material_from_gltf(material, &mut context.texture_cache);
+                    context.add_material(created_material)
+                })
+        })
+        .collect::<Vec<_>>();
+
+    if import_lights {
+        context.lights.extend(scene.lights.iter().map(create_light_from_gltf));
+    }
+
+    let children = scene
+        .meshes
+        .into_iter()
+        .map(|mesh| {
+            let material_index = mesh
+                .material
+                .and_then(|x| material_indices.get(x).copied())
+                .unwrap_or(default_material_index);
+
+            // glTF meshes without normals are meant to be flat shaded.
+            Box::new(create_triangle_mesh(mesh.mesh, None, material_index)) as Box<dyn Intersectable>
+        })
+        .collect();
+
+    Some(GeometryEnum::Intersectable(build_bounding_box_hierarchy(
+        children,
+        &BoundingBoxHierarchyOptions::default(),
+    )))
+}
+
+fn create_material_from_gltf(material: &GltfMaterial, texture_cache: &mut TextureCache) -> Box<dyn Material> {
+    // Only the first set of texture coordinates is loaded, so textures using another set can't be applied.
+    let base_color_image = material
+        .base_color_texture
+        .as_ref()
+        .filter(|x| x.texture_coordinate_set == 0)
+        .and_then(|x| load_gltf_image(&x.image, ColorSpace::Srgb, texture_cache));
+
+    let base_color: Box<dyn Texture> = match base_color_image {
+        Some(mip_map) => {
+            let mut texture = ImageTexture::new(&mip_map, TextureFilter::Trilinear, WrapMode::Repeat);
+            texture.tint = material.base_color;
+
+            Box::new(texture)
+        }
+        None => Box::new(ConstantTexture::new(&material.base_color)),
+    };
+
+    // The metallic-roughness image keeps the roughness in its green channel and the metallic value in its blue one.
+    let metallic_roughness_image = material
+        .metallic_roughness_texture
+        .as_ref()
+        .filter(|x| x.texture_coordinate_set == 0)
+        .and_then(|x| load_gltf_image(&x.image, ColorSpace::Linear, texture_cache));
+
+    let (metallic, roughness): (Box<dyn Texture>, Box<dyn Texture>) = match metallic_roughness_image {
+        Some(mip_map) => {
+            let channel = |channel: usize, factor: Real| {
+                let texture = ImageTexture::new(&mip_map, TextureFilter::Trilinear, WrapMode::Repeat);
+
+                let mut channel_texture = ChannelTexture::new(Box::new(texture), channel);
+                channel_texture.factor = factor;
+
+                Box::new(channel_texture)
+            };
+
+            (channel(2, material.metallic), channel(1, material.roughness))
+        }
+        None => (
+            Box::new(ConstantTexture::from_value(material.metallic)),
+            Box::new(ConstantTexture::from_value(material.roughness)),
+        ),
+    };
+
+    let created_material: Box<dyn Material> = Box::new(MetallicRoughnessMaterial::new(
+        base_color,
+        metallic,
+        roughness,
+        Box::new(ConstantTexture::new(&material.emissive_color)),
+    ));
+
+    let normal_texture = material
+        .normal_texture
+        .as_ref()
+        .filter(|x| x.texture_coordinate_set == 0);
+
+    let normal_image = normal_texture.and_then(|x| load_gltf_image(&x.image, ColorSpace::Linear, texture_cache));
+
+    match (normal_texture, normal_image) {
+        (Some(normal_texture), Some(mip_map)) => {
+            let texture = ImageTexture::new(&mip_map, TextureFilter::Trilinear, WrapMode::Repeat);
+
+            let mut normal_map = NormalMap::new(Box::new(texture));
+            normal_map.strength = normal_texture.scale;
+
+            Box::new(NormalMappedMaterial::new(created_material, Box::new(normal_map)))
+        }
+        _ => created_material,
+    }
+}
+
+/// Loads an image of a glTF file, which stores colors in the sRGB color space and data such as normals in the linear
+/// one. Images in separate files are shared through the texture cache while embedded ones are decoded for every
+/// texture using them.
+fn load_gltf_image(
+    image: &GltfImage,
+    color_space: ColorSpace,
+    texture_cache: &mut TextureCache,
+) -> Option<Arc<MipMap>> {
+    match image {
+        GltfImage::File(path) => texture_cache.load(path, Some(color_space)),
+        GltfImage::Embedded { data, .. } => Some(Arc::new(MipMap::from_memory(data, Some(color_space))?)),
+    }
+}
+
+/// Creates a light from a glTF light. Point and spot lights are given in candela, so their light falls off with the
+/// square of the distance unlike that of the lights defined in the scene.
+fn create_light_from_gltf(light: &GltfLight) -> Box<dyn Light> {
+    match light.light_type {
+        GltfLightType::Directional => Box::new(DirectionalLight::new(&light.color, &light.direction)),
+        GltfLightType::Point => {
+            let mut point_light = PointLight::new(&light.color, &light.position);
+            point_light.inverse_square_falloff = true;
+
+            Box::new(point_light)
+        }
+        GltfLightType::Spot {
+            inner_cone_angle,
+            outer_cone_angle,
+        } => {
+            let mut spot_light = SpotLight::new(
+                &light.color,
+                &light.position,
+                &light.direction,
+                inner_cone_angle,
+                outer_cone_angle,
+            );
+            spot_light.inverse_square_falloff = true;
+
+            Box::new(spot_light)
+        }
+    }
+}
+
+/// Builds a triangle mesh. Meshes without normals get smooth normals generated when given a smoothing angle and are
+/// flat shaded otherwise.
+fn create_triangle_mesh(
+    mut mesh: IndexedMesh,
+    smoothing_angle: Option<Real>,
+    material_index: MaterialIndex,
+) -> TriangleMesh {
+    let normal_indices = if mesh.has_normals() {
+        mesh.indices.iter().copied().map(Some).collect()
+    } else if let Some(smoothing_angle) = smoothing_angle {
+        let (normals, normal_indices) =
+            generate_smooth_normals(&mesh.buffers.positions, &mesh.indices, smoothing_angle);
+
+        mesh.buffers.normals = normals;
+        normal_indices.into_iter().map(Some).collect()
+    } else {
+        vec![None; mesh.indices.len()]
+    };
 
-    Some(GeometryEnum::Intersectable(
-        build_bounding_box_hierarchy_split_by_long_axis(children),
-    ))
+    let buffers = Arc::new(mesh.buffers);
+
+    let triangles = mesh
+        .indices
+        .iter()
+        .zip(normal_indices)
+        .map(|(position_indices, normal_indices)| {
+            // The remaining buffers are indexed by position.
+            let uv_indices = if buffers.uvs.is_empty() {
+                None
+            } else {
+                Some(*position_indices)
+            };
+
+            MeshTriangle::new(*position_indices, normal_indices, uv_indices)
+        })
+        .collect();
+
+    TriangleMesh::new(&buffers, triangles, material_index)
 }
 
+#[allow(clippy::type_complexity, clippy::vec_init_then_push)]
 fn create_bounding_volume_function_map() -> Vec<(&'static str, fn(&Yaml) -> Option<Box<dyn BoundingVolume>>)> {
     let mut map: Vec<(&'static str, fn(&Yaml) -> Option<Box<dyn BoundingVolume>>)> = Vec::new();
 
@@ -1,12 +1,16 @@
-use super::parse_math::{parse_color3, parse_vector3};
-use crate::lights::{directional_light::DirectionalLight, light::Light, point_light::PointLight};
+use super::parse_math::{parse_color3, parse_real, parse_vector3};
+use crate::lights::{
+    directional_light::DirectionalLight, light::Light, point_light::PointLight, spot_light::SpotLight,
+};
 use yaml_rust::Yaml;
 
+#[allow(clippy::type_complexity, clippy::vec_init_then_push)]
 fn create_function_map() -> Vec<(&'static str, fn(&Yaml) -> Option<Box<dyn Light>>)> {
     let mut map: Vec<(&'static str, fn(&Yaml) -> Option<Box<dyn Light>>)> = Vec::new();
 
     map.push(("directional", parse_directional_light));
     map.push(("point", parse_point_light));
+    map.push(("spot", parse_spot_light));
 
     map
 }
@@ -48,3 +52,20 @@ fn parse_point_light(node: &Yaml) -> Option<Box<dyn Light>> {
 
     Some(Box::new(PointLight::new(&color, &position)))
 }
+
+fn parse_spot_light(node: &Yaml) -> Option<Box<dyn Light>> {
+    let color = parse_color3(&node["color"])?;
+    let position = parse_vector3(&node["position"])?;
+    let direction = parse_vector3(&node["direction"])?;
+
+    let outer_cone_angle = parse_real(&node["outerConeAngle"]).unwrap_or(45.0).to_radians();
+    let inner_cone_angle = parse_real(&node["innerConeAngle"]).map_or(outer_cone_angle, |x| x.to_radians());
+
+    Some(Box::new(SpotLight::new(
+        &color,
+        &position,
+        &direction,
+        inner_cone_angle,
+        outer_cone_angle,
+    )))
+}
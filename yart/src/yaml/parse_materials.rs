@@ -1,36 +1,50 @@
-use super::parse_math::parse_color3;
+use super::parse_textures::{parse_bump_map, parse_normal_map, parse_scalar_texture, parse_texture};
 use crate::{
     materials::{
         emissive_material::EmissiveMaterial,
+        holdout_material::HoldoutMaterial,
         lambertian_material::LambertianMaterial,
         material::{Material, MaterialIndex},
+        metallic_roughness_material::MetallicRoughnessMaterial,
+        normal_mapped_material::NormalMappedMaterial,
         phong_material::PhongMaterial,
         reflective_material::ReflectiveMaterial,
         refractive_material::RefractiveMaterial,
+        shadowless_material::ShadowlessMaterial,
     },
     math::color3::Color3,
+    textures::{constant_texture::ConstantTexture, texture_cache::TextureCache},
     yaml::parse_math::parse_real,
 };
 use std::collections::HashMap;
 use yaml_rust::Yaml;
 
-fn create_function_map() -> Vec<(&'static str, fn(&Yaml) -> Option<Box<dyn Material>>)> {
-    let mut map: Vec<(&'static str, fn(&Yaml) -> Option<Box<dyn Material>>)> = Vec::new();
+#[allow(clippy::type_complexity, clippy::vec_init_then_push)]
+fn create_function_map() -> Vec<(&'static str, fn(&Yaml, &mut TextureCache) -> Option<Box<dyn Material>>)> {
+    let mut map: Vec<(&'static str, fn(&Yaml, &mut TextureCache) -> Option<Box<dyn Material>>)> = Vec::new();
 
     map.push(("emissive", parse_emissive));
     map.push(("phong", parse_phong));
     map.push(("reflective", parse_reflective));
     map.push(("refractive", parse_refractive));
     map.push(("lambertian", parse_lambertian));
+    map.push(("holdout", parse_holdout));
+    map.push(("metallicRoughness", parse_metallic_roughness));
 
     map
 }
 
 fn create_default_material() -> Box<dyn Material> {
-    Box::new(EmissiveMaterial::new(&Color3::from_value(0.0)))
+    Box::new(EmissiveMaterial::new(Box::new(ConstantTexture::new(
+        &Color3::from_value(0.0),
+    ))))
 }
 
-pub fn parse_materials(node: &Yaml) -> Option<(Vec<Box<dyn Material>>, HashMap<String, MaterialIndex>)> {
+#[allow(clippy::type_complexity)]
+pub fn parse_materials(
+    node: &Yaml,
+    texture_cache: &mut TextureCache,
+) -> Option<(Vec<Box<dyn Material>>, HashMap<String, MaterialIndex>)> {
     let mut materials = Vec::new();
     let mut material_name_to_index_map = HashMap::new();
 
@@ -38,7 +52,7 @@ pub fn parse_materials(node: &Yaml) -> Option<(Vec<Box<dyn Material>>, HashMap<S
 
     if !node.is_badvalue() && node.is_array() {
         for child_node in node.as_vec()? {
-            let (name, material) = parse_material(child_node)?;
+            let (name, material) = parse_material(child_node, texture_cache)?;
 
             material_name_to_index_map.insert(name, materials.len() as MaterialIndex);
             materials.push(material);
@@ -48,14 +62,30 @@ pub fn parse_materials(node: &Yaml) -> Option<(Vec<Box<dyn Material>>, HashMap<S
     Some((materials, material_name_to_index_map))
 }
 
-fn parse_material(node: &Yaml) -> Option<(String, Box<dyn Material>)> {
+fn parse_material(node: &Yaml, texture_cache: &mut TextureCache) -> Option<(String, Box<dyn Material>)> {
     for (name, function) in create_function_map() {
         let child_node = &node[name];
 
         if !child_node.is_badvalue() {
-            let material = function(child_node)?;
+            let mut material = function(child_node, texture_cache)?;
             let name = child_node["name"].as_str()?.to_string();
 
+            // A material's normal is perturbed by either a normal map or a bump map, never both.
+            let normal_perturbation = match (&child_node["normalMap"], &child_node["bumpMap"]) {
+                (Yaml::BadValue, Yaml::BadValue) => None,
+                (normal_map, Yaml::BadValue) => Some(parse_normal_map(normal_map, texture_cache)?),
+                (Yaml::BadValue, bump_map) => Some(parse_bump_map(bump_map, texture_cache)?),
+                _ => return None,
+            };
+
+            if let Some(normal_perturbation) = normal_perturbation {
+                material = Box::new(NormalMappedMaterial::new(material, normal_perturbation));
+            }
+
+            if !child_node["castsShadows"].as_bool().unwrap_or(true) {
+                material = Box::new(ShadowlessMaterial::new(material));
+            }
+
             return Some((name, material));
         }
     }
@@ -63,39 +93,62 @@ fn parse_material(node: &Yaml) -> Option<(String, Box<dyn Material>)> {
     None
 }
 
-fn parse_emissive(node: &Yaml) -> Option<Box<dyn Material>> {
-    let emissive_color = parse_color3(&node["emissiveColor"])?;
+fn parse_emissive(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn Material>> {
+    let emissive_color = parse_texture(&node["emissiveColor"], texture_cache)?;
 
-    Some(Box::new(EmissiveMaterial::new(&emissive_color)))
+    Some(Box::new(EmissiveMaterial::new(emissive_color)))
 }
 
-fn parse_phong(node: &Yaml) -> Option<Box<dyn Material>> {
-    let ambient_color = parse_color3(&node["ambientColor"])?;
-    let diffuse_color = parse_color3(&node["diffuseColor"])?;
-    let specular_color = parse_color3(&node["specularColor"])?;
+fn parse_phong(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn Material>> {
+    let ambient_color = parse_texture(&node["ambientColor"], texture_cache)?;
+    let diffuse_color = parse_texture(&node["diffuseColor"], texture_cache)?;
+    let specular_color = parse_texture(&node["specularColor"], texture_cache)?;
 
-    let shininess = parse_real(&node["shininess"])?;
+    let shininess = parse_scalar_texture(&node["shininess"], texture_cache)?;
 
     Some(Box::new(PhongMaterial::new(
-        &ambient_color,
-        &diffuse_color,
-        &specular_color,
+        ambient_color,
+        diffuse_color,
+        specular_color,
         shininess,
     )))
 }
 
-fn parse_reflective(_node: &Yaml) -> Option<Box<dyn Material>> {
+fn parse_reflective(_node: &Yaml, _texture_cache: &mut TextureCache) -> Option<Box<dyn Material>> {
     Some(Box::new(ReflectiveMaterial::new()))
 }
 
-fn parse_refractive(node: &Yaml) -> Option<Box<dyn Material>> {
+fn parse_refractive(node: &Yaml, _texture_cache: &mut TextureCache) -> Option<Box<dyn Material>> {
     let refractive_index = parse_real(&node["refractiveIndex"])?;
 
     Some(Box::new(RefractiveMaterial::new(refractive_index)))
 }
 
-fn parse_lambertian(node: &Yaml) -> Option<Box<dyn Material>> {
-    let diffuse_color = parse_color3(&node["diffuseColor"])?;
+fn parse_lambertian(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn Material>> {
+    let diffuse_color = parse_texture(&node["diffuseColor"], texture_cache)?;
+
+    Some(Box::new(LambertianMaterial::new(diffuse_color)))
+}
 
-    Some(Box::new(LambertianMaterial::new(&diffuse_color)))
+fn parse_holdout(node: &Yaml, _texture_cache: &mut TextureCache) -> Option<Box<dyn Material>> {
+    let shadow_catcher = node["shadowCatcher"].as_bool().unwrap_or(false);
+
+    Some(Box::new(HoldoutMaterial::new(shadow_catcher)))
+}
+
+fn parse_metallic_roughness(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn Material>> {
+    let base_color = parse_texture(&node["baseColor"], texture_cache)?;
+    let metallic = parse_scalar_texture(&node["metallic"], texture_cache)
+        .unwrap_or_else(|| Box::new(ConstantTexture::from_value(0.0)));
+    let roughness = parse_scalar_texture(&node["roughness"], texture_cache)
+        .unwrap_or_else(|| Box::new(ConstantTexture::from_value(0.5)));
+    let emissive_color = parse_texture(&node["emissiveColor"], texture_cache)
+        .unwrap_or_else(|| Box::new(ConstantTexture::new(&Color3::default())));
+
+    Some(Box::new(MetallicRoughnessMaterial::new(
+        base_color,
+        metallic,
+        roughness,
+        emissive_color,
+    )))
 }
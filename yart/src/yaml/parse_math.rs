@@ -1,6 +1,10 @@
 use crate::{
     common::Real,
-    math::{color3::Color3, color4::Color4, vector2::Vector2, vector3::Vector3, vector4::Vector4},
+    math::{
+        color3::Color3, color4::Color4, matrix4::Matrix4, quaternion::Quaternion, vector2::Vector2, vector3::Vector3,
+        vector4::Vector4,
+    },
+    normalize,
 };
 use yaml_rust::Yaml;
 
@@ -46,6 +50,12 @@ pub fn parse_vector4(node: &Yaml) -> Option<Vector4> {
 }
 
 pub fn parse_vector3(node: &Yaml) -> Option<Vector3> {
+    let normalize_node = &node["normalize"];
+
+    if !normalize_node.is_badvalue() {
+        return Some(normalize!(parse_vector3(normalize_node)?));
+    }
+
     if !node.is_array() {
         return None;
     }
@@ -140,3 +150,62 @@ pub fn parse_color3(node: &Yaml) -> Option<Color3> {
         _ => None,
     }
 }
+
+/// Parses a transformation that is either given as a row major `matrix` or built from a list of steps. Each step
+/// is applied after the previous ones, so `build: [scale, rotate, translate]` scales, then rotates and then
+/// translates. Rotations are angles in degrees around the x, y and z axes.
+pub fn parse_transformation(node: &Yaml) -> Option<Matrix4> {
+    let matrix_node = &node["matrix"];
+
+    if !matrix_node.is_badvalue() {
+        return parse_matrix4(matrix_node);
+    }
+
+    let mut transformation = Matrix4::identity();
+
+    for step in node["build"].as_vec()? {
+        let step_matrix = if let Some(scale) = parse_vector3(&step["scale"]) {
+            Matrix4::from_scale(&scale)
+        } else if let Some(rotation) = parse_vector3(&step["rotate"]) {
+            let radians = Vector3::new(
+                rotation.x.to_radians(),
+                rotation.y.to_radians(),
+                rotation.z.to_radians(),
+            );
+            Matrix4::from_rotation(&Quaternion::from_euler_angles(&radians))
+        } else if let Some(translation) = parse_vector3(&step["translate"]) {
+            Matrix4::from_translation(&translation)
+        } else {
+            parse_matrix4(&step["matrix"])?
+        };
+
+        transformation = step_matrix * transformation;
+    }
+
+    Some(transformation)
+}
+
+/// Parses a transformation that may be left out, in which case the identity transformation is returned.
+pub fn parse_optional_transformation(node: &Yaml) -> Option<Matrix4> {
+    if node.is_badvalue() {
+        Some(Matrix4::identity())
+    } else {
+        parse_transformation(node)
+    }
+}
+
+fn parse_matrix4(node: &Yaml) -> Option<Matrix4> {
+    let values = node.as_vec()?.iter().map(parse_real).collect::<Option<Vec<_>>>()?;
+
+    if values.len() != 16 {
+        return None;
+    }
+
+    let mut matrix = Matrix4::identity();
+
+    for (i, value) in values.iter().enumerate() {
+        matrix.m[i / 4][i % 4] = *value;
+    }
+
+    Some(matrix)
+}
@@ -1,29 +1,197 @@
-use super::parse_math::parse_color3;
-use crate::miss_shaders::{constant_miss_shader::ConstantMissShader, miss_shader::MissShader};
+use super::parse_math::{parse_color3, parse_real, parse_u32, parse_vector2u32, parse_vector3};
+use crate::{
+    common::Real,
+    geometries::area_light::AreaLight,
+    lights::sun_light::SunLight,
+    miss_shaders::{
+        atmosphere_miss_shader::AtmosphereMissShader, checker_miss_shader::CheckerMissShader,
+        constant_miss_shader::ConstantMissShader, environment_map_miss_shader::EnvironmentMapMissShader,
+        gradient_miss_shader::GradientMissShader, grid_miss_shader::GridMissShader, miss_shader::MissShader,
+        preetham_sky_miss_shader::PreethamSkyMissShader, transparent_miss_shader::TransparentMissShader,
+    },
+};
+use std::path::Path;
 use yaml_rust::Yaml;
 
-fn create_function_map() -> Vec<(&'static str, fn(&Yaml) -> Option<Box<dyn MissShader>>)> {
-    let mut map: Vec<(&'static str, fn(&Yaml) -> Option<Box<dyn MissShader>>)> = Vec::new();
+#[allow(clippy::type_complexity, clippy::vec_init_then_push)]
+fn create_function_map() -> Vec<(
+    &'static str,
+    fn(&Yaml, &mut Vec<Box<dyn AreaLight>>) -> Option<Box<dyn MissShader>>,
+)> {
+    let mut map: Vec<(
+        &'static str,
+        fn(&Yaml, &mut Vec<Box<dyn AreaLight>>) -> Option<Box<dyn MissShader>>,
+    )> = Vec::new();
 
     map.push(("constant", parse_constant_miss_shader));
+    map.push(("environmentMap", parse_environment_map_miss_shader));
+    map.push(("atmosphere", parse_atmosphere_miss_shader));
+    map.push(("preethamSky", parse_preetham_sky_miss_shader));
+    map.push(("gradient", parse_gradient_miss_shader));
+    map.push(("checker", parse_checker_miss_shader));
+    map.push(("grid", parse_grid_miss_shader));
+    map.push(("transparent", parse_transparent_miss_shader));
 
     map
 }
 
-pub fn parse_miss_shader(node: &Yaml) -> Option<Box<dyn MissShader>> {
+pub fn parse_miss_shader(node: &Yaml, area_lights: &mut Vec<Box<dyn AreaLight>>) -> Option<Box<dyn MissShader>> {
     for (name, function) in create_function_map() {
         let child_node = &node[name];
 
         if !child_node.is_badvalue() {
-            return function(child_node);
+            return function(child_node, area_lights);
         }
     }
 
     None
 }
 
-fn parse_constant_miss_shader(node: &Yaml) -> Option<Box<dyn MissShader>> {
+fn parse_constant_miss_shader(node: &Yaml, _area_lights: &mut Vec<Box<dyn AreaLight>>) -> Option<Box<dyn MissShader>> {
     let color = parse_color3(&node["color"])?;
 
     Some(Box::new(ConstantMissShader::new(&color)))
 }
+
+fn parse_environment_map_miss_shader(
+    node: &Yaml,
+    area_lights: &mut Vec<Box<dyn AreaLight>>,
+) -> Option<Box<dyn MissShader>> {
+    let file = node["file"].as_str()?;
+
+    let rotation = parse_real(&node["rotation"]).unwrap_or(0.0).to_radians();
+    let intensity = parse_real(&node["intensity"]).unwrap_or(1.0);
+    let area_light = node["areaLight"].as_bool().unwrap_or(false);
+
+    let miss_shader = Box::new(EnvironmentMapMissShader::from_file(
+        Path::new(file),
+        rotation,
+        intensity,
+    )?);
+
+    if area_light {
+        area_lights.push(miss_shader.clone());
+    }
+
+    Some(miss_shader)
+}
+
+fn parse_sun(node: &Yaml, area_lights: &mut Vec<Box<dyn AreaLight>>) -> Option<SunLight> {
+    // The sun direction is the direction the sun light travels in.
+    let sun_direction = parse_vector3(&node["sunDirection"])?;
+
+    // Default to the apparent angular radius of the sun as seen from the earth.
+    let sun_angular_radius = parse_real(&node["sunAngularRadius"])
+        .map(|x| x.to_radians())
+        .unwrap_or(0.00465 as Real);
+
+    let area_light = node["areaLight"].as_bool().unwrap_or(false);
+
+    let sun = SunLight::new(&-sun_direction, sun_angular_radius);
+
+    if area_light {
+        area_lights.push(Box::new(sun));
+    }
+
+    Some(sun)
+}
+
+fn parse_atmosphere_miss_shader(node: &Yaml, area_lights: &mut Vec<Box<dyn AreaLight>>) -> Option<Box<dyn MissShader>> {
+    let offset = parse_vector3(&node["offset"]).unwrap_or_default();
+    let sun_intensity = parse_color3(&node["sunIntensity"])?;
+
+    // The number of samples taken along each view ray.
+    let sample_count = parse_u32(&node["lambda"]).unwrap_or(16);
+
+    let planet_radius = parse_real(&node["planetRadius"])?;
+    let atmosphere_height = parse_real(&node["atmosphereHeight"])?;
+    let rayleigh_scale_height = parse_real(&node["rayleighScaleHeight"])?;
+    let mie_scale_height = parse_real(&node["mieScaleHeight"])?;
+    let index_of_refraction_at_sea_level = parse_real(&node["indexOfRefractionAtSeaLevel"])?;
+    let number_density_of_air_at_sea_level = parse_real(&node["numberDensityOfAirAtSeaLevel"])?;
+    let number_density_of_aerosols_at_sea_level = parse_real(&node["numberDensityOfAerosolsAtSeaLevel"])?;
+    let mie_u = parse_real(&node["mieU"])?;
+
+    let sun = parse_sun(node, area_lights)?;
+
+    Some(Box::new(AtmosphereMissShader::new(
+        &offset,
+        &sun,
+        &sun_intensity,
+        sample_count,
+        planet_radius,
+        atmosphere_height,
+        rayleigh_scale_height,
+        mie_scale_height,
+        index_of_refraction_at_sea_level,
+        number_density_of_air_at_sea_level,
+        number_density_of_aerosols_at_sea_level,
+        mie_u,
+    )))
+}
+
+fn parse_preetham_sky_miss_shader(
+    node: &Yaml,
+    area_lights: &mut Vec<Box<dyn AreaLight>>,
+) -> Option<Box<dyn MissShader>> {
+    let sun_intensity = parse_color3(&node["sunIntensity"])?;
+    let turbidity = parse_real(&node["turbidity"]).unwrap_or(2.5);
+    let intensity = parse_real(&node["intensity"]).unwrap_or(1.0);
+
+    let sun = parse_sun(node, area_lights)?;
+
+    Some(Box::new(PreethamSkyMissShader::new(
+        &sun,
+        &sun_intensity,
+        turbidity,
+        intensity,
+    )))
+}
+
+fn parse_gradient_miss_shader(node: &Yaml, _area_lights: &mut Vec<Box<dyn AreaLight>>) -> Option<Box<dyn MissShader>> {
+    let horizon_color = parse_color3(&node["horizonColor"])?;
+    let zenith_color = parse_color3(&node["zenithColor"])?;
+    let ground_color = parse_color3(&node["groundColor"]).unwrap_or(horizon_color);
+    let exponent = parse_real(&node["exponent"]).unwrap_or(1.0);
+
+    Some(Box::new(GradientMissShader::new(
+        &horizon_color,
+        &zenith_color,
+        &ground_color,
+        exponent,
+    )))
+}
+
+fn parse_checker_miss_shader(node: &Yaml, _area_lights: &mut Vec<Box<dyn AreaLight>>) -> Option<Box<dyn MissShader>> {
+    let color1 = parse_color3(&node["color1"])?;
+    let color2 = parse_color3(&node["color2"])?;
+    let divisions = parse_vector2u32(&node["divisions"]).unwrap_or((16, 8));
+
+    Some(Box::new(CheckerMissShader::new(&color1, &color2, divisions)))
+}
+
+fn parse_grid_miss_shader(node: &Yaml, _area_lights: &mut Vec<Box<dyn AreaLight>>) -> Option<Box<dyn MissShader>> {
+    let background_color = parse_color3(&node["backgroundColor"])?;
+    let line_color = parse_color3(&node["lineColor"])?;
+    let axis_color = parse_color3(&node["axisColor"]).unwrap_or(line_color);
+
+    let spacing = parse_real(&node["spacing"]).unwrap_or(15.0).to_radians();
+    let line_width = parse_real(&node["lineWidth"]).unwrap_or(0.5).to_radians();
+
+    Some(Box::new(GridMissShader::new(
+        &background_color,
+        &line_color,
+        &axis_color,
+        spacing,
+        line_width,
+    )))
+}
+
+fn parse_transparent_miss_shader(
+    node: &Yaml,
+    _area_lights: &mut Vec<Box<dyn AreaLight>>,
+) -> Option<Box<dyn MissShader>> {
+    let color = parse_color3(&node["color"]).unwrap_or_default();
+
+    Some(Box::new(TransparentMissShader::new(&color)))
+}
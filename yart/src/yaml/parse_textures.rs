@@ -0,0 +1,351 @@
+use super::parse_math::{parse_color3, parse_real, parse_vector2, parse_vector3};
+use crate::{
+    common::Real,
+    math::{noise::NoiseOctaves, vector3::Vector3},
+    textures::{
+        bump_map::BumpMap,
+        checker_texture::CheckerTexture,
+        constant_texture::ConstantTexture,
+        gradient_texture::GradientTexture,
+        image_texture::{ImageTexture, TextureFilter},
+        marble_texture::MarbleTexture,
+        mip_map::{ColorSpace, WrapMode},
+        noise_texture::{NoiseKind, NoiseTexture},
+        normal_map::NormalMap,
+        normal_perturbation::NormalPerturbation,
+        texture::Texture,
+        texture_cache::TextureCache,
+        texture_coordinates::TextureSpace,
+        vertex_color_texture::VertexColorTexture,
+        voronoi_texture::{VoronoiPattern, VoronoiTexture},
+        wood_texture::WoodTexture,
+    },
+};
+use std::path::Path;
+use yaml_rust::Yaml;
+
+#[allow(clippy::type_complexity, clippy::vec_init_then_push)]
+fn create_function_map() -> Vec<(&'static str, fn(&Yaml, &mut TextureCache) -> Option<Box<dyn Texture>>)> {
+    let mut map: Vec<(&'static str, fn(&Yaml, &mut TextureCache) -> Option<Box<dyn Texture>>)> = Vec::new();
+
+    map.push(("checker", parse_checker_texture));
+    map.push(("noise", parse_noise_texture));
+    map.push(("turbulence", parse_turbulence_texture));
+    map.push(("marble", parse_marble_texture));
+    map.push(("wood", parse_wood_texture));
+    map.push(("voronoi", parse_voronoi_texture));
+    map.push(("gradient", parse_gradient_texture));
+    map.push(("vertexColor", parse_vertex_color_texture));
+
+    map
+}
+
+/// Parses a color parameter of a material, which is either a constant color, an image texture or a procedural
+/// texture:
+///
+/// ```yaml
+/// diffuseColor: [0.8, 0.8, 0.8]
+/// diffuseColor:
+///   texture: wood.png
+///   colorSpace: srgb      # or linear, guessed from the image format by default
+///   filter: trilinear     # or bilinear
+///   wrap: repeat          # or clamp or mirror
+///   scale: [4, 4]
+///   offset: [0, 0]
+///   tint: [1, 1, 1]
+/// diffuseColor:
+///   checker:
+///     color1: [0.9, 0.9, 0.9]   # black by default
+///     color2: [0.1, 0.1, 0.1]   # white by default
+///     space: uv                 # or world or object
+///     scale: 8
+/// ```
+///
+/// The colors of procedural textures are textures themselves, so patterns can be nested. Besides `checker` there are
+/// `noise` and `turbulence` with `octaves`, `lacunarity` and `gain`, `marble` and `wood` which also take a
+/// `turbulence` strength, `voronoi` with a `pattern` of `cells`, `distance` or `edges` and an `edgeWidth`, and
+/// `gradient` with `start` and `end` points. Checkers and gradients are evaluated in texture space by default and the
+/// others in object space. Finally `vertexColor` uses the vertex colors of meshes that have them and its `fallback`,
+/// which is white by default, everywhere else.
+pub fn parse_texture(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn Texture>> {
+    parse_texture_in_color_space(node, texture_cache, None)
+}
+
+/// Parses a texture like [`parse_texture`], but reads images that don't name their color space as
+/// `default_color_space` rather than guessing it from their format.
+fn parse_texture_in_color_space(
+    node: &Yaml,
+    texture_cache: &mut TextureCache,
+    default_color_space: Option<ColorSpace>,
+) -> Option<Box<dyn Texture>> {
+    if let Some(color) = parse_color3(node) {
+        return Some(Box::new(ConstantTexture::new(&color)));
+    }
+
+    // Image textures keep their options next to the file name rather than in a child node.
+    if !node["texture"].is_badvalue() {
+        return parse_image_texture(node, texture_cache, default_color_space);
+    }
+
+    for (name, function) in create_function_map() {
+        let child_node = &node[name];
+
+        if !child_node.is_badvalue() {
+            return function(child_node, texture_cache);
+        }
+    }
+
+    None
+}
+
+/// Parses a scalar parameter of a material, which is either a number or a texture that is evaluated as a scalar.
+pub fn parse_scalar_texture(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn Texture>> {
+    if let Some(value) = parse_real(node) {
+        return Some(Box::new(ConstantTexture::from_value(value)));
+    }
+
+    parse_texture(node, texture_cache)
+}
+
+/// Parses the normal map of a material, which is a texture holding normals with an optional `strength`:
+///
+/// ```yaml
+/// normalMap:
+///   texture: bricks-normal.png    # linear unless colorSpace says otherwise
+///   strength: 1
+/// ```
+pub fn parse_normal_map(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn NormalPerturbation>> {
+    let texture = parse_texture_in_color_space(node, texture_cache, Some(ColorSpace::Linear))?;
+    let mut normal_map = NormalMap::new(texture);
+
+    if let Some(strength) = parse_real(&node["strength"]) {
+        normal_map.strength = strength;
+    }
+
+    Some(Box::new(normal_map))
+}
+
+/// Parses the bump map of a material, which is a texture holding heights with an optional `strength` that is the
+/// distance a height of one displaces the surface by. A material can't have both a bump map and a normal map:
+///
+/// ```yaml
+/// bumpMap:
+///   noise:
+///     scale: 20
+///   strength: 0.01
+/// ```
+pub fn parse_bump_map(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn NormalPerturbation>> {
+    let height = parse_texture_in_color_space(node, texture_cache, Some(ColorSpace::Linear))?;
+    let mut bump_map = BumpMap::new(height);
+
+    if let Some(strength) = parse_real(&node["strength"]) {
+        bump_map.strength = strength;
+    }
+
+    Some(Box::new(bump_map))
+}
+
+fn parse_image_texture(
+    node: &Yaml,
+    texture_cache: &mut TextureCache,
+    default_color_space: Option<ColorSpace>,
+) -> Option<Box<dyn Texture>> {
+    let file = node["texture"].as_str()?;
+
+    let color_space = match node["colorSpace"].as_str() {
+        Some("srgb") => Some(ColorSpace::Srgb),
+        Some("linear") => Some(ColorSpace::Linear),
+        None => default_color_space,
+        Some(_) => return None,
+    };
+
+    let filter = match node["filter"].as_str() {
+        Some("bilinear") => TextureFilter::Bilinear,
+        Some("trilinear") | None => TextureFilter::Trilinear,
+        Some(_) => return None,
+    };
+
+    let wrap_mode = match node["wrap"].as_str() {
+        Some("repeat") | None => WrapMode::Repeat,
+        Some("clamp") => WrapMode::Clamp,
+        Some("mirror") => WrapMode::Mirror,
+        Some(_) => return None,
+    };
+
+    let mip_map = texture_cache.load(Path::new(file), color_space)?;
+    let mut texture = ImageTexture::new(&mip_map, filter, wrap_mode);
+
+    if let Some(scale) = parse_vector2(&node["scale"]) {
+        texture.scale = scale;
+    }
+
+    if let Some(offset) = parse_vector2(&node["offset"]) {
+        texture.offset = offset;
+    }
+
+    if let Some(tint) = parse_color3(&node["tint"]) {
+        texture.tint = tint;
+    }
+
+    Some(Box::new(texture))
+}
+
+/// Parses the two textures a procedural texture blends between, which default to black and white.
+fn parse_colors(node: &Yaml, texture_cache: &mut TextureCache) -> Option<(Box<dyn Texture>, Box<dyn Texture>)> {
+    let mut parse_color = |child_node: &Yaml, default: Real| -> Option<Box<dyn Texture>> {
+        if child_node.is_badvalue() {
+            Some(Box::new(ConstantTexture::from_value(default)))
+        } else {
+            parse_texture(child_node, texture_cache)
+        }
+    };
+
+    Some((parse_color(&node["color1"], 0.0)?, parse_color(&node["color2"], 1.0)?))
+}
+
+fn parse_texture_space(node: &Yaml, default: TextureSpace) -> Option<TextureSpace> {
+    match node.as_str() {
+        Some("uv") => Some(TextureSpace::Uv),
+        Some("world") => Some(TextureSpace::World),
+        Some("object") => Some(TextureSpace::Object),
+        None => Some(default),
+        Some(_) => None,
+    }
+}
+
+fn parse_octaves(node: &Yaml) -> Option<NoiseOctaves> {
+    let mut octaves = NoiseOctaves::default();
+
+    if !node["octaves"].is_badvalue() {
+        octaves.count = u32::try_from(node["octaves"].as_i64()?).ok()?;
+    }
+
+    if let Some(lacunarity) = parse_real(&node["lacunarity"]) {
+        octaves.lacunarity = lacunarity;
+    }
+
+    if let Some(gain) = parse_real(&node["gain"]) {
+        octaves.gain = gain;
+    }
+
+    Some(octaves)
+}
+
+fn parse_checker_texture(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn Texture>> {
+    let (color1, color2) = parse_colors(node, texture_cache)?;
+    let space = parse_texture_space(&node["space"], TextureSpace::Uv)?;
+
+    let mut texture = CheckerTexture::new(color1, color2, space);
+
+    if let Some(scale) = parse_real(&node["scale"]) {
+        texture.scale = scale;
+    }
+
+    Some(Box::new(texture))
+}
+
+fn parse_noise_texture(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn Texture>> {
+    parse_fractal_noise_texture(node, texture_cache, NoiseKind::Fbm)
+}
+
+fn parse_turbulence_texture(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn Texture>> {
+    parse_fractal_noise_texture(node, texture_cache, NoiseKind::Turbulence)
+}
+
+fn parse_fractal_noise_texture(
+    node: &Yaml,
+    texture_cache: &mut TextureCache,
+    kind: NoiseKind,
+) -> Option<Box<dyn Texture>> {
+    let (color1, color2) = parse_colors(node, texture_cache)?;
+    let space = parse_texture_space(&node["space"], TextureSpace::Object)?;
+
+    let mut texture = NoiseTexture::new(color1, color2, kind, space);
+    texture.octaves = parse_octaves(node)?;
+
+    if let Some(scale) = parse_real(&node["scale"]) {
+        texture.scale = scale;
+    }
+
+    Some(Box::new(texture))
+}
+
+fn parse_marble_texture(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn Texture>> {
+    let (color1, color2) = parse_colors(node, texture_cache)?;
+    let space = parse_texture_space(&node["space"], TextureSpace::Object)?;
+
+    let mut texture = MarbleTexture::new(color1, color2, space);
+    texture.octaves = parse_octaves(node)?;
+
+    if let Some(scale) = parse_real(&node["scale"]) {
+        texture.scale = scale;
+    }
+
+    if let Some(turbulence) = parse_real(&node["turbulence"]) {
+        texture.turbulence = turbulence;
+    }
+
+    Some(Box::new(texture))
+}
+
+fn parse_wood_texture(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn Texture>> {
+    let (color1, color2) = parse_colors(node, texture_cache)?;
+    let space = parse_texture_space(&node["space"], TextureSpace::Object)?;
+
+    let mut texture = WoodTexture::new(color1, color2, space);
+    texture.octaves = parse_octaves(node)?;
+
+    if let Some(scale) = parse_real(&node["scale"]) {
+        texture.scale = scale;
+    }
+
+    if let Some(turbulence) = parse_real(&node["turbulence"]) {
+        texture.turbulence = turbulence;
+    }
+
+    Some(Box::new(texture))
+}
+
+fn parse_voronoi_texture(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn Texture>> {
+    let (color1, color2) = parse_colors(node, texture_cache)?;
+    let space = parse_texture_space(&node["space"], TextureSpace::Object)?;
+
+    let pattern = match node["pattern"].as_str() {
+        Some("cells") | None => VoronoiPattern::Cells,
+        Some("distance") => VoronoiPattern::Distance,
+        Some("edges") => VoronoiPattern::Edges,
+        Some(_) => return None,
+    };
+
+    let mut texture = VoronoiTexture::new(color1, color2, pattern, space);
+
+    if let Some(scale) = parse_real(&node["scale"]) {
+        texture.scale = scale;
+    }
+
+    if let Some(edge_width) = parse_real(&node["edgeWidth"]) {
+        texture.edge_width = edge_width;
+    }
+
+    Some(Box::new(texture))
+}
+
+fn parse_gradient_texture(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn Texture>> {
+    let (color1, color2) = parse_colors(node, texture_cache)?;
+    let space = parse_texture_space(&node["space"], TextureSpace::Uv)?;
+
+    let start = parse_vector3(&node["start"]).unwrap_or_default();
+    let end = parse_vector3(&node["end"]).unwrap_or_else(|| Vector3::new(1.0, 0.0, 0.0));
+
+    Some(Box::new(GradientTexture::new(color1, color2, space, &start, &end)))
+}
+
+fn parse_vertex_color_texture(node: &Yaml, texture_cache: &mut TextureCache) -> Option<Box<dyn Texture>> {
+    let fallback: Box<dyn Texture> = if node["fallback"].is_badvalue() {
+        Box::new(ConstantTexture::from_value(1.0))
+    } else {
+        parse_texture(&node["fallback"], texture_cache)?
+    };
+
+    Some(Box::new(VertexColorTexture::new(fallback)))
+}